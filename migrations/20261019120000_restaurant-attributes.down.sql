@@ -0,0 +1,4 @@
+drop table restaurant_service_modes;
+drop table restaurant_tags;
+
+alter table restaurants drop column price_tier;
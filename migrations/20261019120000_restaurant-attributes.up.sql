@@ -0,0 +1,32 @@
+alter table restaurants add column price_tier integer check (
+    price_tier between 1 and 4
+);
+
+create table restaurant_tags
+(
+    restaurant_id integer not null,
+    user_id integer not null,
+    tag text not null check (
+        length(tag) = length(trim(tag))
+        and length(tag) > 0
+        and tag = lower(tag)
+        and instr(tag, ',') = 0
+    ),
+    foreign key (restaurant_id, user_id) references restaurants (
+        id, user_id
+    ) on delete cascade,
+    primary key (restaurant_id, tag)
+);
+
+create table restaurant_service_modes
+(
+    restaurant_id integer not null,
+    user_id integer not null,
+    service_mode text not null check (
+        service_mode in ('dine-in', 'takeout', 'delivery')
+    ),
+    foreign key (restaurant_id, user_id) references restaurants (
+        id, user_id
+    ) on delete cascade,
+    primary key (restaurant_id, service_mode)
+);
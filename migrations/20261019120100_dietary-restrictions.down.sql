@@ -0,0 +1,4 @@
+drop view if exists restaurants_view;
+
+drop table restaurant_dietary_accommodations;
+drop table homies_dietary_restrictions;
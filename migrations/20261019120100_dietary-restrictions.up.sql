@@ -0,0 +1,64 @@
+create table homies_dietary_restrictions
+(
+    homie_id integer not null,
+    user_id integer not null,
+    restriction text not null check (
+        restriction in (
+            'vegetarian',
+            'vegan',
+            'gluten-free',
+            'nut-free',
+            'dairy-free',
+            'halal',
+            'kosher'
+        )
+    ),
+    foreign key (homie_id, user_id) references homies (
+        id, user_id
+    ) on delete cascade,
+    primary key (homie_id, restriction)
+);
+
+create table restaurant_dietary_accommodations
+(
+    restaurant_id integer not null,
+    user_id integer not null,
+    restriction text not null check (
+        restriction in (
+            'vegetarian',
+            'vegan',
+            'gluten-free',
+            'nut-free',
+            'dairy-free',
+            'halal',
+            'kosher'
+        )
+    ),
+    foreign key (restaurant_id, user_id) references restaurants (
+        id, user_id
+    ) on delete cascade,
+    primary key (restaurant_id, restriction)
+);
+
+create view restaurants_view as
+select
+    r.id,
+    r.user_id,
+    r.name,
+    r.price_tier,
+    (
+        select group_concat(t.tag)
+        from restaurant_tags as t
+        where t.restaurant_id = r.id
+    ) as tags,
+    (
+        select group_concat(m.service_mode)
+        from restaurant_service_modes as m
+        where m.restaurant_id = r.id
+    ) as service_modes,
+    (
+        select group_concat(a.restriction)
+        from restaurant_dietary_accommodations as a
+        where a.restaurant_id = r.id
+    ) as accommodations
+from restaurants as r;
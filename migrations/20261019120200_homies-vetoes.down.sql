@@ -0,0 +1 @@
+drop table homies_vetoed_restaurants;
@@ -0,0 +1,14 @@
+create table homies_vetoed_restaurants
+(
+    homie_id integer not null,
+    restaurant_id integer not null,
+    user_id integer not null,
+    created_at timestamp not null default current_timestamp,
+    foreign key (restaurant_id, user_id) references restaurants (
+        id, user_id
+    ) on delete cascade,
+    foreign key (homie_id, user_id) references homies (
+        id, user_id
+    ) on delete cascade,
+    primary key (homie_id, restaurant_id)
+);
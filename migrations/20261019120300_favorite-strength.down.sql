@@ -0,0 +1 @@
+alter table homies_favorite_restaurants drop column strength;
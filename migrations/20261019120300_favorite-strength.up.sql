@@ -0,0 +1,4 @@
+-- 1 = it's fine, 2 = like it, 3 = love it
+alter table homies_favorite_restaurants add column strength integer not null default 2 check (
+    strength between 1 and 3
+);
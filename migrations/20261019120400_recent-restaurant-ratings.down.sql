@@ -0,0 +1 @@
+drop table recent_restaurant_ratings;
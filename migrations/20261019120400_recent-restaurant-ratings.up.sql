@@ -0,0 +1,14 @@
+create table recent_restaurant_ratings
+(
+    homie_id integer not null,
+    restaurant_id integer not null,
+    date date not null,
+    user_id integer not null,
+    rating integer not null check (rating between 1 and 5),
+    note text check (note is null or length(trim(note)) > 0),
+    created_at timestamp not null default current_timestamp,
+    foreign key (homie_id, restaurant_id, date) references recent_restaurants (
+        homie_id, restaurant_id, date
+    ) on delete cascade,
+    primary key (homie_id, restaurant_id, date)
+);
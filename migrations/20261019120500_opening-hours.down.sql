@@ -0,0 +1,2 @@
+drop table restaurant_hours_exceptions;
+drop table restaurant_opening_hours;
@@ -0,0 +1,28 @@
+create table restaurant_opening_hours
+(
+    restaurant_id integer not null,
+    user_id integer not null,
+    weekday integer not null check (weekday between 0 and 6),
+    opens text not null,
+    closes text not null check (closes > opens),
+    foreign key (restaurant_id, user_id) references restaurants (
+        id, user_id
+    ) on delete cascade,
+    primary key (restaurant_id, weekday, opens)
+);
+
+create table restaurant_hours_exceptions
+(
+    restaurant_id integer not null,
+    user_id integer not null,
+    date date not null,
+    opens text,
+    closes text,
+    check ((opens is null and closes is null) or closes > opens),
+    foreign key (restaurant_id, user_id) references restaurants (
+        id, user_id
+    ) on delete cascade
+);
+
+create unique index restaurant_hours_exceptions_unique
+    on restaurant_hours_exceptions (restaurant_id, date, coalesce(opens, ''));
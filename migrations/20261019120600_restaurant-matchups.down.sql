@@ -0,0 +1,2 @@
+drop table restaurant_matchup_homies;
+drop table restaurant_matchups;
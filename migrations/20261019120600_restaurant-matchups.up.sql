@@ -0,0 +1,25 @@
+create table restaurant_matchups
+(
+    id integer primary key,
+    user_id integer not null,
+    winner_id integer not null,
+    loser_id integer not null check (loser_id <> winner_id),
+    created_at timestamp not null default current_timestamp,
+    foreign key (winner_id, user_id) references restaurants (
+        id, user_id
+    ) on delete cascade,
+    foreign key (loser_id, user_id) references restaurants (
+        id, user_id
+    ) on delete cascade
+);
+
+create table restaurant_matchup_homies
+(
+    matchup_id integer not null references restaurant_matchups (id) on delete cascade,
+    homie_id integer not null,
+    user_id integer not null,
+    foreign key (homie_id, user_id) references homies (
+        id, user_id
+    ) on delete cascade,
+    primary key (matchup_id, homie_id)
+);
@@ -0,0 +1,4 @@
+drop table homies_restaurant_elo;
+drop table selection_event_homies;
+drop table selection_event_restaurants;
+drop table selection_events;
@@ -0,0 +1,50 @@
+create table selection_events
+(
+    id integer primary key,
+    user_id integer not null,
+    chosen_id integer not null,
+    created_at timestamp not null default current_timestamp,
+    foreign key (chosen_id, user_id) references restaurants (
+        id, user_id
+    ) on delete cascade
+);
+
+create table selection_event_restaurants
+(
+    event_id integer not null references selection_events (id) on delete cascade,
+    restaurant_id integer not null,
+    user_id integer not null,
+    position integer not null,
+    foreign key (restaurant_id, user_id) references restaurants (
+        id, user_id
+    ) on delete cascade,
+    primary key (event_id, restaurant_id)
+);
+
+create table selection_event_homies
+(
+    event_id integer not null references selection_events (id) on delete cascade,
+    homie_id integer not null,
+    user_id integer not null,
+    foreign key (homie_id, user_id) references homies (
+        id, user_id
+    ) on delete cascade,
+    primary key (event_id, homie_id)
+);
+
+create table homies_restaurant_elo
+(
+    homie_id integer not null,
+    restaurant_id integer not null,
+    user_id integer not null,
+    rating real not null default 1500,
+    matches integer not null default 0,
+    updated_at timestamp not null default current_timestamp,
+    foreign key (homie_id, user_id) references homies (
+        id, user_id
+    ) on delete cascade,
+    foreign key (restaurant_id, user_id) references restaurants (
+        id, user_id
+    ) on delete cascade,
+    primary key (homie_id, restaurant_id)
+);
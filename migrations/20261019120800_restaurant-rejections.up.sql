@@ -0,0 +1,12 @@
+create table restaurant_rejections
+(
+    id integer primary key,
+    restaurant_id integer not null,
+    user_id integer not null,
+    rejected_at timestamp not null default current_timestamp,
+    foreign key (restaurant_id, user_id) references restaurants (
+        id, user_id
+    ) on delete cascade
+);
+
+create index restaurant_rejections_rejected_at on restaurant_rejections (user_id, rejected_at);
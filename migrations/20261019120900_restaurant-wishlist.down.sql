@@ -0,0 +1 @@
+drop table restaurant_wishlist;
@@ -0,0 +1,18 @@
+create table restaurant_wishlist
+(
+    id integer primary key,
+    restaurant_id integer not null,
+    homie_id integer,
+    user_id integer not null,
+    created_at timestamp not null default current_timestamp,
+    foreign key (restaurant_id, user_id) references restaurants (
+        id, user_id
+    ) on delete cascade,
+    foreign key (homie_id, user_id) references homies (
+        id, user_id
+    ) on delete cascade
+);
+
+create unique index restaurant_wishlist_uindex on restaurant_wishlist (
+    user_id, restaurant_id, coalesce(homie_id, 0)
+);
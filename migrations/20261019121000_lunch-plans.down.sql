@@ -0,0 +1 @@
+drop table planned_lunches;
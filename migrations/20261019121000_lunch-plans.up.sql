@@ -0,0 +1,11 @@
+create table planned_lunches
+(
+    date date not null,
+    restaurant_id integer not null,
+    user_id integer not null,
+    created_at timestamp not null default current_timestamp,
+    foreign key (restaurant_id, user_id) references restaurants (
+        id, user_id
+    ) on delete cascade,
+    primary key (user_id, date)
+);
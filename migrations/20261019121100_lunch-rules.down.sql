@@ -0,0 +1 @@
+drop table lunch_rules;
@@ -0,0 +1,16 @@
+create table lunch_rules
+(
+    id integer primary key,
+    user_id integer not null,
+    weekday integer not null check (weekday between 0 and 6),
+    week_of_month integer check (week_of_month between 1 and 4 or week_of_month = -1),
+    effect text not null check (effect in ('prefer', 'avoid', 'force')),
+    tag text,
+    restaurant_id integer,
+    created_at timestamp not null default current_timestamp,
+    check ((tag is null) != (restaurant_id is null)),
+    foreign key (user_id) references users (id) on delete cascade,
+    foreign key (restaurant_id, user_id) references restaurants (
+        id, user_id
+    ) on delete cascade
+);
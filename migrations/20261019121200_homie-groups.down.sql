@@ -0,0 +1,3 @@
+drop table last_home_homies;
+drop table homie_group_members;
+drop table homie_groups;
@@ -0,0 +1,36 @@
+create table homie_groups
+(
+    id integer primary key,
+    user_id integer not null,
+    name text not null check (
+        length(name) = length(trim(name)) and length(name) > 0
+    ),
+    created_at timestamp not null default current_timestamp,
+    foreign key (user_id) references users (id) on delete cascade
+);
+
+create unique index homie_groups_user_name_uindex on homie_groups (
+    user_id, name
+);
+
+create table homie_group_members
+(
+    group_id integer not null,
+    homie_id integer not null,
+    user_id integer not null,
+    foreign key (group_id) references homie_groups (id) on delete cascade,
+    foreign key (homie_id, user_id) references homies (
+        id, user_id
+    ) on delete cascade,
+    primary key (group_id, homie_id)
+);
+
+create table last_home_homies
+(
+    homie_id integer not null,
+    user_id integer not null,
+    foreign key (homie_id, user_id) references homies (
+        id, user_id
+    ) on delete cascade,
+    primary key (user_id, homie_id)
+);
@@ -0,0 +1 @@
+drop table homie_away_periods;
@@ -0,0 +1,12 @@
+create table homie_away_periods
+(
+    id integer primary key,
+    homie_id integer not null,
+    user_id integer not null,
+    starts_on date not null,
+    ends_on date not null check (ends_on >= starts_on),
+    created_at timestamp not null default current_timestamp,
+    foreign key (homie_id, user_id) references homies (
+        id, user_id
+    ) on delete cascade
+);
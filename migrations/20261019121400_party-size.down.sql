@@ -0,0 +1,27 @@
+drop view restaurants_view;
+
+alter table restaurants drop column reservations_above;
+alter table restaurants drop column max_party_size;
+
+create view restaurants_view as
+select
+    r.id,
+    r.user_id,
+    r.name,
+    r.price_tier,
+    (
+        select group_concat(t.tag)
+        from restaurant_tags as t
+        where t.restaurant_id = r.id
+    ) as tags,
+    (
+        select group_concat(m.service_mode)
+        from restaurant_service_modes as m
+        where m.restaurant_id = r.id
+    ) as service_modes,
+    (
+        select group_concat(a.restriction)
+        from restaurant_dietary_accommodations as a
+        where a.restaurant_id = r.id
+    ) as accommodations
+from restaurants as r;
@@ -0,0 +1,33 @@
+drop view restaurants_view;
+
+alter table restaurants add column max_party_size integer check (
+    max_party_size > 0
+);
+alter table restaurants add column reservations_above integer check (
+    reservations_above > 0
+);
+
+create view restaurants_view as
+select
+    r.id,
+    r.user_id,
+    r.name,
+    r.price_tier,
+    r.max_party_size,
+    r.reservations_above,
+    (
+        select group_concat(t.tag)
+        from restaurant_tags as t
+        where t.restaurant_id = r.id
+    ) as tags,
+    (
+        select group_concat(m.service_mode)
+        from restaurant_service_modes as m
+        where m.restaurant_id = r.id
+    ) as service_modes,
+    (
+        select group_concat(a.restriction)
+        from restaurant_dietary_accommodations as a
+        where a.restaurant_id = r.id
+    ) as accommodations
+from restaurants as r;
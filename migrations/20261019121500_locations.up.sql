@@ -0,0 +1,51 @@
+create table user_locations
+(
+    id integer primary key,
+    user_id integer not null,
+    name text not null,
+    latitude real not null check (latitude between -90 and 90),
+    longitude real not null check (longitude between -180 and 180),
+    created_at timestamp not null default current_timestamp,
+    updated_at timestamp not null default current_timestamp,
+    foreign key (user_id) references users (id) on delete cascade
+);
+
+create unique index user_locations_name_uindex on user_locations (
+    user_id, name
+);
+
+drop view restaurants_view;
+
+alter table restaurants add column latitude real check (
+    latitude between -90 and 90
+);
+alter table restaurants add column longitude real check (
+    longitude between -180 and 180
+);
+
+create view restaurants_view as
+select
+    r.id,
+    r.user_id,
+    r.name,
+    r.price_tier,
+    r.max_party_size,
+    r.reservations_above,
+    r.latitude,
+    r.longitude,
+    (
+        select group_concat(t.tag)
+        from restaurant_tags as t
+        where t.restaurant_id = r.id
+    ) as tags,
+    (
+        select group_concat(m.service_mode)
+        from restaurant_service_modes as m
+        where m.restaurant_id = r.id
+    ) as service_modes,
+    (
+        select group_concat(a.restriction)
+        from restaurant_dietary_accommodations as a
+        where a.restaurant_id = r.id
+    ) as accommodations
+from restaurants as r;
@@ -0,0 +1,34 @@
+drop view restaurants_view;
+
+-- restaurants without a location are around wherever lunch is
+alter table restaurants add column location_id integer references user_locations (
+    id
+) on delete set null;
+
+create view restaurants_view as
+select
+    r.id,
+    r.user_id,
+    r.name,
+    r.price_tier,
+    r.max_party_size,
+    r.reservations_above,
+    r.latitude,
+    r.longitude,
+    r.location_id,
+    (
+        select group_concat(t.tag)
+        from restaurant_tags as t
+        where t.restaurant_id = r.id
+    ) as tags,
+    (
+        select group_concat(m.service_mode)
+        from restaurant_service_modes as m
+        where m.restaurant_id = r.id
+    ) as service_modes,
+    (
+        select group_concat(a.restriction)
+        from restaurant_dietary_accommodations as a
+        where a.restaurant_id = r.id
+    ) as accommodations
+from restaurants as r;
@@ -0,0 +1,89 @@
+drop view homies_recents_restaurants_view;
+
+create table recent_restaurants_by_meal
+(
+    restaurant_id integer not null,
+    homie_id integer not null,
+    user_id integer not null,
+    date date not null default current_date,
+    meal text not null default 'lunch' check (
+        meal in ('breakfast', 'lunch', 'dinner')
+    ),
+    created_at timestamp not null default current_timestamp,
+    foreign key (restaurant_id, user_id) references restaurants (
+        id, user_id
+    ) on delete cascade,
+    foreign key (homie_id, user_id) references homies (
+        id, user_id
+    ) on delete cascade,
+    primary key (homie_id, restaurant_id, date, meal)
+);
+
+insert into recent_restaurants_by_meal (
+    restaurant_id, homie_id, user_id, date, created_at
+)
+select
+    restaurant_id,
+    homie_id,
+    user_id,
+    date,
+    created_at
+from recent_restaurants;
+
+create table recent_restaurant_ratings_by_meal
+(
+    homie_id integer not null,
+    restaurant_id integer not null,
+    date date not null,
+    meal text not null default 'lunch',
+    user_id integer not null,
+    rating integer not null check (rating between 1 and 5),
+    note text check (note is null or length(trim(note)) > 0),
+    created_at timestamp not null default current_timestamp,
+    foreign key (
+        homie_id, restaurant_id, date, meal
+    ) references recent_restaurants_by_meal (
+        homie_id, restaurant_id, date, meal
+    ) on delete cascade,
+    primary key (homie_id, restaurant_id, date, meal)
+);
+
+insert into recent_restaurant_ratings_by_meal (
+    homie_id, restaurant_id, date, user_id, rating, note, created_at
+)
+select
+    homie_id,
+    restaurant_id,
+    date,
+    user_id,
+    rating,
+    note,
+    created_at
+from recent_restaurant_ratings;
+
+drop table recent_restaurant_ratings;
+drop table recent_restaurants;
+
+alter table recent_restaurants_by_meal rename to recent_restaurants;
+alter table recent_restaurant_ratings_by_meal rename to recent_restaurant_ratings;
+
+-- lunch history doesn't keep a place off the dinner list, each meal has its own window
+create view homies_recents_restaurants_view as
+select
+    restaurant_id,
+    homie_id,
+    user_id,
+    date,
+    meal,
+    rank
+from (select
+    restaurant_id,
+    homie_id,
+    user_id,
+    date,
+    meal,
+    rank() over (partition by homie_id, meal order by date desc) as rank
+from recent_restaurants) as t
+where
+    rank <= 5
+    and date > current_date - '21 days';
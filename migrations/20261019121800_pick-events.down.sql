@@ -0,0 +1,3 @@
+alter table recent_restaurants drop column pick_event_id;
+
+drop table pick_events;
@@ -0,0 +1,19 @@
+create table pick_events
+(
+    id integer primary key,
+    user_id integer not null,
+    restaurant_id integer not null,
+    date date not null default current_date,
+    meal text not null default 'lunch' check (
+        meal in ('breakfast', 'lunch', 'dinner')
+    ),
+    created_at timestamp not null default current_timestamp,
+    foreign key (restaurant_id, user_id) references restaurants (
+        id, user_id
+    ) on delete cascade
+);
+
+-- visits added by hand don't belong to a pick and can't be undone
+alter table recent_restaurants add column pick_event_id integer references pick_events (
+    id
+) on delete cascade;
@@ -0,0 +1,28 @@
+create table restaurant_opening_hours_without_closed
+(
+    restaurant_id integer not null,
+    user_id integer not null,
+    weekday integer not null check (weekday between 0 and 6),
+    opens text not null,
+    closes text not null check (closes > opens),
+    foreign key (restaurant_id, user_id) references restaurants (
+        id, user_id
+    ) on delete cascade,
+    primary key (restaurant_id, weekday, opens)
+);
+
+insert into restaurant_opening_hours_without_closed (
+    restaurant_id, user_id, weekday, opens, closes
+)
+select
+    restaurant_id,
+    user_id,
+    weekday,
+    opens,
+    closes
+from restaurant_opening_hours
+where opens is not null;
+
+drop table restaurant_opening_hours;
+
+alter table restaurant_opening_hours_without_closed rename to restaurant_opening_hours;
@@ -0,0 +1,31 @@
+-- a weekday without opens and closes is closed all day, like the exceptions
+create table restaurant_opening_hours_with_closed
+(
+    restaurant_id integer not null,
+    user_id integer not null,
+    weekday integer not null check (weekday between 0 and 6),
+    opens text,
+    closes text,
+    check ((opens is null and closes is null) or closes > opens),
+    foreign key (restaurant_id, user_id) references restaurants (
+        id, user_id
+    ) on delete cascade
+);
+
+insert into restaurant_opening_hours_with_closed (
+    restaurant_id, user_id, weekday, opens, closes
+)
+select
+    restaurant_id,
+    user_id,
+    weekday,
+    opens,
+    closes
+from restaurant_opening_hours;
+
+drop table restaurant_opening_hours;
+
+alter table restaurant_opening_hours_with_closed rename to restaurant_opening_hours;
+
+create unique index restaurant_opening_hours_unique
+    on restaurant_opening_hours (restaurant_id, weekday, coalesce(opens, ''));
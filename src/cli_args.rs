@@ -1,7 +1,29 @@
+use std::path::PathBuf;
+
+use chrono::Local;
+use chrono::NaiveDate;
+use chrono::NaiveTime;
 use clap::Args;
 use clap::Parser;
 use clap::Subcommand;
 
+use crate::features::apply_guests;
+use crate::features::AwayPeriod;
+use crate::features::CandidateOptions;
+use crate::features::Coordinates;
+use crate::features::DietaryRestriction;
+use crate::features::Guest;
+use crate::features::LunchRule;
+use crate::features::MealSlot;
+use crate::features::OpeningHours;
+use crate::features::PlanFormat;
+use crate::features::PriceTier;
+use crate::features::RestaurantTag;
+use crate::features::ServiceMode;
+use crate::features::SimulationPolicy;
+use crate::features::VotingMethod;
+use crate::features::FAIRNESS_WINDOW_WEEKS;
+
 #[derive(Parser, Debug)]
 #[command(author, version, about, long_about = None)]
 pub struct CliArgs {
@@ -24,6 +46,10 @@ pub struct CliArgs {
     /// Specify emitting additional debug information
     #[clap(short, long, value_parser)]
     pub debug: bool,
+
+    /// Only deal with the restaurants of this location, defaults to the config's current location
+    #[clap(long, global = true, value_parser)]
+    pub location: Option<String>,
 }
 #[derive(Subcommand, Debug)]
 pub enum Command {
@@ -44,7 +70,311 @@ pub enum Command {
         visible_alias = "p",
         about = "Pick what to eat for lunch"
     )]
-    Pick,
+    Pick(PickArgs),
+
+    /// Rate a recent lunch; prompts for the latest unrated one without arguments
+    Rate(RateArgs),
+
+    /// Take back the most recently recorded pick, e.g. after changing your mind
+    UndoLastPick,
+
+    /// How fairly lunches have been shared between the homies
+    #[command(subcommand)]
+    Fairness(Fairness),
+
+    /// Operations related to recent lunches
+    #[command(subcommand)]
+    Recents(Recents),
+
+    /// Try the picker out over weeks of lunches without recording anything
+    Simulate(SimulateArgs),
+
+    /// Plan the lunches of a week ahead
+    #[command(subcommand)]
+    Plan(Plan),
+
+    /// Recurring rules like "on tuesdays prefer tag=tacos"
+    #[command(subcommand)]
+    Rules(Rules),
+
+    /// Named groups of homies to pick who's home in one go
+    #[command(subcommand)]
+    Groups(Groups),
+
+    /// Named places lunch starts from, like the office or home
+    #[command(subcommand)]
+    Locations(Locations),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Locations {
+    /// Add a location, or move it when it already exists
+    #[clap(visible_alias = "s")]
+    Set {
+        /// Name of location, e.g. office
+        #[clap(name = "location name", value_parser)]
+        name: String,
+        /// Latitude and longitude, e.g. "52.5200,13.4050"
+        #[clap(value_parser, allow_hyphen_values = true)]
+        coordinates: Coordinates,
+    },
+
+    /// Remove a location
+    #[clap(visible_alias = "r")]
+    Remove {
+        /// Name of location
+        #[clap(name = "location name", value_parser)]
+        name: String,
+    },
+
+    /// List the locations
+    #[clap(visible_alias = "l")]
+    List,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Groups {
+    /// Add a group
+    #[clap(visible_alias = "a")]
+    Add {
+        /// Name of group
+        #[clap(name = "group name", value_parser)]
+        name: String,
+        /// Homies in the group, separated by commas
+        #[clap(long, required = true, value_delimiter = ',', value_parser)]
+        homies: Vec<String>,
+    },
+
+    /// Replace the homies in a group
+    #[clap(visible_alias = "e")]
+    Edit {
+        /// Name of group
+        #[clap(name = "group name", value_parser)]
+        name: String,
+        /// Homies in the group, separated by commas
+        #[clap(long, required = true, value_delimiter = ',', value_parser)]
+        homies: Vec<String>,
+    },
+
+    /// List the groups
+    #[clap(visible_alias = "l")]
+    List,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Rules {
+    /// List the rules
+    #[clap(visible_alias = "l")]
+    List,
+
+    /// Add a rule: on <schedule> <prefer|no|always> <tag=x|restaurant=x>
+    #[clap(visible_alias = "a")]
+    Add {
+        /// e.g. "on the first friday of the month always restaurant=Big Thai"
+        #[clap(name = "rule", value_parser)]
+        rule: LunchRule,
+    },
+
+    /// Remove a rule by its id
+    #[clap(visible_alias = "r")]
+    Remove {
+        /// Id of the rule as listed
+        #[clap(value_parser)]
+        id: i64,
+    },
+
+    /// Show which rules apply on a date
+    #[clap(visible_alias = "t")]
+    Test {
+        /// Date to check (YYYY-MM-DD), defaults to today
+        #[clap(long, short, value_parser)]
+        date: Option<NaiveDate>,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Plan {
+    /// Plan Monday to Friday for the home homies, replacing the earlier plan for that week
+    #[clap(visible_alias = "g")]
+    Generate {
+        /// Any day of the week to plan (YYYY-MM-DD), defaults to this week or next week on weekends
+        #[clap(long, short, value_parser)]
+        week: Option<NaiveDate>,
+        /// Export as text or json
+        #[clap(long, short, default_value_t, value_parser)]
+        format: PlanFormat,
+    },
+
+    /// Show the plan for a week
+    #[clap(visible_alias = "s")]
+    Show {
+        /// Any day of the week to show (YYYY-MM-DD), defaults to this week or next week on weekends
+        #[clap(long, short, value_parser)]
+        week: Option<NaiveDate>,
+        /// Export as text or json
+        #[clap(long, short, default_value_t, value_parser)]
+        format: PlanFormat,
+    },
+}
+
+#[derive(Args, Debug)]
+pub struct SimulateArgs {
+    /// How many days of lunches to simulate
+    #[clap(long, short, default_value_t = 60, value_parser)]
+    pub days: u32,
+
+    /// Homies at lunch every day, separated by commas; defaults to everyone
+    #[clap(long, value_delimiter = ',', value_parser)]
+    pub homies: Vec<String>,
+
+    /// How the scripted chooser picks each day (first, random, explore)
+    #[clap(long, short, default_value_t, value_parser)]
+    pub policy: SimulationPolicy,
+
+    /// How many of each homie's latest lunches keep a restaurant off the list
+    #[clap(long, value_parser)]
+    pub recency_window: Option<u32>,
+
+    /// Don't weight favorites by how fairly lunches were shared
+    #[clap(long, value_parser)]
+    pub no_fairness: bool,
+    /// Don't weight favorites by the head-to-head matchups they won
+    #[clap(long, value_parser)]
+    pub no_elo: bool,
+}
+
+#[derive(Args, Debug)]
+pub struct RateArgs {
+    /// Name of homie
+    #[clap(value_parser, requires_all = ["restaurant_name", "rating"])]
+    pub homie_name: Option<String>,
+
+    /// Name of the restaurant visited
+    #[clap(value_parser)]
+    pub restaurant_name: Option<String>,
+
+    /// How it was, from 1 to 5
+    #[clap(value_parser = clap::value_parser!(u8).range(1..=5))]
+    pub rating: Option<u8>,
+
+    /// A note for the visit journal
+    #[clap(long, short, value_parser)]
+    pub note: Option<String>,
+
+    /// Date of the visit (YYYY-MM-DD), defaults to the latest visit
+    #[clap(long, value_parser)]
+    pub date: Option<NaiveDate>,
+
+    /// Meal of the visit (breakfast, lunch, dinner), defaults to the latest that day
+    #[clap(long, value_parser)]
+    pub meal: Option<MealSlot>,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Fairness {
+    /// List each homie's share of lunches at one of their favorites
+    #[clap(visible_alias = "r")]
+    Report {
+        /// How many weeks back to look
+        #[clap(long, short, default_value_t = FAIRNESS_WINDOW_WEEKS, value_parser)]
+        weeks: u32,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Recents {
+    /// List recent lunches with their ratings and notes
+    #[clap(visible_alias = "l")]
+    List {
+        /// How many days back to look
+        #[clap(long, short, default_value_t = 14, value_parser)]
+        days: u32,
+    },
+}
+
+#[derive(Args, Debug, Default)]
+pub struct PickArgs {
+    /// Who's home, by group name, instead of asking
+    #[clap(long, short, value_parser)]
+    pub group: Option<String>,
+
+    /// Where lunch starts from by location name, defaults to the current location
+    #[clap(long, value_parser)]
+    pub from: Option<String>,
+
+    /// Only suggest restaurants within this many kilometers of --from or the current location
+    #[clap(long, value_parser)]
+    pub max_km: Option<f64>,
+
+    /// Someone joining just this once, like "Sam:vegetarian,thai" (can be repeated)
+    #[clap(long = "guest", value_parser)]
+    pub guests: Vec<Guest>,
+
+    /// Only suggest restaurants with this tag (can be repeated)
+    #[clap(long = "tag", value_parser)]
+    pub tags: Vec<RestaurantTag>,
+
+    /// Only suggest restaurants at or below this price tier (1-4)
+    #[clap(long, value_parser = clap::value_parser!(u8).range(1..=4))]
+    pub max_price: Option<u8>,
+
+    /// Only suggest restaurants offering this service mode (dine-in, takeout, delivery)
+    #[clap(long, value_parser)]
+    pub mode: Option<ServiceMode>,
+
+    /// Time of lunch today (HH:MM) used to skip closed restaurants, defaults to now
+    #[clap(long, value_parser)]
+    pub at: Option<NaiveTime>,
+
+    /// Which meal this is (breakfast, lunch, dinner), each has its own recent history
+    #[clap(long, value_parser, default_value_t)]
+    pub meal: MealSlot,
+
+    /// Let every home homie vote instead of picking from the list (borda, instant-runoff, approval)
+    #[clap(long, value_parser, num_args = 0..=1, default_missing_value = "borda")]
+    pub vote: Option<VotingMethod>,
+
+    /// Narrow the candidates down through head-to-head "this or that" prompts
+    #[clap(long, value_parser, conflicts_with = "vote")]
+    pub bracket: bool,
+
+    /// How many times the whole list can be rejected for a new one, defaults to the config's budget
+    #[clap(long, value_parser)]
+    pub rerolls: Option<u32>,
+
+    /// Always add a restaurant nobody has favorited or visited yet to the list
+    #[clap(long, value_parser)]
+    pub explore: bool,
+
+    /// Explain how the candidates were weighted
+    #[clap(long, value_parser)]
+    pub explain: bool,
+
+    /// Don't weight favorites by the head-to-head matchups they won
+    #[clap(long, value_parser)]
+    pub no_elo: bool,
+}
+
+impl From<&PickArgs> for CandidateOptions {
+    fn from(args: &PickArgs) -> Self {
+        let mut options = Self {
+            tags: args.tags.clone(),
+            max_price_tier: args
+                .max_price
+                .map(|p| PriceTier::try_from(p).expect("clap validates the price tier range")),
+            service_mode: args.mode,
+            meal: args.meal,
+            max_km: args.max_km,
+            ignore_elo: args.no_elo,
+            at: Some(match args.at {
+                Some(time) => Local::now().date_naive().and_time(time),
+                None => Local::now().naive_local(),
+            }),
+            ..Default::default()
+        };
+        apply_guests(&mut options, &args.guests);
+        options
+    }
 }
 
 #[derive(Args, Debug)]
@@ -85,6 +415,49 @@ pub enum Homies {
     #[command(subcommand)]
     RecentRestaurant(AddRestaurant),
 
+    /// Set how much a homie likes one of their favorites
+    #[clap(visible_alias = "s")]
+    Strength {
+        /// Homie Name
+        #[clap(name = "homie name", value_parser)]
+        homie_name: String,
+        /// Name of restaurant
+        #[clap(name = "restaurant name", value_parser)]
+        restaurant_name: String,
+        /// 1 (it's fine), 2 (like it) or 3 (love it)
+        #[clap(name = "strength", value_parser = clap::value_parser!(u8).range(1..=3))]
+        strength: u8,
+    },
+
+    /// Manage a homie's dietary restrictions
+    #[command(subcommand)]
+    Diet(HomieDiet),
+
+    /// Manage restaurants a homie refuses to go to
+    #[command(subcommand)]
+    Veto(VetoRestaurant),
+
+    /// Mark a homie as away, e.g. `homies away Alice 2026-11-02..2026-11-06`
+    Away {
+        /// Homie Name
+        #[clap(name = "homie name", value_parser)]
+        homie_name: String,
+        /// First and last day away (YYYY-MM-DD..YYYY-MM-DD), or a single day
+        #[clap(name = "period", value_parser)]
+        period: AwayPeriod,
+    },
+
+    /// List who is away from today on
+    Availability,
+
+    /// Mark homies away for the out of office events in exported `.ics` files
+    ImportCalendar {
+        /// Calendar files, attendees and titles are matched to homies with the config's
+        /// calendar mapping
+        #[clap(name = "file", required = true, value_parser)]
+        files: Vec<PathBuf>,
+    },
+
     /// Manage Favorites for a Homie Interactively
     #[clap(visible_alias = "i")]
     Interactive,
@@ -173,4 +546,220 @@ pub enum Restaurants {
         #[clap(short, value_parser)]
         updated_name: String,
     },
+
+    /// Manage a restaurant's tags
+    #[command(subcommand)]
+    Tags(RestaurantTags),
+
+    /// Set a restaurant's price tier
+    #[clap(visible_alias = "p")]
+    Price {
+        /// Name of restaurant
+        #[clap(name = "restaurant name", value_parser)]
+        restaurant_name: String,
+        /// Price tier from 1 ($) to 4 ($$$$)
+        #[clap(name = "price tier", value_parser = clap::value_parser!(u8).range(1..=4))]
+        price_tier: u8,
+    },
+
+    /// Set the service modes a restaurant offers
+    #[clap(visible_alias = "m")]
+    Modes {
+        /// Name of restaurant
+        #[clap(name = "restaurant name", value_parser)]
+        restaurant_name: String,
+        /// Service modes (dine-in, takeout, delivery); leave empty to clear
+        #[clap(name = "service modes", value_parser)]
+        service_modes: Vec<ServiceMode>,
+    },
+
+    /// List the restaurants with how far they are
+    #[clap(visible_alias = "l")]
+    List {
+        /// Only measure from this location instead of all of them
+        #[clap(long, value_parser)]
+        from: Option<String>,
+    },
+
+    /// Set where a restaurant is; leave the coordinates out to clear
+    Location {
+        /// Name of restaurant
+        #[clap(name = "restaurant name", value_parser)]
+        restaurant_name: String,
+        /// Latitude and longitude, e.g. "52.5200,13.4050"
+        #[clap(value_parser, allow_hyphen_values = true)]
+        coordinates: Option<Coordinates>,
+    },
+
+    /// Tie a restaurant to the location it's picked from; leave the location out for all
+    Scope {
+        /// Name of restaurant
+        #[clap(name = "restaurant name", value_parser)]
+        restaurant_name: String,
+        /// Name of location
+        #[clap(name = "location name", value_parser)]
+        location: Option<String>,
+    },
+
+    /// Set how many people a restaurant seats; leave both out to clear
+    PartySize {
+        /// Name of restaurant
+        #[clap(name = "restaurant name", value_parser)]
+        restaurant_name: String,
+        /// The most people that can be seated together
+        #[clap(long, value_parser = clap::value_parser!(u32).range(1..))]
+        max: Option<u32>,
+        /// Recommend calling ahead for parties larger than this
+        #[clap(long, value_parser = clap::value_parser!(u32).range(1..))]
+        reservations_above: Option<u32>,
+    },
+
+    /// Manage the dietary restrictions a restaurant can accommodate
+    #[command(subcommand)]
+    Accommodates(RestaurantAccommodations),
+
+    /// Set a restaurant's opening hours, e.g. "Mo-Fr 11:00-15:00; Sa 12:00-14:00; 2026-12-25 off"
+    #[clap(visible_alias = "h")]
+    Hours {
+        /// Name of restaurant
+        #[clap(name = "restaurant name", value_parser)]
+        restaurant_name: String,
+        /// Opening hours; leave empty to clear
+        #[clap(name = "opening hours", value_parser)]
+        opening_hours: OpeningHours,
+    },
+
+    /// Show everything known about a restaurant
+    #[clap(visible_alias = "s")]
+    Show {
+        /// Name of restaurant
+        #[clap(name = "restaurant name", value_parser)]
+        restaurant_name: String,
+    },
+
+    /// Edit a restaurant's tags, price tier, service modes and accommodations interactively
+    #[clap(visible_alias = "i")]
+    Interactive,
+
+    /// Manage the restaurants the homies want to try
+    #[command(subcommand)]
+    Wishlist(Wishlist),
+}
+
+#[derive(Subcommand, Debug)]
+pub enum Wishlist {
+    /// Put a restaurant on the wishlist, it comes first in the candidates until visited
+    #[clap(visible_alias = "a")]
+    Add {
+        /// Name of restaurant
+        #[clap(name = "restaurant name", value_parser)]
+        restaurant_name: String,
+        /// Homie who wants to try it, shared with everyone when omitted
+        #[clap(long, value_parser)]
+        homie: Option<String>,
+    },
+
+    /// List the restaurants nobody has visited since they were wishlisted
+    #[clap(visible_alias = "l")]
+    List,
+}
+
+#[derive(Subcommand, Debug)]
+pub enum VetoRestaurant {
+    /// Veto a restaurant, it will never be suggested while this homie is home
+    #[clap(visible_alias = "a")]
+    Add {
+        /// Homie Name
+        #[clap(name = "homie name", value_parser)]
+        homie_name: String,
+        /// Name of restaurant
+        #[clap(name = "restaurant name", value_parser)]
+        restaurant_name: String,
+    },
+
+    /// Lift a veto
+    #[clap(visible_aliases = ["d", "delete"])]
+    Remove {
+        /// Homie Name
+        #[clap(name = "homie name", value_parser)]
+        homie_name: String,
+        /// Name of restaurant
+        #[clap(name = "restaurant name", value_parser)]
+        restaurant_name: String,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum HomieDiet {
+    /// Add a dietary restriction to a homie
+    #[clap(visible_alias = "a")]
+    Add {
+        /// Homie Name
+        #[clap(name = "homie name", value_parser)]
+        homie_name: String,
+        /// vegetarian, vegan, gluten-free, nut-free, dairy-free, halal or kosher
+        #[clap(name = "restriction", value_parser)]
+        restriction: DietaryRestriction,
+    },
+
+    /// Remove a dietary restriction from a homie
+    #[clap(visible_alias = "d")]
+    Delete {
+        /// Homie Name
+        #[clap(name = "homie name", value_parser)]
+        homie_name: String,
+        /// Restriction to remove
+        #[clap(name = "restriction", value_parser)]
+        restriction: DietaryRestriction,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum RestaurantAccommodations {
+    /// Mark a restaurant as able to accommodate a dietary restriction
+    #[clap(visible_alias = "a")]
+    Add {
+        /// Name of restaurant
+        #[clap(name = "restaurant name", value_parser)]
+        restaurant_name: String,
+        /// vegetarian, vegan, gluten-free, nut-free, dairy-free, halal or kosher
+        #[clap(name = "restriction", value_parser)]
+        restriction: DietaryRestriction,
+    },
+
+    /// Mark a restaurant as unable to accommodate a dietary restriction
+    #[clap(visible_alias = "d")]
+    Delete {
+        /// Name of restaurant
+        #[clap(name = "restaurant name", value_parser)]
+        restaurant_name: String,
+        /// Restriction to remove
+        #[clap(name = "restriction", value_parser)]
+        restriction: DietaryRestriction,
+    },
+}
+
+#[derive(Subcommand, Debug)]
+pub enum RestaurantTags {
+    /// Tag a restaurant
+    #[clap(visible_alias = "a")]
+    Add {
+        /// Name of restaurant
+        #[clap(name = "restaurant name", value_parser)]
+        restaurant_name: String,
+        /// Tag, e.g. a cuisine like "thai" or "vegan-friendly"
+        #[clap(name = "tag", value_parser)]
+        tag: String,
+    },
+
+    /// Remove a tag from a restaurant
+    #[clap(visible_alias = "d")]
+    Delete {
+        /// Name of restaurant
+        #[clap(name = "restaurant name", value_parser)]
+        restaurant_name: String,
+        /// Tag to remove
+        #[clap(name = "tag", value_parser)]
+        tag: String,
+    },
 }
@@ -4,10 +4,32 @@ use std::path::PathBuf;
 use std::str::FromStr;
 use thiserror::Error;
 
+use crate::features::CalendarMapping;
+
 #[derive(Serialize, Deserialize, Clone)]
 pub struct Settings {
     pub database_url: String,
     pub telemetry_enabled: bool,
+    /// Ask for ratings of the last lunch when picking the next one
+    #[serde(default = "default_prompt_for_ratings")]
+    pub prompt_for_ratings: bool,
+    /// How many times the candidates can be rerolled per pick
+    #[serde(default = "default_reroll_budget")]
+    pub reroll_budget: u32,
+    /// How `.ics` files are read when importing who's away
+    #[serde(default)]
+    pub calendar: CalendarMapping,
+    /// The location whose restaurants are picked from, unless `--location` says otherwise
+    #[serde(default)]
+    pub current_location: Option<String>,
+}
+
+fn default_reroll_budget() -> u32 {
+    2
+}
+
+fn default_prompt_for_ratings() -> bool {
+    true
 }
 
 impl Settings {
@@ -15,6 +37,10 @@ impl Settings {
         Self {
             database_url,
             telemetry_enabled,
+            prompt_for_ratings: default_prompt_for_ratings(),
+            reroll_budget: default_reroll_budget(),
+            calendar: CalendarMapping::default(),
+            current_location: None,
         }
     }
 }
@@ -24,6 +50,10 @@ impl Default for Settings {
         Self {
             database_url: DatabaseSettings::default().to_url(),
             telemetry_enabled: true,
+            prompt_for_ratings: default_prompt_for_ratings(),
+            reroll_budget: default_reroll_budget(),
+            calendar: CalendarMapping::default(),
+            current_location: None,
         }
     }
 }
@@ -1,16 +1,45 @@
+mod availability;
+mod dietary;
+mod fairness;
 mod get_homie_by_name;
+mod groups;
+mod guests;
 mod homies;
 mod homies_favorites;
+mod homies_vetoes;
+mod locations;
+mod matchups;
+mod plans;
 mod recents;
+mod rejections;
 // mod recipes;
 mod restaurants;
+mod rules;
+mod simulation;
+mod voting;
+mod wishlist;
 mod read_homie {}
 mod update_homie {}
 mod delete_homie {}
 mod remove_favorite_from_homie {}
 
+pub use availability::*;
+pub use dietary::*;
+pub use fairness::*;
+pub use get_homie_by_name::*;
+pub use groups::*;
+pub use guests::*;
 pub use homies::*;
 pub use homies_favorites::*;
+pub use homies_vetoes::*;
+pub use locations::*;
+pub use matchups::*;
+pub use plans::*;
 pub use recents::*;
+pub use rejections::*;
 // pub use recipes::*;
 pub use restaurants::*;
+pub use rules::*;
+pub use simulation::*;
+pub use voting::*;
+pub use wishlist::*;
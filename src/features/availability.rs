@@ -0,0 +1,6 @@
+mod calendar;
+mod homie_availability;
+mod models;
+pub use calendar::*;
+pub use homie_availability::*;
+pub use models::*;
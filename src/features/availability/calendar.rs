@@ -0,0 +1,344 @@
+use std::collections::HashMap;
+
+use chrono::Days;
+use chrono::NaiveDate;
+use serde::Deserialize;
+use serde::Serialize;
+use thiserror::Error;
+
+use super::AwayPeriod;
+
+const DATE_FORMAT: &str = "%Y%m%d";
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum CalendarParseError {
+    #[error("Not an iCalendar file, expected it to start with BEGIN:VCALENDAR")]
+    NotACalendar,
+
+    #[error("Invalid date {:?} in event {:?}", value, summary)]
+    InvalidDate { value: String, summary: String },
+
+    #[error("Event {:?} has no start date", summary)]
+    MissingStart { summary: String },
+}
+
+/// Which calendar events mean someone is away and who they belong to.
+#[derive(Serialize, Deserialize, Clone, Debug)]
+pub struct CalendarMapping {
+    /// Attendee emails or names, lowercase, mapped to the homie they belong to; homie
+    /// names match without being listed
+    #[serde(default)]
+    pub homies: HashMap<String, String>,
+    /// Words in an event title that mark it as out of office
+    #[serde(default = "default_away_keywords")]
+    pub away_keywords: Vec<String>,
+}
+
+fn default_away_keywords() -> Vec<String> {
+    ["ooo", "out of office", "vacation", "holiday", "pto", "away"]
+        .map(String::from)
+        .to_vec()
+}
+
+impl Default for CalendarMapping {
+    fn default() -> Self {
+        Self {
+            homies: HashMap::new(),
+            away_keywords: default_away_keywords(),
+        }
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Default)]
+pub struct CalendarAttendee {
+    pub email: Option<String>,
+    pub name: Option<String>,
+}
+
+/// The parts of a `VEVENT` needed to tell who is away when; times are dropped.
+#[derive(Debug, PartialEq, Eq)]
+pub struct CalendarEvent {
+    pub summary: String,
+    pub starts_on: NaiveDate,
+    /// The last day of the event, included
+    pub ends_on: NaiveDate,
+    pub attendees: Vec<CalendarAttendee>,
+    /// Marked out of office by the calendar itself, as Outlook does
+    pub out_of_office: bool,
+}
+
+/// Splits `NAME;PARAM=a;PARAM="b:c":value` into its name, parameters and value.
+fn split_property(line: &str) -> (&str, Vec<(&str, &str)>, &str) {
+    let mut quoted = false;
+    let colon = line
+        .char_indices()
+        .find(|&(_, c)| {
+            quoted ^= c == '"';
+            c == ':' && !quoted
+        })
+        .map_or(line.len(), |(index, _)| index);
+    let (head, value) = (&line[..colon], line.get(colon + 1..).unwrap_or(""));
+    let mut parts = head.split(';');
+    let name = parts.next().unwrap_or("");
+    let params = parts
+        .filter_map(|p| p.split_once('='))
+        .map(|(key, value)| (key, value.trim_matches('"')))
+        .collect();
+    (name, params, value)
+}
+
+fn unescape(text: &str) -> String {
+    text.replace("\\n", " ")
+        .replace("\\N", " ")
+        .replace("\\,", ",")
+        .replace("\\;", ";")
+        .replace("\\\\", "\\")
+}
+
+fn parse_date(value: &str, summary: &str) -> Result<NaiveDate, CalendarParseError> {
+    value
+        .get(..8)
+        .and_then(|date| NaiveDate::parse_from_str(date, DATE_FORMAT).ok())
+        .ok_or_else(|| CalendarParseError::InvalidDate {
+            value: value.to_string(),
+            summary: summary.to_string(),
+        })
+}
+
+#[derive(Default)]
+struct EventProperties<'a> {
+    summary: String,
+    start: Option<&'a str>,
+    end: Option<&'a str>,
+    attendees: Vec<CalendarAttendee>,
+    out_of_office: bool,
+}
+
+impl EventProperties<'_> {
+    fn into_event(self) -> Result<CalendarEvent, CalendarParseError> {
+        let start = self.start.ok_or_else(|| CalendarParseError::MissingStart {
+            summary: self.summary.clone(),
+        })?;
+        let starts_on = parse_date(start, &self.summary)?;
+        let ends_on = match self.end {
+            // the end is exclusive, for all day events that's the day after
+            Some(end) => {
+                let date = parse_date(end, &self.summary)?;
+                let midnight = end.len() == 8 || end.get(9..15) == Some("000000");
+                match midnight && date > starts_on {
+                    true => date - Days::new(1),
+                    false => date.max(starts_on),
+                }
+            }
+            None => starts_on,
+        };
+        Ok(CalendarEvent {
+            summary: self.summary,
+            starts_on,
+            ends_on,
+            attendees: self.attendees,
+            out_of_office: self.out_of_office,
+        })
+    }
+}
+
+/// Reads the events of an `.ics` file.
+///
+/// Only the first occurrence of a recurring event is read.
+pub fn parse_calendar(ics: &str) -> Result<Vec<CalendarEvent>, CalendarParseError> {
+    // long lines are folded onto the next ones, which start with a space or a tab
+    let mut lines: Vec<String> = Vec::new();
+    for line in ics.lines() {
+        match (line.strip_prefix([' ', '\t']), lines.last_mut()) {
+            (Some(folded), Some(last)) => last.push_str(folded),
+            _ => lines.push(line.to_string()),
+        }
+    }
+    if !lines
+        .iter()
+        .find(|l| !l.trim().is_empty())
+        .is_some_and(|l| l.trim().eq_ignore_ascii_case("BEGIN:VCALENDAR"))
+    {
+        return Err(CalendarParseError::NotACalendar);
+    }
+
+    let mut events = Vec::new();
+    let mut event: Option<EventProperties> = None;
+    for line in &lines {
+        let (name, params, value) = split_property(line);
+        match (name.to_ascii_uppercase().as_str(), event.as_mut()) {
+            ("BEGIN", None) if value.eq_ignore_ascii_case("VEVENT") => {
+                event = Some(EventProperties::default())
+            }
+            ("END", Some(_)) if value.eq_ignore_ascii_case("VEVENT") => {
+                if let Some(properties) = event.take() {
+                    events.push(properties.into_event()?);
+                }
+            }
+            ("SUMMARY", Some(event)) => event.summary = unescape(value),
+            ("DTSTART", Some(event)) => event.start = Some(value),
+            ("DTEND", Some(event)) => event.end = Some(value),
+            ("ATTENDEE" | "ORGANIZER", Some(event)) => event.attendees.push(CalendarAttendee {
+                email: value
+                    .get(..7)
+                    .filter(|scheme| scheme.eq_ignore_ascii_case("mailto:"))
+                    .map(|_| value[7..].to_lowercase()),
+                name: params
+                    .iter()
+                    .find(|(key, _)| key.eq_ignore_ascii_case("CN"))
+                    .map(|(_, name)| name.to_string()),
+            }),
+            ("X-MICROSOFT-CDO-BUSYSTATUS", Some(event)) => {
+                event.out_of_office |= value.eq_ignore_ascii_case("OOF")
+            }
+            _ => {}
+        }
+    }
+    Ok(events)
+}
+
+fn words(text: &str) -> Vec<String> {
+    text.split(|c: char| !c.is_alphanumeric())
+        .filter(|w| !w.is_empty())
+        .map(|w| w.to_lowercase())
+        .collect()
+}
+
+/// Whether `phrase` shows up in `text` as whole words.
+fn contains_words(text: &[String], phrase: &str) -> bool {
+    let phrase = words(phrase);
+    !phrase.is_empty() && text.windows(phrase.len()).any(|w| w == phrase.as_slice())
+}
+
+/// The homies that out of office events are about, with the days they're away.
+///
+/// An event is out of office when the calendar marks it so or its title has one of the
+/// keywords. It belongs to the homies its attendees map to, by email or name, and to
+/// the homies whose mapped or own name is in the title.
+pub fn away_from_calendar(
+    events: &[CalendarEvent],
+    mapping: &CalendarMapping,
+    homie_names: &[String],
+) -> Vec<(String, AwayPeriod)> {
+    let mut names = homie_names
+        .iter()
+        .map(|name| (name.to_lowercase(), name.clone()))
+        .collect::<HashMap<_, _>>();
+    names.extend(
+        mapping
+            .homies
+            .iter()
+            .filter(|(_, homie)| homie_names.contains(homie))
+            .map(|(key, homie)| (key.to_lowercase(), homie.clone())),
+    );
+
+    let mut away = Vec::new();
+    for event in events {
+        let title = words(&event.summary);
+        if !event.out_of_office
+            && !mapping
+                .away_keywords
+                .iter()
+                .any(|k| contains_words(&title, k))
+        {
+            continue;
+        }
+        let period = AwayPeriod {
+            starts_on: event.starts_on,
+            ends_on: event.ends_on,
+        };
+        let mut homies = event
+            .attendees
+            .iter()
+            .flat_map(|a| [a.email.as_ref(), a.name.as_ref()])
+            .flatten()
+            .filter_map(|key| names.get(&key.to_lowercase()))
+            .chain(
+                names
+                    .iter()
+                    .filter(|(key, _)| !key.contains('@') && contains_words(&title, key))
+                    .map(|(_, homie)| homie),
+            )
+            .collect::<Vec<_>>();
+        homies.sort();
+        homies.dedup();
+        away.extend(homies.into_iter().map(|homie| (homie.clone(), period)));
+    }
+    away
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::away_from_calendar;
+    use super::parse_calendar;
+    use super::CalendarMapping;
+    use super::CalendarParseError;
+
+    const CALENDAR: &str = "BEGIN:VCALENDAR\r
+VERSION:2.0\r
+BEGIN:VEVENT\r
+SUMMARY:Out of office\r
+DTSTART;VALUE=DATE:20261102\r
+DTEND;VALUE=DATE:20261107\r
+ATTENDEE;CN=\"Smith, Alice\";ROLE=REQ-PARTICIPANT:mailto:Alice@Example.com\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+SUMMARY:Bob OOO - dentist\r
+DTSTART;TZID=Europe/Berlin:20261110T090000\r
+DTEND;TZID=Europe/Berlin:20261110T120000\r
+END:VEVENT\r
+BEGIN:VEVENT\r
+SUMMARY:Planning with Bob and a very long title that is folded onto the\r
+  next line\r
+DTSTART:20261111T100000Z\r
+END:VEVENT\r
+END:VCALENDAR\r
+";
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 11, day).unwrap()
+    }
+
+    #[test]
+    fn parses_events() {
+        let events = parse_calendar(CALENDAR).unwrap();
+
+        assert_eq!(3, events.len());
+        assert_eq!((date(2), date(6)), (events[0].starts_on, events[0].ends_on));
+        assert_eq!(
+            Some("alice@example.com"),
+            events[0].attendees[0].email.as_deref()
+        );
+        assert_eq!(Some("Smith, Alice"), events[0].attendees[0].name.as_deref());
+        assert_eq!(
+            (date(10), date(10)),
+            (events[1].starts_on, events[1].ends_on)
+        );
+        assert_eq!(
+            "Planning with Bob and a very long title that is folded onto the next line",
+            events[2].summary
+        );
+        assert_eq!(
+            Err(CalendarParseError::NotACalendar),
+            parse_calendar("SUMMARY:nope")
+        );
+    }
+
+    #[test]
+    fn maps_out_of_office_events_to_homies() {
+        let events = parse_calendar(CALENDAR).unwrap();
+        let mut mapping = CalendarMapping::default();
+        mapping
+            .homies
+            .insert("alice@example.com".to_string(), "Alice".to_string());
+
+        let away = away_from_calendar(&events, &mapping, &["Alice".to_string(), "Bob".to_string()])
+            .into_iter()
+            .map(|(homie, period)| format!("{} {}", homie, period))
+            .collect::<Vec<_>>();
+
+        assert_eq!(vec!["Alice 2026-11-02..2026-11-06", "Bob 2026-11-10"], away);
+    }
+}
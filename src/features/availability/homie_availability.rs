@@ -0,0 +1,201 @@
+use std::fmt::Debug;
+
+use chrono::NaiveDate;
+use sqlx::Pool;
+
+use sqlx::Sqlite;
+use thiserror::Error;
+use tracing::Instrument;
+
+use crate::features::get_all_homies;
+use crate::features::get_homie;
+use crate::features::GetAllHomies;
+use crate::features::GetAllHomiesError;
+use crate::features::GetHomie;
+use crate::features::GetHomieError;
+use crate::features::HomieId;
+use crate::features::HomieNameValidationError;
+use crate::features::HomiesName;
+use crate::user::UserId;
+
+use super::away_from_calendar;
+use super::parse_calendar;
+use super::AwayPeriod;
+use super::CalendarMapping;
+use super::CalendarParseError;
+use super::HomieAwayPeriod;
+use super::HomieAwayPeriodRow;
+
+#[tracing::instrument(skip(db))]
+pub async fn add_homie_away_period(
+    homie_name: impl TryInto<HomiesName, Error = HomieNameValidationError> + Debug,
+    period: AwayPeriod,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl HomieAvailability,
+) -> Result<(), HomieAvailabilityError> {
+    let user_id = user_id.into();
+    let homie = get_homie(user_id, homie_name, db).await?;
+
+    db.add_homie_away_period(homie.id, period, user_id).await?;
+
+    Ok(())
+}
+
+/// Marks homies away for the out of office events in an `.ics` file, see
+/// [`away_from_calendar`]. Periods that are already known are skipped.
+#[tracing::instrument(skip(ics, db))]
+pub async fn import_calendar_away_periods(
+    ics: &str,
+    mapping: &CalendarMapping,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl HomieAvailability,
+) -> Result<Vec<HomieAwayPeriod>, HomieAvailabilityError> {
+    let user_id = user_id.into();
+    let events = parse_calendar(ics)?;
+    let homies = get_all_homies(user_id, db).await?;
+    let homie_names = homies
+        .iter()
+        .map(|h| h.name.as_str().to_string())
+        .collect::<Vec<_>>();
+
+    let mut known = db.get_homie_away_periods(user_id).await?;
+    let mut imported = Vec::new();
+    for (homie_name, period) in away_from_calendar(&events, mapping, &homie_names) {
+        let Some(homie) = homies.iter().find(|h| h.name.as_str() == homie_name) else {
+            continue;
+        };
+        if known
+            .iter()
+            .any(|a| a.homie_id == homie.id && a.period == period)
+        {
+            continue;
+        }
+        db.add_homie_away_period(homie.id, period, user_id).await?;
+        let away = HomieAwayPeriod {
+            homie_id: homie.id,
+            homie_name,
+            period,
+        };
+        known.push(away.clone());
+        imported.push(away);
+    }
+
+    Ok(imported)
+}
+
+/// Away periods that haven't ended before `from`.
+#[tracing::instrument(skip(db))]
+pub async fn get_homie_away_periods(
+    from: NaiveDate,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl HomieAvailability,
+) -> Result<Vec<HomieAwayPeriod>, HomieAvailabilityError> {
+    Ok(db
+        .get_homie_away_periods(user_id.into())
+        .await?
+        .into_iter()
+        .filter(|a| a.period.ends_on >= from)
+        .collect())
+}
+
+/// The homies that aren't away on `date`.
+#[tracing::instrument(skip(db))]
+pub async fn get_available_homies<T, Y>(
+    homie_ids: T,
+    date: NaiveDate,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl HomieAvailability,
+) -> Result<Vec<HomieId>, HomieAvailabilityError>
+where
+    T: IntoIterator<Item = Y> + Debug,
+    Y: Into<HomieId> + Debug,
+{
+    let away = db
+        .get_homie_away_periods(user_id.into())
+        .await?
+        .into_iter()
+        .filter(|a| a.period.contains(date))
+        .map(|a| a.homie_id)
+        .collect::<Vec<_>>();
+
+    Ok(homie_ids
+        .into_iter()
+        .map(|id| id.into())
+        .filter(|id| !away.contains(id))
+        .collect())
+}
+
+#[derive(Error, Debug)]
+pub enum HomieAvailabilityError {
+    #[error(transparent)]
+    GetHomieError(#[from] GetHomieError),
+
+    #[error(transparent)]
+    GetAllHomiesError(#[from] GetAllHomiesError),
+
+    #[error(transparent)]
+    CalendarParseError(#[from] CalendarParseError),
+
+    #[error(transparent)]
+    DbError(#[from] sqlx::Error),
+}
+
+pub trait HomieAvailability: GetHomie + GetAllHomies {
+    async fn add_homie_away_period(
+        &self,
+        homie_id: HomieId,
+        period: AwayPeriod,
+        user_id: UserId,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn get_homie_away_periods(
+        &self,
+        user_id: UserId,
+    ) -> Result<Vec<HomieAwayPeriod>, sqlx::Error>;
+}
+
+impl HomieAvailability for Pool<Sqlite> {
+    #[tracing::instrument(skip(self))]
+    async fn add_homie_away_period(
+        &self,
+        homie_id: HomieId,
+        period: AwayPeriod,
+        user_id: UserId,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+insert into homie_away_periods (homie_id, user_id, starts_on, ends_on)
+values (?, ?, ?, ?)
+            "#,
+        )
+        .bind(homie_id.as_i32())
+        .bind(user_id.as_i32())
+        .bind(period.starts_on)
+        .bind(period.ends_on)
+        .execute(self)
+        .instrument(tracing::info_span!("Adding homie away period db query"))
+        .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_homie_away_periods(
+        &self,
+        user_id: UserId,
+    ) -> Result<Vec<HomieAwayPeriod>, sqlx::Error> {
+        let periods: Vec<HomieAwayPeriodRow> = sqlx::query_as(
+            r#"
+select a.homie_id, h.name as homie_name, a.starts_on, a.ends_on
+from homie_away_periods a
+         join homies h on h.id = a.homie_id and h.user_id = a.user_id
+where a.user_id = ?
+order by a.starts_on, a.ends_on, h.name
+            "#,
+        )
+        .bind(user_id.as_i32())
+        .fetch_all(self)
+        .instrument(tracing::info_span!("Getting homie away periods db query"))
+        .await?;
+        Ok(periods.into_iter().map(|p| p.into()).collect())
+    }
+}
@@ -0,0 +1,123 @@
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use sqlx::FromRow;
+use thiserror::Error;
+
+use crate::features::HomieId;
+
+const DATE_FORMAT: &str = "%Y-%m-%d";
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum AwayPeriodParseError {
+    #[error(
+        "Invalid away period {:?}, expected something like \"2026-11-02..2026-11-06\"",
+        period
+    )]
+    InvalidPeriod { period: String },
+}
+
+/// The days, both included, a homie isn't around for lunch.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct AwayPeriod {
+    pub starts_on: NaiveDate,
+    pub ends_on: NaiveDate,
+}
+
+impl AwayPeriod {
+    pub fn contains(&self, date: NaiveDate) -> bool {
+        self.starts_on <= date && date <= self.ends_on
+    }
+}
+
+impl Display for AwayPeriod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.starts_on == self.ends_on {
+            true => write!(f, "{}", self.starts_on.format(DATE_FORMAT)),
+            false => write!(
+                f,
+                "{}..{}",
+                self.starts_on.format(DATE_FORMAT),
+                self.ends_on.format(DATE_FORMAT)
+            ),
+        }
+    }
+}
+
+/// Parses `2026-11-02..2026-11-06`, or a single date for one day.
+impl FromStr for AwayPeriod {
+    type Err = AwayPeriodParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || AwayPeriodParseError::InvalidPeriod {
+            period: s.to_string(),
+        };
+        let (starts_on, ends_on) = s.trim().split_once("..").unwrap_or((s, s));
+        let starts_on =
+            NaiveDate::parse_from_str(starts_on.trim(), DATE_FORMAT).map_err(|_| invalid())?;
+        let ends_on =
+            NaiveDate::parse_from_str(ends_on.trim(), DATE_FORMAT).map_err(|_| invalid())?;
+        if ends_on < starts_on {
+            return Err(invalid());
+        }
+        Ok(Self { starts_on, ends_on })
+    }
+}
+
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct HomieAwayPeriod {
+    pub homie_id: HomieId,
+    pub homie_name: String,
+    pub period: AwayPeriod,
+}
+
+impl Display for HomieAwayPeriod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{} is away {}", self.homie_name, self.period)
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct HomieAwayPeriodRow {
+    homie_id: i32,
+    homie_name: String,
+    starts_on: NaiveDate,
+    ends_on: NaiveDate,
+}
+
+impl From<HomieAwayPeriodRow> for HomieAwayPeriod {
+    fn from(row: HomieAwayPeriodRow) -> Self {
+        Self {
+            homie_id: row.homie_id.into(),
+            homie_name: row.homie_name,
+            period: AwayPeriod {
+                starts_on: row.starts_on,
+                ends_on: row.ends_on,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::AwayPeriod;
+
+    #[test]
+    fn parses_ranges_and_single_days() {
+        let period: AwayPeriod = "2026-11-02..2026-11-06".parse().unwrap();
+
+        assert_eq!("2026-11-02..2026-11-06", period.to_string());
+        assert!(period.contains(NaiveDate::from_ymd_opt(2026, 11, 6).unwrap()));
+        assert!(!period.contains(NaiveDate::from_ymd_opt(2026, 11, 7).unwrap()));
+        assert_eq!(
+            "2026-11-02",
+            "2026-11-02".parse::<AwayPeriod>().unwrap().to_string()
+        );
+        assert!("2026-11-06..2026-11-02".parse::<AwayPeriod>().is_err());
+        assert!("next week".parse::<AwayPeriod>().is_err());
+    }
+}
@@ -0,0 +1,7 @@
+mod excluded_favorites;
+mod models;
+mod restrictions;
+
+pub use excluded_favorites::*;
+pub use models::*;
+pub use restrictions::*;
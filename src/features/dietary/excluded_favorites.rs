@@ -0,0 +1,130 @@
+use std::fmt::Debug;
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use sqlx::FromRow;
+use sqlx::Pool;
+
+use sqlx::Sqlite;
+use thiserror::Error;
+use tracing::Instrument;
+
+use crate::features::HomieId;
+use crate::user::UserId;
+
+use super::DietaryRestriction;
+
+/// A home homie's favorite that was left out of the candidates because it
+/// can't cater for someone at the table.
+#[derive(Debug, PartialEq, Eq)]
+pub struct ExcludedFavorite {
+    pub restaurant_name: String,
+    pub homie_name: String,
+    pub restriction: DietaryRestriction,
+}
+
+impl Display for ExcludedFavorite {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{} was skipped: it can't accommodate {} ({})",
+            self.restaurant_name, self.homie_name, self.restriction
+        )
+    }
+}
+
+#[derive(Debug, FromRow)]
+struct ExcludedFavoriteRow {
+    restaurant_name: String,
+    homie_name: String,
+    restriction: String,
+}
+
+impl TryFrom<ExcludedFavoriteRow> for ExcludedFavorite {
+    type Error = super::DietaryRestrictionParseError;
+
+    fn try_from(row: ExcludedFavoriteRow) -> Result<Self, Self::Error> {
+        Ok(Self {
+            restaurant_name: row.restaurant_name,
+            homie_name: row.homie_name,
+            restriction: row.restriction.parse()?,
+        })
+    }
+}
+
+#[tracing::instrument(skip(db))]
+pub async fn get_dietary_excluded_favorites<T, Y>(
+    homie_ids: T,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl GetDietaryExcludedFavorites,
+) -> Result<Vec<ExcludedFavorite>, GetDietaryExcludedFavoritesError>
+where
+    T: IntoIterator<Item = Y> + Debug,
+    Y: Into<HomieId> + Debug,
+{
+    let homie_ids: Vec<HomieId> = homie_ids.into_iter().map(|id| id.into()).collect();
+
+    let excluded = db
+        .get_dietary_excluded_favorites(&homie_ids, user_id.into())
+        .await?;
+
+    Ok(excluded)
+}
+
+#[derive(Error, Debug)]
+pub enum GetDietaryExcludedFavoritesError {
+    #[error(transparent)]
+    DbError(#[from] sqlx::Error),
+}
+
+pub trait GetDietaryExcludedFavorites {
+    async fn get_dietary_excluded_favorites(
+        &self,
+        home_homies: &[HomieId],
+        user_id: UserId,
+    ) -> Result<Vec<ExcludedFavorite>, sqlx::Error>;
+}
+
+impl GetDietaryExcludedFavorites for Pool<Sqlite> {
+    #[tracing::instrument(skip(self))]
+    async fn get_dietary_excluded_favorites(
+        &self,
+        home_homies: &[HomieId],
+        user_id: UserId,
+    ) -> Result<Vec<ExcludedFavorite>, sqlx::Error> {
+        let rows: Vec<ExcludedFavoriteRow> = sqlx::query_as(
+            r#"
+with home_homies AS (SELECT value as homie_id FROM json_each(?))
+select distinct r.name as restaurant_name, h.name as homie_name, d.restriction
+from homies_favorite_restaurants hfr
+         join home_homies fav on fav.homie_id = hfr.homie_id
+         join restaurants r on r.id = hfr.restaurant_id and r.user_id = hfr.user_id
+         join homies_dietary_restrictions d on d.user_id = r.user_id
+         join home_homies hh on hh.homie_id = d.homie_id
+         join homies h on h.id = d.homie_id
+where r.user_id = ?
+  and not exists (select 1
+                  from restaurant_dietary_accommodations a
+                  where a.restaurant_id = r.id
+                    and a.restriction = d.restriction)
+order by r.name, h.name, d.restriction
+            "#,
+        )
+        .bind(
+            serde_json::to_string(&home_homies.iter().map(|h| h.as_i32()).collect::<Vec<i32>>())
+                .expect("unable to serialize list of home homie ids as json"),
+        )
+        .bind(user_id.as_i32())
+        .fetch_all(self)
+        .instrument(tracing::info_span!(
+            "Getting favorites excluded by dietary restrictions"
+        ))
+        .await?;
+
+        rows.into_iter()
+            .map(|row| {
+                ExcludedFavorite::try_from(row).map_err(|e| sqlx::Error::Decode(Box::new(e)))
+            })
+            .collect()
+    }
+}
@@ -0,0 +1,92 @@
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum DietaryRestrictionParseError {
+    #[error("Unknown dietary restriction: {:?}", restriction)]
+    UnknownRestriction { restriction: String },
+}
+
+/// A dietary need a homie has, or one a restaurant is able to accommodate.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum DietaryRestriction {
+    Vegetarian,
+    Vegan,
+    GlutenFree,
+    NutFree,
+    DairyFree,
+    Halal,
+    Kosher,
+}
+
+impl DietaryRestriction {
+    pub const ALL: [DietaryRestriction; 7] = [
+        DietaryRestriction::Vegetarian,
+        DietaryRestriction::Vegan,
+        DietaryRestriction::GlutenFree,
+        DietaryRestriction::NutFree,
+        DietaryRestriction::DairyFree,
+        DietaryRestriction::Halal,
+        DietaryRestriction::Kosher,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            DietaryRestriction::Vegetarian => "vegetarian",
+            DietaryRestriction::Vegan => "vegan",
+            DietaryRestriction::GlutenFree => "gluten-free",
+            DietaryRestriction::NutFree => "nut-free",
+            DietaryRestriction::DairyFree => "dairy-free",
+            DietaryRestriction::Halal => "halal",
+            DietaryRestriction::Kosher => "kosher",
+        }
+    }
+}
+
+impl Display for DietaryRestriction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for DietaryRestriction {
+    type Err = DietaryRestrictionParseError;
+
+    fn from_str(restriction: &str) -> Result<Self, Self::Err> {
+        match restriction.trim().to_lowercase().as_str() {
+            "vegetarian" => Ok(DietaryRestriction::Vegetarian),
+            "vegan" => Ok(DietaryRestriction::Vegan),
+            "gluten-free" | "celiac" => Ok(DietaryRestriction::GlutenFree),
+            "nut-free" | "nut-allergy" => Ok(DietaryRestriction::NutFree),
+            "dairy-free" | "lactose-intolerant" => Ok(DietaryRestriction::DairyFree),
+            "halal" => Ok(DietaryRestriction::Halal),
+            "kosher" => Ok(DietaryRestriction::Kosher),
+            _ => Err(DietaryRestrictionParseError::UnknownRestriction {
+                restriction: restriction.to_string(),
+            }),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::DietaryRestriction;
+
+    #[test]
+    fn restrictions_round_trip() {
+        for restriction in DietaryRestriction::ALL {
+            assert_eq!(restriction, restriction.as_str().parse().unwrap());
+        }
+    }
+
+    #[test]
+    fn celiac_means_gluten_free() {
+        assert_eq!(
+            DietaryRestriction::GlutenFree,
+            "Celiac".parse::<DietaryRestriction>().unwrap()
+        );
+    }
+}
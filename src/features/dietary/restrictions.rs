@@ -0,0 +1,170 @@
+use std::fmt::Debug;
+
+use sqlx::Pool;
+
+use sqlx::Sqlite;
+use thiserror::Error;
+use tracing::Instrument;
+
+use crate::features::HomieNameValidationError;
+use crate::features::HomiesName;
+use crate::user::UserId;
+
+use super::DietaryRestriction;
+
+#[tracing::instrument(skip(db))]
+pub async fn add_homies_dietary_restriction(
+    homie_name: impl TryInto<HomiesName, Error = HomieNameValidationError> + Debug,
+    restriction: DietaryRestriction,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl HomiesDietaryRestrictions,
+) -> Result<(), HomiesDietaryRestrictionError> {
+    let params =
+        HomiesDietaryRestrictionParams::new(user_id.into(), homie_name.try_into()?, restriction);
+
+    db.add_homies_dietary_restriction(&params)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db_error) if db_error.is_unique_violation() => {
+                HomiesDietaryRestrictionError::HomieAlreadyHasRestriction {
+                    name: params.name.as_str().to_string(),
+                    restriction,
+                }
+            }
+            sqlx::Error::RowNotFound => HomiesDietaryRestrictionError::HomieNotFound {
+                name: params.name.as_str().to_string(),
+            },
+            _ => HomiesDietaryRestrictionError::UnknownDbError(e),
+        })?;
+
+    Ok(())
+}
+
+#[tracing::instrument(skip(db))]
+pub async fn remove_homies_dietary_restriction(
+    homie_name: impl TryInto<HomiesName, Error = HomieNameValidationError> + Debug,
+    restriction: DietaryRestriction,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl HomiesDietaryRestrictions,
+) -> Result<(), HomiesDietaryRestrictionError> {
+    let params =
+        HomiesDietaryRestrictionParams::new(user_id.into(), homie_name.try_into()?, restriction);
+
+    db.remove_homies_dietary_restriction(&params)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => HomiesDietaryRestrictionError::NoRestrictionRemoved,
+            _ => HomiesDietaryRestrictionError::UnknownDbError(e),
+        })?;
+
+    Ok(())
+}
+
+#[derive(Debug)]
+pub struct HomiesDietaryRestrictionParams {
+    user_id: UserId,
+    name: HomiesName,
+    restriction: DietaryRestriction,
+}
+
+impl HomiesDietaryRestrictionParams {
+    fn new(user_id: UserId, name: HomiesName, restriction: DietaryRestriction) -> Self {
+        Self {
+            user_id,
+            name,
+            restriction,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum HomiesDietaryRestrictionError {
+    #[error(transparent)]
+    HomieNameValidationError(#[from] HomieNameValidationError),
+
+    #[error("Homie not found: {:?}", name)]
+    HomieNotFound { name: String },
+
+    #[error("{:?} is already {}", name, restriction)]
+    HomieAlreadyHasRestriction {
+        name: String,
+        restriction: DietaryRestriction,
+    },
+
+    #[error("No dietary restriction removed")]
+    NoRestrictionRemoved,
+
+    #[error("Unknown db error")]
+    UnknownDbError(#[from] sqlx::Error),
+}
+
+pub trait HomiesDietaryRestrictions {
+    async fn add_homies_dietary_restriction(
+        &self,
+        params: &HomiesDietaryRestrictionParams,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn remove_homies_dietary_restriction(
+        &self,
+        params: &HomiesDietaryRestrictionParams,
+    ) -> Result<(), sqlx::Error>;
+}
+
+impl HomiesDietaryRestrictions for Pool<Sqlite> {
+    #[tracing::instrument(skip(self))]
+    async fn add_homies_dietary_restriction(
+        &self,
+        params: &HomiesDietaryRestrictionParams,
+    ) -> Result<(), sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+insert into homies_dietary_restrictions (homie_id, user_id, restriction)
+select h.id, h.user_id, ?
+from homies h
+where h.name = ?
+  and h.user_id = ?
+            "#,
+        )
+        .bind(params.restriction.as_str())
+        .bind(params.name.as_str())
+        .bind(params.user_id.as_i32())
+        .execute(self)
+        .instrument(tracing::info_span!(
+            "Adding dietary restriction to homie db query"
+        ))
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn remove_homies_dietary_restriction(
+        &self,
+        params: &HomiesDietaryRestrictionParams,
+    ) -> Result<(), sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+delete
+from homies_dietary_restrictions
+where restriction = ?
+  and user_id = ?
+  and homie_id = (select id from homies where name = ? and user_id = ?)
+            "#,
+        )
+        .bind(params.restriction.as_str())
+        .bind(params.user_id.as_i32())
+        .bind(params.name.as_str())
+        .bind(params.user_id.as_i32())
+        .execute(self)
+        .instrument(tracing::info_span!(
+            "Removing dietary restriction from homie db query"
+        ))
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,4 @@
+mod models;
+mod satisfaction;
+pub use models::*;
+pub use satisfaction::*;
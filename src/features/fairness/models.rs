@@ -0,0 +1,133 @@
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use sqlx::FromRow;
+
+use crate::features::HomieId;
+
+/// How many weeks of lunches count towards a homie's satisfaction when picking.
+pub const FAIRNESS_WINDOW_WEEKS: u32 = 4;
+
+/// How strongly a homie who is behind the rest of the table is boosted.
+const FAIRNESS_WEIGHT: f64 = 2.0;
+
+/// How often a homie's lunches were at one of their favorites.
+#[derive(Debug, PartialEq, Clone)]
+pub struct HomieSatisfaction {
+    pub homie_id: HomieId,
+    pub homie_name: String,
+    pub lunches: i64,
+    pub satisfied: i64,
+}
+
+impl HomieSatisfaction {
+    /// `None` while the homie hasn't had any lunches in the window.
+    pub fn share(&self) -> Option<f64> {
+        match self.lunches {
+            0 => None,
+            lunches => Some(self.satisfied as f64 / lunches as f64),
+        }
+    }
+}
+
+impl Display for HomieSatisfaction {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.share() {
+            Some(share) => write!(
+                f,
+                "{}: {} of {} lunches were a favorite ({:.0}%)",
+                self.homie_name,
+                self.satisfied,
+                self.lunches,
+                share * 100.0
+            ),
+            None => write!(f, "{}: no lunches", self.homie_name),
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct HomieSatisfactionRow {
+    homie_id: i32,
+    homie_name: String,
+    lunches: i64,
+    satisfied: i64,
+}
+
+impl From<HomieSatisfactionRow> for HomieSatisfaction {
+    fn from(row: HomieSatisfactionRow) -> Self {
+        Self {
+            homie_id: row.homie_id.into(),
+            homie_name: row.homie_name,
+            lunches: row.lunches,
+            satisfied: row.satisfied,
+        }
+    }
+}
+
+/// The factor a homie's favorites are weighted with so that whoever is behind gets a turn.
+#[derive(Debug, PartialEq, Clone)]
+pub struct FairnessBoost {
+    pub satisfaction: HomieSatisfaction,
+    pub boost: f64,
+}
+
+impl Display for FairnessBoost {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}, boost x{:.2}", self.satisfaction, self.boost)
+    }
+}
+
+/// Boosts homies whose share of favorite lunches is below the table's average share.
+///
+/// Homies without any lunches in the window aren't boosted, nor do they count towards
+/// the average.
+pub fn fairness_boosts(satisfaction: Vec<HomieSatisfaction>) -> Vec<FairnessBoost> {
+    let shares = satisfaction
+        .iter()
+        .filter_map(|s| s.share())
+        .collect::<Vec<f64>>();
+    let average = match shares.is_empty() {
+        true => 0.0,
+        false => shares.iter().sum::<f64>() / shares.len() as f64,
+    };
+
+    satisfaction
+        .into_iter()
+        .map(|s| FairnessBoost {
+            boost: s.share().map_or(1.0, |share| {
+                1.0 + FAIRNESS_WEIGHT * (average - share).max(0.0)
+            }),
+            satisfaction: s,
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::fairness_boosts;
+    use super::HomieSatisfaction;
+
+    fn satisfaction(homie_id: i32, lunches: i64, satisfied: i64) -> HomieSatisfaction {
+        HomieSatisfaction {
+            homie_id: homie_id.into(),
+            homie_name: homie_id.to_string(),
+            lunches,
+            satisfied,
+        }
+    }
+
+    #[test]
+    fn homies_behind_the_average_are_boosted() {
+        let boosts = fairness_boosts(vec![
+            satisfaction(1, 4, 4),
+            satisfaction(2, 4, 0),
+            satisfaction(3, 0, 0),
+        ]);
+
+        assert_eq!(
+            vec![1.0, 2.0, 1.0],
+            boosts.iter().map(|b| b.boost).collect::<Vec<f64>>()
+        );
+    }
+}
@@ -0,0 +1,122 @@
+use std::fmt::Debug;
+
+use chrono::Local;
+use chrono::NaiveDate;
+use sqlx::Pool;
+
+use sqlx::Sqlite;
+use thiserror::Error;
+use tracing::Instrument;
+
+use crate::features::HomieId;
+use crate::user::UserId;
+
+use super::fairness_boosts;
+use super::FairnessBoost;
+use super::HomieSatisfaction;
+use super::HomieSatisfactionRow;
+use super::FAIRNESS_WINDOW_WEEKS;
+
+/// Every homie's share of favorite lunches over the last `weeks` weeks.
+#[tracing::instrument(skip(db))]
+pub async fn get_fairness_report(
+    weeks: u32,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl GetHomiesSatisfaction,
+) -> Result<Vec<HomieSatisfaction>, GetHomiesSatisfactionError> {
+    Ok(db
+        .get_homies_satisfaction(None, weeks, Local::now().date_naive(), user_id.into())
+        .await?)
+}
+
+/// The boosts the home homies' favorites get when picking candidates on `date`.
+#[tracing::instrument(skip(db))]
+pub async fn get_fairness_boosts<T, Y>(
+    homie_ids: T,
+    date: NaiveDate,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl GetHomiesSatisfaction,
+) -> Result<Vec<FairnessBoost>, GetHomiesSatisfactionError>
+where
+    T: IntoIterator<Item = Y> + Debug,
+    Y: Into<HomieId> + Debug,
+{
+    let homie_ids: Vec<HomieId> = homie_ids.into_iter().map(|id| id.into()).collect();
+
+    let satisfaction = db
+        .get_homies_satisfaction(
+            Some(&homie_ids),
+            FAIRNESS_WINDOW_WEEKS,
+            date,
+            user_id.into(),
+        )
+        .await?;
+
+    Ok(fairness_boosts(satisfaction))
+}
+
+#[derive(Error, Debug)]
+pub enum GetHomiesSatisfactionError {
+    #[error(transparent)]
+    DbError(#[from] sqlx::Error),
+}
+
+pub trait GetHomiesSatisfaction {
+    /// Without `homie_ids` every homie of the user is included. Only lunches in the
+    /// `weeks` weeks up to `until` count.
+    async fn get_homies_satisfaction(
+        &self,
+        homie_ids: Option<&[HomieId]>,
+        weeks: u32,
+        until: NaiveDate,
+        user_id: UserId,
+    ) -> Result<Vec<HomieSatisfaction>, sqlx::Error>;
+}
+
+impl GetHomiesSatisfaction for Pool<Sqlite> {
+    #[tracing::instrument(skip(self))]
+    async fn get_homies_satisfaction(
+        &self,
+        homie_ids: Option<&[HomieId]>,
+        weeks: u32,
+        until: NaiveDate,
+        user_id: UserId,
+    ) -> Result<Vec<HomieSatisfaction>, sqlx::Error> {
+        let homie_ids = homie_ids.map(|ids| {
+            serde_json::to_string(&ids.iter().map(|h| h.as_i32()).collect::<Vec<i32>>())
+                .expect("unable to serialize list of homie ids as json")
+        });
+        let rows: Vec<HomieSatisfactionRow> = sqlx::query_as(
+            r#"
+with lunches as (select rr.homie_id,
+                        exists (select 1
+                                from homies_favorite_restaurants hfr
+                                where hfr.homie_id = rr.homie_id
+                                  and hfr.restaurant_id = rr.restaurant_id) as satisfied
+                 from recent_restaurants rr
+                 where rr.user_id = ?
+                   and rr.date >= date(?, '-' || (? * 7) || ' days')
+                   and rr.date <= ?)
+select h.id as homie_id, h.name as homie_name, count(l.homie_id) as lunches, coalesce(sum(l.satisfied), 0) as satisfied
+from homies h
+         left join lunches l on l.homie_id = h.id
+where h.user_id = ?
+  and (? is null or h.id in (select value from json_each(?)))
+group by h.id, h.name
+order by h.name
+            "#,
+        )
+        .bind(user_id.as_i32())
+        .bind(until)
+        .bind(weeks)
+        .bind(until)
+        .bind(user_id.as_i32())
+        .bind(homie_ids.as_deref())
+        .bind(homie_ids.as_deref())
+        .fetch_all(self)
+        .instrument(tracing::info_span!("Getting homies satisfaction db query"))
+        .await?;
+
+        Ok(rows.into_iter().map(|row| row.into()).collect())
+    }
+}
@@ -1,11 +1,16 @@
 use std::fmt::Debug;
 
+use sqlx::Pool;
+
+use sqlx::Sqlite;
 use thiserror::Error;
+use tracing::Instrument;
 
 use crate::features::Homie;
 use crate::user::UserId;
 
 use super::HomieNameValidationError;
+use super::HomieRow;
 use super::HomiesName;
 
 #[tracing::instrument(name = "Getting Homie by Name", skip(db))]
@@ -14,12 +19,19 @@ pub async fn get_homie(
     homie_name: impl TryInto<HomiesName, Error = HomieNameValidationError> + Debug,
     db: &impl GetHomie,
 ) -> Result<Homie, GetHomieError> {
+    let homie_name: HomiesName = homie_name.try_into()?;
     let retrieved_homie = db
         .get_homie(GetHomieParams::new(
             user_id.into().as_i32(),
-            homie_name.try_into().unwrap().as_str(),
+            homie_name.as_str(),
         ))
-        .await?;
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => GetHomieError::HomieNotFound {
+                name: homie_name.as_str().to_string(),
+            },
+            _ => GetHomieError::DbError(e),
+        })?;
 
     Ok(retrieved_homie)
 }
@@ -28,11 +40,13 @@ pub async fn get_homie(
 pub enum GetHomieError {
     #[error(transparent)]
     InvalidName(#[from] HomieNameValidationError),
+    #[error("Homie not found: {:?}", name)]
+    HomieNotFound { name: String },
     #[error(transparent)]
     DbError(#[from] sqlx::Error),
 }
 
-struct GetHomieParams<'a> {
+pub struct GetHomieParams<'a> {
     user_id: &'a i32,
     name: &'a str,
 }
@@ -42,9 +56,28 @@ impl<'a> GetHomieParams<'a> {
     }
 }
 
-trait GetHomie {
+pub trait GetHomie {
     async fn get_homie<'a>(
         &self,
         params: impl Into<GetHomieParams<'a>>,
     ) -> Result<Homie, sqlx::Error>;
 }
+
+impl GetHomie for Pool<Sqlite> {
+    #[tracing::instrument(name = "Getting Homie by Name", skip(self, params))]
+    async fn get_homie<'a>(
+        &self,
+        params: impl Into<GetHomieParams<'a>>,
+    ) -> Result<Homie, sqlx::Error> {
+        let params = params.into();
+        let homie: HomieRow = sqlx::query_as(
+            r#"select id, user_id, name from homies where user_id = ? and name = ?"#,
+        )
+        .bind(params.user_id)
+        .bind(params.name)
+        .fetch_one(self)
+        .instrument(tracing::info_span!("Querying homie by name"))
+        .await?;
+        Ok(homie.into())
+    }
+}
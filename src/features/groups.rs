@@ -0,0 +1,4 @@
+mod homie_groups;
+mod models;
+pub use homie_groups::*;
+pub use models::*;
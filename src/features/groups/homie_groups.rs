@@ -0,0 +1,313 @@
+use std::fmt::Debug;
+
+use sqlx::Pool;
+
+use sqlx::Sqlite;
+use thiserror::Error;
+use tracing::Instrument;
+
+use crate::features::get_homie;
+use crate::features::GetHomie;
+use crate::features::GetHomieError;
+use crate::features::HomieId;
+use crate::user::UserId;
+
+use super::collect_homie_groups;
+use super::HomieGroup;
+use super::HomieGroupMemberRow;
+
+/// Resolves the homies of a group, there has to be at least one.
+async fn get_group_homies(
+    homie_names: &[String],
+    user_id: UserId,
+    db: &impl HomieGroups,
+) -> Result<Vec<HomieId>, HomieGroupsError> {
+    if homie_names.is_empty() {
+        return Err(HomieGroupsError::NoHomies);
+    }
+    let mut homie_ids = Vec::new();
+    for name in homie_names {
+        let homie_id = get_homie(user_id, name.trim().to_string(), db).await?.id;
+        if !homie_ids.contains(&homie_id) {
+            homie_ids.push(homie_id);
+        }
+    }
+    Ok(homie_ids)
+}
+
+#[tracing::instrument(skip(db))]
+pub async fn add_homie_group(
+    name: &str,
+    homie_names: &[String],
+    user_id: impl Into<UserId> + Debug,
+    db: &impl HomieGroups,
+) -> Result<HomieGroup, HomieGroupsError> {
+    let user_id = user_id.into();
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(HomieGroupsError::EmptyName);
+    }
+    let homie_ids = get_group_homies(homie_names, user_id, db).await?;
+
+    db.add_homie_group(name, &homie_ids, user_id)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db_error) if db_error.is_unique_violation() => {
+                HomieGroupsError::GroupAlreadyExists {
+                    name: name.to_string(),
+                }
+            }
+            _ => HomieGroupsError::DbError(e),
+        })?;
+
+    get_homie_group(name, user_id, db).await
+}
+
+/// Replaces who is in a group.
+#[tracing::instrument(skip(db))]
+pub async fn edit_homie_group(
+    name: &str,
+    homie_names: &[String],
+    user_id: impl Into<UserId> + Debug,
+    db: &impl HomieGroups,
+) -> Result<HomieGroup, HomieGroupsError> {
+    let user_id = user_id.into();
+    let name = name.trim();
+    let homie_ids = get_group_homies(homie_names, user_id, db).await?;
+
+    db.set_homie_group_members(name, &homie_ids, user_id)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => HomieGroupsError::GroupNotFound {
+                name: name.to_string(),
+            },
+            _ => HomieGroupsError::DbError(e),
+        })?;
+
+    get_homie_group(name, user_id, db).await
+}
+
+#[tracing::instrument(skip(db))]
+pub async fn get_homie_groups(
+    user_id: impl Into<UserId> + Debug,
+    db: &impl HomieGroups,
+) -> Result<Vec<HomieGroup>, HomieGroupsError> {
+    Ok(db.get_homie_groups(user_id.into()).await?)
+}
+
+#[tracing::instrument(skip(db))]
+pub async fn get_homie_group(
+    name: &str,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl HomieGroups,
+) -> Result<HomieGroup, HomieGroupsError> {
+    db.get_homie_groups(user_id.into())
+        .await?
+        .into_iter()
+        .find(|g| g.name == name.trim())
+        .ok_or_else(|| HomieGroupsError::GroupNotFound {
+            name: name.to_string(),
+        })
+}
+
+/// The homies who were home for the last pick, to pre-select next time.
+#[tracing::instrument(skip(db))]
+pub async fn get_last_home_homies(
+    user_id: impl Into<UserId> + Debug,
+    db: &impl HomieGroups,
+) -> Result<Vec<HomieId>, HomieGroupsError> {
+    Ok(db.get_last_home_homies(user_id.into()).await?)
+}
+
+#[tracing::instrument(skip(db))]
+pub async fn remember_home_homies<T, Y>(
+    homie_ids: T,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl HomieGroups,
+) -> Result<(), HomieGroupsError>
+where
+    T: IntoIterator<Item = Y> + Debug,
+    Y: Into<HomieId> + Debug,
+{
+    let homie_ids: Vec<HomieId> = homie_ids.into_iter().map(|id| id.into()).collect();
+    Ok(db.set_last_home_homies(&homie_ids, user_id.into()).await?)
+}
+
+#[derive(Error, Debug)]
+pub enum HomieGroupsError {
+    #[error("Group name cannot be empty")]
+    EmptyName,
+
+    #[error("A group needs at least one homie")]
+    NoHomies,
+
+    #[error("Group already exists: {}", name)]
+    GroupAlreadyExists { name: String },
+
+    #[error("Group not found: {}", name)]
+    GroupNotFound { name: String },
+
+    #[error(transparent)]
+    GetHomieError(#[from] GetHomieError),
+
+    #[error(transparent)]
+    DbError(#[from] sqlx::Error),
+}
+
+pub trait HomieGroups: GetHomie {
+    async fn add_homie_group(
+        &self,
+        name: &str,
+        homie_ids: &[HomieId],
+        user_id: UserId,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn set_homie_group_members(
+        &self,
+        name: &str,
+        homie_ids: &[HomieId],
+        user_id: UserId,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn get_homie_groups(&self, user_id: UserId) -> Result<Vec<HomieGroup>, sqlx::Error>;
+
+    async fn get_last_home_homies(&self, user_id: UserId) -> Result<Vec<HomieId>, sqlx::Error>;
+
+    async fn set_last_home_homies(
+        &self,
+        homie_ids: &[HomieId],
+        user_id: UserId,
+    ) -> Result<(), sqlx::Error>;
+}
+
+impl HomieGroups for Pool<Sqlite> {
+    #[tracing::instrument(skip(self))]
+    async fn add_homie_group(
+        &self,
+        name: &str,
+        homie_ids: &[HomieId],
+        user_id: UserId,
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let group_id = sqlx::query(r#"insert into homie_groups (user_id, name) values (?, ?)"#)
+            .bind(user_id.as_i32())
+            .bind(name)
+            .execute(&mut *tx)
+            .instrument(tracing::info_span!("Adding homie group db query"))
+            .await?
+            .last_insert_rowid();
+        for homie_id in homie_ids {
+            sqlx::query(
+                r#"insert into homie_group_members (group_id, homie_id, user_id) values (?, ?, ?)"#,
+            )
+            .bind(group_id)
+            .bind(homie_id.as_i32())
+            .bind(user_id.as_i32())
+            .execute(&mut *tx)
+            .instrument(tracing::info_span!("Adding homie group member db query"))
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn set_homie_group_members(
+        &self,
+        name: &str,
+        homie_ids: &[HomieId],
+        user_id: UserId,
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.begin().await?;
+        // writing first takes the write lock before reading within the transaction
+        sqlx::query(
+            r#"
+delete
+from homie_group_members
+where group_id = (select id from homie_groups where user_id = ? and name = ?)
+            "#,
+        )
+        .bind(user_id.as_i32())
+        .bind(name)
+        .execute(&mut *tx)
+        .instrument(tracing::info_span!("Clearing homie group members db query"))
+        .await?;
+        let group_id: i64 =
+            sqlx::query_scalar(r#"select id from homie_groups where user_id = ? and name = ?"#)
+                .bind(user_id.as_i32())
+                .bind(name)
+                .fetch_one(&mut *tx)
+                .instrument(tracing::info_span!("Getting homie group db query"))
+                .await?;
+        for homie_id in homie_ids {
+            sqlx::query(
+                r#"insert into homie_group_members (group_id, homie_id, user_id) values (?, ?, ?)"#,
+            )
+            .bind(group_id)
+            .bind(homie_id.as_i32())
+            .bind(user_id.as_i32())
+            .execute(&mut *tx)
+            .instrument(tracing::info_span!("Adding homie group member db query"))
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_homie_groups(&self, user_id: UserId) -> Result<Vec<HomieGroup>, sqlx::Error> {
+        let members: Vec<HomieGroupMemberRow> = sqlx::query_as(
+            r#"
+select g.id as group_id, g.name as group_name, h.id as homie_id, h.name as homie_name
+from homie_groups g
+         join homie_group_members m on m.group_id = g.id
+         join homies h on h.id = m.homie_id and h.user_id = g.user_id
+where g.user_id = ?
+order by g.name, g.id, h.name
+            "#,
+        )
+        .bind(user_id.as_i32())
+        .fetch_all(self)
+        .instrument(tracing::info_span!("Getting homie groups db query"))
+        .await?;
+        Ok(collect_homie_groups(members))
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_last_home_homies(&self, user_id: UserId) -> Result<Vec<HomieId>, sqlx::Error> {
+        let homie_ids: Vec<i32> = sqlx::query_scalar(
+            r#"select homie_id from last_home_homies where user_id = ? order by homie_id"#,
+        )
+        .bind(user_id.as_i32())
+        .fetch_all(self)
+        .instrument(tracing::info_span!("Getting last home homies db query"))
+        .await?;
+        Ok(homie_ids.into_iter().map(|id| id.into()).collect())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn set_last_home_homies(
+        &self,
+        homie_ids: &[HomieId],
+        user_id: UserId,
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.begin().await?;
+        sqlx::query(r#"delete from last_home_homies where user_id = ?"#)
+            .bind(user_id.as_i32())
+            .execute(&mut *tx)
+            .instrument(tracing::info_span!("Clearing last home homies db query"))
+            .await?;
+        for homie_id in homie_ids {
+            sqlx::query(
+                r#"insert or ignore into last_home_homies (homie_id, user_id) values (?, ?)"#,
+            )
+            .bind(homie_id.as_i32())
+            .bind(user_id.as_i32())
+            .execute(&mut *tx)
+            .instrument(tracing::info_span!("Adding last home homie db query"))
+            .await?;
+        }
+        tx.commit().await?;
+        Ok(())
+    }
+}
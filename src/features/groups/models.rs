@@ -0,0 +1,57 @@
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use sqlx::FromRow;
+
+use crate::features::HomieId;
+
+/// A named set of homies, like "office crew", to pick who's home in one go.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct HomieGroup {
+    pub id: i64,
+    pub name: String,
+    pub homie_ids: Vec<HomieId>,
+    pub homie_names: Vec<String>,
+}
+
+impl HomieGroup {
+    /// Whether the group is exactly these homies.
+    pub fn is(&self, homie_ids: &[HomieId]) -> bool {
+        self.homie_ids.len() == homie_ids.len()
+            && self.homie_ids.iter().all(|h| homie_ids.contains(h))
+    }
+}
+
+impl Display for HomieGroup {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.name, self.homie_names.join(", "))
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct HomieGroupMemberRow {
+    pub group_id: i64,
+    pub group_name: String,
+    pub homie_id: i32,
+    pub homie_name: String,
+}
+
+/// Folds rows ordered by group into groups.
+pub fn collect_homie_groups(rows: Vec<HomieGroupMemberRow>) -> Vec<HomieGroup> {
+    let mut groups: Vec<HomieGroup> = Vec::new();
+    for row in rows {
+        match groups.last_mut() {
+            Some(group) if group.id == row.group_id => {
+                group.homie_ids.push(row.homie_id.into());
+                group.homie_names.push(row.homie_name);
+            }
+            _ => groups.push(HomieGroup {
+                id: row.group_id,
+                name: row.group_name,
+                homie_ids: vec![row.homie_id.into()],
+                homie_names: vec![row.homie_name],
+            }),
+        }
+    }
+    groups
+}
@@ -0,0 +1,2 @@
+mod models;
+pub use models::*;
@@ -0,0 +1,103 @@
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::features::CandidateOptions;
+use crate::features::DietaryRestriction;
+use crate::features::RestaurantTag;
+use crate::features::RestaurantTagValidationError;
+
+#[derive(Error, Debug)]
+pub enum GuestParseError {
+    #[error("A guest needs a name, like \"Sam:vegetarian,thai\"")]
+    EmptyName,
+
+    #[error(transparent)]
+    InvalidPreference(#[from] RestaurantTagValidationError),
+}
+
+/// Someone joining lunch once, who isn't stored as a homie.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Guest {
+    pub name: String,
+    pub dietary_restrictions: Vec<DietaryRestriction>,
+    pub preferred_tags: Vec<RestaurantTag>,
+}
+
+impl Display for Guest {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let preferences = self
+            .dietary_restrictions
+            .iter()
+            .map(|d| d.as_str())
+            .chain(self.preferred_tags.iter().map(|t| t.as_str()))
+            .collect::<Vec<_>>();
+        match preferences.is_empty() {
+            true => write!(f, "{}", self.name),
+            false => write!(f, "{} ({})", self.name, preferences.join(", ")),
+        }
+    }
+}
+
+/// Parses `Sam:vegetarian,thai`, where anything that isn't a dietary restriction is a
+/// tag the guest would like.
+impl FromStr for Guest {
+    type Err = GuestParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let (name, preferences) = s.split_once(':').unwrap_or((s, ""));
+        let name = name.trim();
+        if name.is_empty() {
+            return Err(GuestParseError::EmptyName);
+        }
+
+        let mut dietary_restrictions = Vec::new();
+        let mut preferred_tags = Vec::new();
+        for preference in preferences.split(',').filter(|p| !p.trim().is_empty()) {
+            match preference.parse::<DietaryRestriction>() {
+                Ok(restriction) => dietary_restrictions.push(restriction),
+                Err(_) => preferred_tags.push(preference.parse()?),
+            }
+        }
+        Ok(Self {
+            name: name.to_string(),
+            dietary_restrictions,
+            preferred_tags,
+        })
+    }
+}
+
+/// Folds the guests into the options: restaurants have to accommodate their dietary
+/// restrictions and the tags they like are preferred.
+pub fn apply_guests(options: &mut CandidateOptions, guests: &[Guest]) {
+    for guest in guests {
+        options
+            .dietary_restrictions
+            .extend(guest.dietary_restrictions.iter().copied());
+        options
+            .prefer_tags
+            .extend(guest.preferred_tags.iter().cloned());
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::Guest;
+    use crate::features::DietaryRestriction;
+
+    #[test]
+    fn parses_restrictions_and_preferences() {
+        let guest: Guest = "Sam:vegetarian, thai".parse().unwrap();
+
+        assert_eq!("Sam", guest.name);
+        assert_eq!(
+            vec![DietaryRestriction::Vegetarian],
+            guest.dietary_restrictions
+        );
+        assert_eq!("Sam (vegetarian, thai)", guest.to_string());
+        assert_eq!("Sam", "Sam".parse::<Guest>().unwrap().to_string());
+        assert!(":vegan".parse::<Guest>().is_err());
+    }
+}
@@ -27,7 +27,7 @@ pub async fn create_homie(
 }
 
 #[derive(Debug)]
-struct CreateHomieParams<'a> {
+pub struct CreateHomieParams<'a> {
     user_id: i32,
     name: &'a str,
 }
@@ -59,7 +59,7 @@ pub enum CreateHomieError {
     Unknown,
 }
 
-pub(crate) trait CreateHomie {
+pub trait CreateHomie {
     async fn create_homie<'a>(
         &self,
         params: CreateHomieParams<'a>,
@@ -1,6 +1,10 @@
 mod get_restaurants;
+mod models;
 mod remove_homies_favorite_restaurant;
 mod restaurants;
+mod set_strength;
 pub use get_restaurants::*;
+pub use models::*;
 pub use remove_homies_favorite_restaurant::*;
 pub use restaurants::*;
+pub use set_strength::*;
@@ -6,15 +6,18 @@ use sqlx::Sqlite;
 use thiserror::Error;
 use tracing::Instrument;
 
-use crate::features::{HomieId, Restaurant, RestaurantRow};
+use crate::features::HomieId;
 use crate::user::UserId;
 
+use super::FavoriteRestaurant;
+use super::FavoriteRestaurantRow;
+
 #[tracing::instrument(name = "Getting all Restaurants", skip(db))]
 pub async fn get_homies_favorite_restaurants(
     user_id: impl Into<UserId> + Debug,
     homie_id: impl Into<HomieId> + Debug,
     db: &impl GetHomiesFavoriteRestaurants,
-) -> Result<Vec<Restaurant>, GetHomiesFavoriteRestaurantsError> {
+) -> Result<Vec<FavoriteRestaurant>, GetHomiesFavoriteRestaurantsError> {
     let params = GetHomiesFavoriteRestaurantsParams::new(user_id.into(), homie_id.into());
     let retrieved_restaurants = db.get_homies_favorite_restaurants(&params).await?;
 
@@ -22,7 +25,7 @@ pub async fn get_homies_favorite_restaurants(
 }
 
 #[derive(Debug)]
-struct GetHomiesFavoriteRestaurantsParams {
+pub struct GetHomiesFavoriteRestaurantsParams {
     user_id: UserId,
     homie_id: HomieId,
 }
@@ -45,7 +48,7 @@ pub trait GetHomiesFavoriteRestaurants {
     async fn get_homies_favorite_restaurants(
         &self,
         params: &GetHomiesFavoriteRestaurantsParams,
-    ) -> Result<Vec<Restaurant>, sqlx::Error>;
+    ) -> Result<Vec<FavoriteRestaurant>, sqlx::Error>;
 }
 
 // todo: do we need the second user_id clause?
@@ -55,11 +58,11 @@ impl GetHomiesFavoriteRestaurants for Pool<Sqlite> {
     async fn get_homies_favorite_restaurants(
         &self,
         params: &GetHomiesFavoriteRestaurantsParams,
-    ) -> Result<Vec<Restaurant>, sqlx::Error> {
-        let restaurant: Vec<RestaurantRow> = sqlx::query_as(
+    ) -> Result<Vec<FavoriteRestaurant>, sqlx::Error> {
+        let restaurant: Vec<FavoriteRestaurantRow> = sqlx::query_as(
             r#"
-select id, r.user_id, name
-from restaurants r
+select r.*, homies_favorite_restaurants.strength
+from restaurants_view r
          inner join homies_favorite_restaurants
               on r.id = homies_favorite_restaurants.restaurant_id
                   and homies_favorite_restaurants.homie_id = ?
@@ -0,0 +1,103 @@
+use std::fmt::Display;
+use std::fmt::Formatter;
+
+use sqlx::FromRow;
+use thiserror::Error;
+
+use crate::features::Restaurant;
+use crate::features::RestaurantRow;
+
+#[derive(Error, Debug)]
+pub enum FavoriteStrengthValidationError {
+    #[error("Favorite strength must be between 1 and 3, got {}", strength)]
+    OutOfRange { strength: i32 },
+}
+
+/// How much a homie likes one of their favorites, from 1 (it's fine) to 3 (love it).
+/// Stronger favorites weigh more when ranking candidates.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct FavoriteStrength(u8);
+
+impl FavoriteStrength {
+    pub const ALL: [FavoriteStrength; 3] = [
+        FavoriteStrength(1),
+        FavoriteStrength(2),
+        FavoriteStrength(3),
+    ];
+
+    pub fn as_u8(&self) -> u8 {
+        self.0
+    }
+}
+
+impl Default for FavoriteStrength {
+    fn default() -> Self {
+        Self(2)
+    }
+}
+
+impl TryFrom<i32> for FavoriteStrength {
+    type Error = FavoriteStrengthValidationError;
+
+    fn try_from(strength: i32) -> Result<Self, Self::Error> {
+        match strength {
+            1..=3 => Ok(FavoriteStrength(strength as u8)),
+            _ => Err(FavoriteStrengthValidationError::OutOfRange { strength }),
+        }
+    }
+}
+
+impl TryFrom<u8> for FavoriteStrength {
+    type Error = FavoriteStrengthValidationError;
+
+    fn try_from(strength: u8) -> Result<Self, Self::Error> {
+        i32::from(strength).try_into()
+    }
+}
+
+impl Display for FavoriteStrength {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self.0 {
+            1 => f.write_str("it's fine"),
+            2 => f.write_str("like it"),
+            _ => f.write_str("love it"),
+        }
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct FavoriteRestaurantRow {
+    #[sqlx(flatten)]
+    restaurant: RestaurantRow,
+    strength: i32,
+}
+
+#[derive(Debug, PartialEq, Eq)]
+pub struct FavoriteRestaurant {
+    pub restaurant: Restaurant,
+    pub strength: FavoriteStrength,
+}
+
+impl From<FavoriteRestaurantRow> for FavoriteRestaurant {
+    fn from(row: FavoriteRestaurantRow) -> Self {
+        Self {
+            restaurant: row.restaurant.into(),
+            strength: row.strength.try_into().unwrap_or_default(),
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::FavoriteStrength;
+
+    #[test]
+    fn strength_must_be_between_one_and_three() {
+        assert!(FavoriteStrength::try_from(0).is_err());
+        assert!(FavoriteStrength::try_from(4).is_err());
+        assert_eq!(
+            "love it",
+            FavoriteStrength::try_from(3).unwrap().to_string()
+        );
+    }
+}
@@ -55,7 +55,7 @@ pub async fn remove_homies_favorite_restaurant(
 }
 
 #[derive(Debug)]
-struct RemoveFavoriteRestaurantFromHomieParams {
+pub struct RemoveFavoriteRestaurantFromHomieParams {
     user_id: UserId,
     name: HomiesName,
     restaurant_name: RestaurantName,
@@ -99,7 +99,7 @@ pub enum RemoveHomiesFavoriteRestaurantError {
 }
 
 pub trait RemoveFavoriteRestaurantFromHomie {
-    async fn remove_homies_favorite_restaurant<'a>(
+    async fn remove_homies_favorite_restaurant(
         &self,
         params: &RemoveFavoriteRestaurantFromHomieParams,
     ) -> Result<(), sqlx::Error>;
@@ -107,7 +107,7 @@ pub trait RemoveFavoriteRestaurantFromHomie {
 
 impl RemoveFavoriteRestaurantFromHomie for Pool<Sqlite> {
     #[tracing::instrument(skip(self))]
-    async fn remove_homies_favorite_restaurant<'a>(
+    async fn remove_homies_favorite_restaurant(
         &self,
         params: &RemoveFavoriteRestaurantFromHomieParams,
     ) -> Result<(), sqlx::Error> {
@@ -55,7 +55,7 @@ pub async fn add_homies_favorite_restaurant(
 }
 
 #[derive(Debug)]
-struct AddFavoriteRestaurantToHomieParams {
+pub struct AddFavoriteRestaurantToHomieParams {
     user_id: UserId,
     name: HomiesName,
     restaurant_name: RestaurantName,
@@ -99,7 +99,7 @@ pub enum AddHomiesFavoriteRestaurantError {
 }
 
 pub trait AddFavoriteRestaurantToHomie {
-    async fn add_homies_favorite_restaurant<'a>(
+    async fn add_homies_favorite_restaurant(
         &self,
         params: &AddFavoriteRestaurantToHomieParams,
     ) -> Result<(), sqlx::Error>;
@@ -107,7 +107,7 @@ pub trait AddFavoriteRestaurantToHomie {
 
 impl AddFavoriteRestaurantToHomie for Pool<Sqlite> {
     #[tracing::instrument(skip(self))]
-    async fn add_homies_favorite_restaurant<'a>(
+    async fn add_homies_favorite_restaurant(
         &self,
         params: &AddFavoriteRestaurantToHomieParams,
     ) -> Result<(), sqlx::Error> {
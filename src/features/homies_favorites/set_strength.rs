@@ -0,0 +1,125 @@
+use std::fmt::Debug;
+
+use sqlx::Pool;
+
+use sqlx::Sqlite;
+use thiserror::Error;
+use tracing::Instrument;
+
+use crate::features::HomieNameValidationError;
+use crate::features::HomiesName;
+use crate::features::RestaurantName;
+use crate::features::RestaurantNameValidationError;
+use crate::user::UserId;
+
+use super::FavoriteStrength;
+
+#[tracing::instrument(skip(db))]
+pub async fn set_homies_favorite_restaurant_strength(
+    homie_name: impl TryInto<HomiesName, Error = HomieNameValidationError> + Debug,
+    restaurant_name: impl TryInto<RestaurantName, Error = RestaurantNameValidationError> + Debug,
+    strength: FavoriteStrength,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl SetFavoriteRestaurantStrength,
+) -> Result<(), SetHomiesFavoriteRestaurantStrengthError> {
+    let params = SetFavoriteRestaurantStrengthParams::new(
+        user_id.into(),
+        homie_name.try_into()?,
+        restaurant_name.try_into()?,
+        strength,
+    );
+
+    db.set_homies_favorite_restaurant_strength(&params)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => SetHomiesFavoriteRestaurantStrengthError::NotAFavorite {
+                name: params.name.as_str().to_string(),
+                restaurant_name: params.restaurant_name.as_str().to_string(),
+            },
+            _ => SetHomiesFavoriteRestaurantStrengthError::UnknownDbError(e),
+        })?;
+
+    Ok(())
+}
+
+#[derive(Debug)]
+pub struct SetFavoriteRestaurantStrengthParams {
+    user_id: UserId,
+    name: HomiesName,
+    restaurant_name: RestaurantName,
+    strength: FavoriteStrength,
+}
+
+impl SetFavoriteRestaurantStrengthParams {
+    fn new(
+        user_id: UserId,
+        name: HomiesName,
+        restaurant_name: RestaurantName,
+        strength: FavoriteStrength,
+    ) -> Self {
+        Self {
+            user_id,
+            name,
+            restaurant_name,
+            strength,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum SetHomiesFavoriteRestaurantStrengthError {
+    #[error(transparent)]
+    HomieNameValidationError(#[from] HomieNameValidationError),
+
+    #[error(transparent)]
+    RestaurantNameValidationError(#[from] RestaurantNameValidationError),
+
+    #[error("{:?} hasn't favorited {:?}", name, restaurant_name)]
+    NotAFavorite {
+        name: String,
+        restaurant_name: String,
+    },
+
+    #[error("Unknown db error")]
+    UnknownDbError(#[from] sqlx::Error),
+}
+
+pub trait SetFavoriteRestaurantStrength {
+    async fn set_homies_favorite_restaurant_strength(
+        &self,
+        params: &SetFavoriteRestaurantStrengthParams,
+    ) -> Result<(), sqlx::Error>;
+}
+
+impl SetFavoriteRestaurantStrength for Pool<Sqlite> {
+    #[tracing::instrument(skip(self))]
+    async fn set_homies_favorite_restaurant_strength(
+        &self,
+        params: &SetFavoriteRestaurantStrengthParams,
+    ) -> Result<(), sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+update homies_favorite_restaurants
+set strength = ?
+where user_id = ?
+  and homie_id = (select id from homies where name = ? and user_id = ?)
+  and restaurant_id = (select id from restaurants where name = ? and user_id = ?)
+            "#,
+        )
+        .bind(params.strength.as_u8())
+        .bind(params.user_id.as_i32())
+        .bind(params.name.as_str())
+        .bind(params.user_id.as_i32())
+        .bind(params.restaurant_name.as_str())
+        .bind(params.user_id.as_i32())
+        .execute(self)
+        .instrument(tracing::info_span!(
+            "Setting favorite restaurant strength db query"
+        ))
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,6 @@
+mod get_restaurants;
+mod remove_homies_vetoed_restaurant;
+mod restaurants;
+pub use get_restaurants::*;
+pub use remove_homies_vetoed_restaurant::*;
+pub use restaurants::*;
@@ -0,0 +1,73 @@
+use std::fmt::Debug;
+
+use sqlx::Pool;
+
+use sqlx::Sqlite;
+use thiserror::Error;
+use tracing::Instrument;
+
+use crate::features::{HomieId, Restaurant, RestaurantRow};
+use crate::user::UserId;
+
+#[tracing::instrument(name = "Getting vetoed Restaurants", skip(db))]
+pub async fn get_homies_vetoed_restaurants(
+    user_id: impl Into<UserId> + Debug,
+    homie_id: impl Into<HomieId> + Debug,
+    db: &impl GetHomiesVetoedRestaurants,
+) -> Result<Vec<Restaurant>, GetHomiesVetoedRestaurantsError> {
+    let params = GetHomiesVetoedRestaurantsParams::new(user_id.into(), homie_id.into());
+    let retrieved_restaurants = db.get_homies_vetoed_restaurants(&params).await?;
+
+    Ok(retrieved_restaurants)
+}
+
+#[derive(Debug)]
+pub struct GetHomiesVetoedRestaurantsParams {
+    user_id: UserId,
+    homie_id: HomieId,
+}
+
+impl GetHomiesVetoedRestaurantsParams {
+    fn new(user_id: UserId, homie_id: HomieId) -> Self {
+        Self { user_id, homie_id }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum GetHomiesVetoedRestaurantsError {
+    #[error(transparent)]
+    DbError(#[from] sqlx::Error),
+}
+
+pub trait GetHomiesVetoedRestaurants {
+    async fn get_homies_vetoed_restaurants(
+        &self,
+        params: &GetHomiesVetoedRestaurantsParams,
+    ) -> Result<Vec<Restaurant>, sqlx::Error>;
+}
+
+impl GetHomiesVetoedRestaurants for Pool<Sqlite> {
+    #[tracing::instrument(name = "Getting vetoed Restaurants", skip(self))]
+    async fn get_homies_vetoed_restaurants(
+        &self,
+        params: &GetHomiesVetoedRestaurantsParams,
+    ) -> Result<Vec<Restaurant>, sqlx::Error> {
+        let restaurant: Vec<RestaurantRow> = sqlx::query_as(
+            r#"
+select r.*
+from restaurants_view r
+         inner join homies_vetoed_restaurants v
+                    on r.id = v.restaurant_id
+                        and v.homie_id = ?
+                        and v.user_id = r.user_id
+where r.user_id = ?
+"#,
+        )
+        .bind(params.homie_id.as_i32())
+        .bind(params.user_id.as_i32())
+        .fetch_all(self)
+        .instrument(tracing::info_span!("Querying vetoed restaurants"))
+        .await?;
+        Ok(restaurant.into_iter().map(|x| x.into()).collect())
+    }
+}
@@ -0,0 +1,107 @@
+use std::fmt::Debug;
+
+use sqlx::Pool;
+
+use sqlx::Sqlite;
+use thiserror::Error;
+use tracing::Instrument;
+
+use crate::features::HomieNameValidationError;
+use crate::features::HomiesName;
+use crate::features::RestaurantName;
+use crate::features::RestaurantNameValidationError;
+use crate::user::UserId;
+
+#[tracing::instrument(skip(db))]
+pub async fn remove_homies_vetoed_restaurant(
+    homie_name: impl TryInto<HomiesName, Error = HomieNameValidationError> + Debug,
+    restaurant_name: impl TryInto<RestaurantName, Error = RestaurantNameValidationError> + Debug,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl RemoveVetoedRestaurantFromHomie,
+) -> Result<(), RemoveHomiesVetoedRestaurantError> {
+    let params = RemoveVetoedRestaurantFromHomieParams::new(
+        user_id.into(),
+        homie_name.try_into()?,
+        restaurant_name.try_into()?,
+    );
+
+    db.remove_homies_vetoed_restaurant(&params)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => RemoveHomiesVetoedRestaurantError::NoVetoRemoved,
+            _ => RemoveHomiesVetoedRestaurantError::UnknownDbError(e),
+        })?;
+
+    Ok(())
+}
+
+#[derive(Debug)]
+pub struct RemoveVetoedRestaurantFromHomieParams {
+    user_id: UserId,
+    name: HomiesName,
+    restaurant_name: RestaurantName,
+}
+
+impl RemoveVetoedRestaurantFromHomieParams {
+    fn new(user_id: UserId, name: HomiesName, restaurant_name: RestaurantName) -> Self {
+        Self {
+            user_id,
+            name,
+            restaurant_name,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum RemoveHomiesVetoedRestaurantError {
+    #[error(transparent)]
+    HomieNameValidationError(#[from] HomieNameValidationError),
+
+    #[error(transparent)]
+    RestaurantNameValidationError(#[from] RestaurantNameValidationError),
+
+    #[error("No veto removed")]
+    NoVetoRemoved,
+
+    #[error("Unknown db error")]
+    UnknownDbError(#[from] sqlx::Error),
+}
+
+pub trait RemoveVetoedRestaurantFromHomie {
+    async fn remove_homies_vetoed_restaurant(
+        &self,
+        params: &RemoveVetoedRestaurantFromHomieParams,
+    ) -> Result<(), sqlx::Error>;
+}
+
+impl RemoveVetoedRestaurantFromHomie for Pool<Sqlite> {
+    #[tracing::instrument(skip(self))]
+    async fn remove_homies_vetoed_restaurant(
+        &self,
+        params: &RemoveVetoedRestaurantFromHomieParams,
+    ) -> Result<(), sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+delete
+from homies_vetoed_restaurants
+where user_id = ?
+  and homie_id = (select id from homies where name = ? and user_id = ?)
+  and restaurant_id = (select id from restaurants where name = ? and user_id = ?)
+            "#,
+        )
+        .bind(params.user_id.as_i32())
+        .bind(params.name.as_str())
+        .bind(params.user_id.as_i32())
+        .bind(params.restaurant_name.as_str())
+        .bind(params.user_id.as_i32())
+        .execute(self)
+        .instrument(tracing::info_span!(
+            "Removing vetoed restaurant from homie db query"
+        ))
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,131 @@
+use std::fmt::Debug;
+
+use sqlx::Pool;
+
+use sqlx::Sqlite;
+use thiserror::Error;
+use tracing::Instrument;
+
+use crate::features::HomieNameValidationError;
+use crate::features::HomiesName;
+use crate::features::RestaurantName;
+use crate::features::RestaurantNameValidationError;
+use crate::user::UserId;
+
+#[tracing::instrument(skip(db))]
+pub async fn add_homies_vetoed_restaurant(
+    homie_name: impl TryInto<HomiesName, Error = HomieNameValidationError> + Debug,
+    restaurant_name: impl TryInto<RestaurantName, Error = RestaurantNameValidationError> + Debug,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl AddVetoedRestaurantToHomie,
+) -> Result<(), AddHomiesVetoedRestaurantError> {
+    let params = AddVetoedRestaurantToHomieParams::new(
+        user_id.into(),
+        homie_name.try_into()?,
+        restaurant_name.try_into()?,
+    );
+
+    db.add_homies_vetoed_restaurant(&params)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db_error) => {
+                if db_error.is_unique_violation() {
+                    return AddHomiesVetoedRestaurantError::HomieAlreadyHasVeto {
+                        name: params.name.as_str().to_string(),
+                        restaurant_name: params.restaurant_name.as_str().to_string(),
+                    };
+                } else if db_error.is_foreign_key_violation() {
+                    return AddHomiesVetoedRestaurantError::ForeignKeyViolation {
+                        constraint: db_error
+                            .constraint()
+                            .expect("Constraint should be named if it is a ForeignKeyViolation")
+                            .to_string(),
+                    };
+                }
+                AddHomiesVetoedRestaurantError::UnknownDbError(sqlx::Error::Database(db_error))
+            }
+            sqlx::Error::RowNotFound => AddHomiesVetoedRestaurantError::NoVetoAdded,
+            _ => AddHomiesVetoedRestaurantError::UnknownDbError(e),
+        })?;
+
+    Ok(())
+}
+
+#[derive(Debug)]
+pub struct AddVetoedRestaurantToHomieParams {
+    user_id: UserId,
+    name: HomiesName,
+    restaurant_name: RestaurantName,
+}
+
+impl AddVetoedRestaurantToHomieParams {
+    fn new(user_id: UserId, name: HomiesName, restaurant_name: RestaurantName) -> Self {
+        Self {
+            user_id,
+            name,
+            restaurant_name,
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum AddHomiesVetoedRestaurantError {
+    #[error(transparent)]
+    HomieNameValidationError(#[from] HomieNameValidationError),
+
+    #[error(transparent)]
+    RestaurantNameValidationError(#[from] RestaurantNameValidationError),
+
+    #[error("No veto added")]
+    NoVetoAdded,
+
+    #[error("Invalid User")]
+    ForeignKeyViolation { constraint: String },
+
+    #[error("{:?} already vetoed {:?}", name, restaurant_name)]
+    HomieAlreadyHasVeto {
+        name: String,
+        restaurant_name: String,
+    },
+
+    #[error("Unknown db error")]
+    UnknownDbError(#[from] sqlx::Error),
+}
+
+pub trait AddVetoedRestaurantToHomie {
+    async fn add_homies_vetoed_restaurant(
+        &self,
+        params: &AddVetoedRestaurantToHomieParams,
+    ) -> Result<(), sqlx::Error>;
+}
+
+impl AddVetoedRestaurantToHomie for Pool<Sqlite> {
+    #[tracing::instrument(skip(self))]
+    async fn add_homies_vetoed_restaurant(
+        &self,
+        params: &AddVetoedRestaurantToHomieParams,
+    ) -> Result<(), sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+insert into homies_vetoed_restaurants (homie_id, user_id, restaurant_id)
+select h.id, h.user_id, r.id
+from homies h
+         join restaurants r on r.name = ? and r.user_id = h.user_id
+where h.name = ?
+  and h.user_id = ?
+            "#,
+        )
+        .bind(params.restaurant_name.as_str())
+        .bind(params.name.as_str())
+        .bind(params.user_id.as_i32())
+        .execute(self)
+        .instrument(tracing::info_span!(
+            "Adding vetoed restaurant to homie db query"
+        ))
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        Ok(())
+    }
+}
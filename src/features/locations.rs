@@ -0,0 +1,4 @@
+mod models;
+mod user_locations;
+pub use models::*;
+pub use user_locations::*;
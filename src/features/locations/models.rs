@@ -0,0 +1,144 @@
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+use sqlx::FromRow;
+use thiserror::Error;
+
+/// Mean radius of the earth, which is close enough for lunch.
+const EARTH_RADIUS_KM: f64 = 6371.0;
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum CoordinatesParseError {
+    #[error(
+        "Invalid coordinates {:?}, expected latitude and longitude like \"52.5200,13.4050\"",
+        coordinates
+    )]
+    InvalidCoordinates { coordinates: String },
+}
+
+/// A point on the map in decimal degrees.
+#[derive(Debug, PartialEq, Clone, Copy)]
+pub struct Coordinates {
+    latitude: f64,
+    longitude: f64,
+}
+
+// the degrees are always finite, so equality is total
+impl Eq for Coordinates {}
+
+impl Coordinates {
+    pub fn new(latitude: f64, longitude: f64) -> Option<Self> {
+        ((-90.0..=90.0).contains(&latitude) && (-180.0..=180.0).contains(&longitude)).then_some(
+            Self {
+                latitude,
+                longitude,
+            },
+        )
+    }
+
+    pub fn latitude(&self) -> f64 {
+        self.latitude
+    }
+
+    pub fn longitude(&self) -> f64 {
+        self.longitude
+    }
+
+    /// The great-circle distance between two points, using the haversine formula.
+    pub fn distance_km(&self, other: &Coordinates) -> f64 {
+        let (lat1, lat2) = (self.latitude.to_radians(), other.latitude.to_radians());
+        let d_lat = lat2 - lat1;
+        let d_lon = (other.longitude - self.longitude).to_radians();
+        let a = (d_lat / 2.0).sin().powi(2) + lat1.cos() * lat2.cos() * (d_lon / 2.0).sin().powi(2);
+        2.0 * EARTH_RADIUS_KM * a.sqrt().min(1.0).asin()
+    }
+}
+
+impl Display for Coordinates {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{},{}", self.latitude, self.longitude)
+    }
+}
+
+/// Parses `52.5200,13.4050`, latitude first.
+impl FromStr for Coordinates {
+    type Err = CoordinatesParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || CoordinatesParseError::InvalidCoordinates {
+            coordinates: s.to_string(),
+        };
+        let (latitude, longitude) = s.split_once(',').ok_or_else(invalid)?;
+        let latitude = latitude.trim().parse().map_err(|_| invalid())?;
+        let longitude = longitude.trim().parse().map_err(|_| invalid())?;
+        Self::new(latitude, longitude).ok_or_else(invalid)
+    }
+}
+
+/// How far away something is, rounded for reading.
+pub fn format_distance(km: f64) -> String {
+    match km < 1.0 {
+        true => format!("{:.0} m", km * 1000.0),
+        false => format!("{:.1} km", km),
+    }
+}
+
+/// A named place lunch starts from, like "office" or "home".
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct UserLocation {
+    pub id: i64,
+    pub name: String,
+    pub coordinates: Coordinates,
+}
+
+impl Display for UserLocation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.name, self.coordinates)
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct UserLocationRow {
+    id: i64,
+    name: String,
+    latitude: f64,
+    longitude: f64,
+}
+
+impl From<UserLocationRow> for UserLocation {
+    fn from(row: UserLocationRow) -> Self {
+        Self {
+            id: row.id,
+            name: row.name,
+            coordinates: Coordinates {
+                latitude: row.latitude,
+                longitude: row.longitude,
+            },
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::format_distance;
+    use super::Coordinates;
+
+    #[test]
+    fn measures_great_circle_distances() {
+        let brandenburg_gate: Coordinates = "52.5163,13.3777".parse().unwrap();
+        let alexanderplatz: Coordinates = "52.5219, 13.4132".parse().unwrap();
+        let paris: Coordinates = "48.8566,2.3522".parse().unwrap();
+
+        let nearby = brandenburg_gate.distance_km(&alexanderplatz);
+        let far = brandenburg_gate.distance_km(&paris);
+
+        assert!((2.4..2.5).contains(&nearby), "{}", nearby);
+        assert!((875.0..885.0).contains(&far), "{}", far);
+        assert_eq!(0.0, paris.distance_km(&paris));
+        assert_eq!("2.5 km", format_distance(nearby));
+        assert_eq!("350 m", format_distance(0.35));
+        assert!("91,0".parse::<Coordinates>().is_err());
+        assert!("office".parse::<Coordinates>().is_err());
+    }
+}
@@ -0,0 +1,155 @@
+use std::fmt::Debug;
+
+use sqlx::Pool;
+
+use sqlx::Sqlite;
+use thiserror::Error;
+use tracing::Instrument;
+
+use crate::user::UserId;
+
+use super::Coordinates;
+use super::UserLocation;
+use super::UserLocationRow;
+
+/// Saves where a named location is, moving it when it already exists.
+#[tracing::instrument(skip(db))]
+pub async fn set_user_location(
+    name: &str,
+    coordinates: Coordinates,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl UserLocations,
+) -> Result<(), UserLocationsError> {
+    let name = name.trim();
+    if name.is_empty() {
+        return Err(UserLocationsError::EmptyName);
+    }
+
+    db.set_user_location(name, coordinates, user_id.into())
+        .await?;
+
+    Ok(())
+}
+
+#[tracing::instrument(skip(db))]
+pub async fn remove_user_location(
+    name: &str,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl UserLocations,
+) -> Result<(), UserLocationsError> {
+    db.remove_user_location(name.trim(), user_id.into())
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => UserLocationsError::LocationNotFound {
+                name: name.to_string(),
+            },
+            _ => UserLocationsError::DbError(e),
+        })
+}
+
+#[tracing::instrument(skip(db))]
+pub async fn get_user_locations(
+    user_id: impl Into<UserId> + Debug,
+    db: &impl UserLocations,
+) -> Result<Vec<UserLocation>, UserLocationsError> {
+    Ok(db.get_user_locations(user_id.into()).await?)
+}
+
+#[tracing::instrument(skip(db))]
+pub async fn get_user_location(
+    name: &str,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl UserLocations,
+) -> Result<UserLocation, UserLocationsError> {
+    db.get_user_locations(user_id.into())
+        .await?
+        .into_iter()
+        .find(|l| l.name == name.trim())
+        .ok_or_else(|| UserLocationsError::LocationNotFound {
+            name: name.to_string(),
+        })
+}
+
+#[derive(Error, Debug)]
+pub enum UserLocationsError {
+    #[error("Location name cannot be empty")]
+    EmptyName,
+
+    #[error("Location not found: {}", name)]
+    LocationNotFound { name: String },
+
+    #[error(transparent)]
+    DbError(#[from] sqlx::Error),
+}
+
+pub trait UserLocations {
+    async fn set_user_location(
+        &self,
+        name: &str,
+        coordinates: Coordinates,
+        user_id: UserId,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn remove_user_location(&self, name: &str, user_id: UserId) -> Result<(), sqlx::Error>;
+
+    async fn get_user_locations(&self, user_id: UserId) -> Result<Vec<UserLocation>, sqlx::Error>;
+}
+
+impl UserLocations for Pool<Sqlite> {
+    #[tracing::instrument(skip(self))]
+    async fn set_user_location(
+        &self,
+        name: &str,
+        coordinates: Coordinates,
+        user_id: UserId,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+insert into user_locations (user_id, name, latitude, longitude)
+values (?, ?, ?, ?)
+on conflict (user_id, name) do update set latitude   = excluded.latitude,
+                                          longitude  = excluded.longitude,
+                                          updated_at = current_timestamp
+            "#,
+        )
+        .bind(user_id.as_i32())
+        .bind(name)
+        .bind(coordinates.latitude())
+        .bind(coordinates.longitude())
+        .execute(self)
+        .instrument(tracing::info_span!("Setting user location db query"))
+        .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn remove_user_location(&self, name: &str, user_id: UserId) -> Result<(), sqlx::Error> {
+        let result = sqlx::query(r#"delete from user_locations where user_id = ? and name = ?"#)
+            .bind(user_id.as_i32())
+            .bind(name)
+            .execute(self)
+            .instrument(tracing::info_span!("Removing user location db query"))
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_user_locations(&self, user_id: UserId) -> Result<Vec<UserLocation>, sqlx::Error> {
+        let locations: Vec<UserLocationRow> = sqlx::query_as(
+            r#"
+select id, name, latitude, longitude
+from user_locations
+where user_id = ?
+order by name
+            "#,
+        )
+        .bind(user_id.as_i32())
+        .fetch_all(self)
+        .instrument(tracing::info_span!("Getting user locations db query"))
+        .await?;
+        Ok(locations.into_iter().map(|l| l.into()).collect())
+    }
+}
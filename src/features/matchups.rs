@@ -0,0 +1,8 @@
+mod elo_ratings;
+mod models;
+mod restaurant_matchups;
+mod selection_events;
+pub use elo_ratings::*;
+pub use models::*;
+pub use restaurant_matchups::*;
+pub use selection_events::*;
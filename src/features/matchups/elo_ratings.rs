@@ -0,0 +1,148 @@
+use std::fmt::Debug;
+
+use sqlx::Pool;
+
+use sqlx::Sqlite;
+use sqlx::SqliteConnection;
+use thiserror::Error;
+use tracing::Instrument;
+
+use crate::features::HomieId;
+use crate::user::UserId;
+
+use super::elo_update;
+use super::EloRating;
+use super::EloRatingRow;
+use super::Matchup;
+use super::INITIAL_ELO;
+
+/// A homie's learned ratings, best first.
+#[tracing::instrument(skip(db))]
+pub async fn get_homies_elo_ratings(
+    homie_id: impl Into<HomieId> + Debug,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl GetHomiesEloRatings,
+) -> Result<Vec<EloRating>, GetHomiesEloRatingsError> {
+    Ok(db
+        .get_homies_elo_ratings(homie_id.into(), user_id.into())
+        .await?)
+}
+
+#[derive(Error, Debug)]
+pub enum GetHomiesEloRatingsError {
+    #[error(transparent)]
+    DbError(#[from] sqlx::Error),
+}
+
+pub trait GetHomiesEloRatings {
+    async fn get_homies_elo_ratings(
+        &self,
+        homie_id: HomieId,
+        user_id: UserId,
+    ) -> Result<Vec<EloRating>, sqlx::Error>;
+}
+
+impl GetHomiesEloRatings for Pool<Sqlite> {
+    #[tracing::instrument(skip(self))]
+    async fn get_homies_elo_ratings(
+        &self,
+        homie_id: HomieId,
+        user_id: UserId,
+    ) -> Result<Vec<EloRating>, sqlx::Error> {
+        let rows: Vec<EloRatingRow> = sqlx::query_as(
+            r#"
+select restaurant_id, rating, matches
+from homies_restaurant_elo
+where homie_id = ?
+  and user_id = ?
+order by rating desc
+            "#,
+        )
+        .bind(homie_id.as_i32())
+        .bind(user_id.as_i32())
+        .fetch_all(self)
+        .instrument(tracing::info_span!("Getting homies elo ratings db query"))
+        .await?;
+
+        Ok(rows.into_iter().map(|row| row.into()).collect())
+    }
+}
+
+async fn get_elo(
+    conn: &mut SqliteConnection,
+    homie_id: i32,
+    restaurant_id: i32,
+) -> Result<f64, sqlx::Error> {
+    let rating: Option<f64> = sqlx::query_scalar(
+        r#"select rating from homies_restaurant_elo where homie_id = ? and restaurant_id = ?"#,
+    )
+    .bind(homie_id)
+    .bind(restaurant_id)
+    .fetch_optional(conn)
+    .await?;
+    Ok(rating.unwrap_or(INITIAL_ELO))
+}
+
+async fn set_elo(
+    conn: &mut SqliteConnection,
+    homie_id: i32,
+    restaurant_id: i32,
+    rating: f64,
+    user_id: UserId,
+) -> Result<(), sqlx::Error> {
+    sqlx::query(
+        r#"
+insert into homies_restaurant_elo (homie_id, restaurant_id, user_id, rating, matches)
+select h.id, r.id, h.user_id, ?, 1
+from homies h
+         join restaurants r on r.user_id = h.user_id
+where h.id = ?
+  and r.id = ?
+  and h.user_id = ?
+on conflict (homie_id, restaurant_id) do update set rating     = excluded.rating,
+                                                    matches    = matches + 1,
+                                                    updated_at = current_timestamp
+        "#,
+    )
+    .bind(rating)
+    .bind(homie_id)
+    .bind(restaurant_id)
+    .bind(user_id.as_i32())
+    .execute(conn)
+    .await?;
+    Ok(())
+}
+
+/// Plays every matchup for every homie, moving their ratings by `k` at most.
+pub(super) async fn apply_matchups(
+    conn: &mut SqliteConnection,
+    homie_ids: &[HomieId],
+    matchups: &[Matchup],
+    k: f64,
+    user_id: UserId,
+) -> Result<(), sqlx::Error> {
+    for homie_id in homie_ids {
+        for matchup in matchups {
+            let winner = get_elo(conn, homie_id.as_i32(), *matchup.winner.as_i32()).await?;
+            let loser = get_elo(conn, homie_id.as_i32(), *matchup.loser.as_i32()).await?;
+            let (winner, loser) = elo_update(winner, loser, k);
+            set_elo(
+                conn,
+                homie_id.as_i32(),
+                *matchup.winner.as_i32(),
+                winner,
+                user_id,
+            )
+            .await?;
+            set_elo(
+                conn,
+                homie_id.as_i32(),
+                *matchup.loser.as_i32(),
+                loser,
+                user_id,
+            )
+            .await?;
+        }
+    }
+    Ok(())
+}
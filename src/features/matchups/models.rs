@@ -0,0 +1,103 @@
+use sqlx::FromRow;
+
+use crate::features::HomieId;
+use crate::features::RestaurantId;
+
+/// The outcome of a head-to-head "this or that" prompt between two restaurants.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct Matchup {
+    pub winner: RestaurantId,
+    pub loser: RestaurantId,
+}
+
+/// A matchup as it was recorded, with the homies who were home at the time.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct RecordedMatchup {
+    pub matchup: Matchup,
+    pub homie_ids: Vec<HomieId>,
+}
+
+#[derive(Debug, FromRow)]
+pub struct RecordedMatchupRow {
+    winner_id: i32,
+    loser_id: i32,
+    homie_ids: Option<String>,
+}
+
+impl From<RecordedMatchupRow> for RecordedMatchup {
+    fn from(row: RecordedMatchupRow) -> Self {
+        Self {
+            matchup: Matchup {
+                winner: row.winner_id.into(),
+                loser: row.loser_id.into(),
+            },
+            homie_ids: row
+                .homie_ids
+                .unwrap_or_default()
+                .split(',')
+                .filter_map(|id| id.parse::<i32>().ok())
+                .map(|id| id.into())
+                .collect(),
+        }
+    }
+}
+
+/// The rating every homie starts out with for every restaurant.
+pub const INITIAL_ELO: f64 = 1500.0;
+
+/// How far a single matchup moves the ratings.
+pub const ELO_K: f64 = 32.0;
+
+/// The ratings of the winner and the loser of a matchup after it was played.
+pub fn elo_update(winner: f64, loser: f64, k: f64) -> (f64, f64) {
+    let expected = 1.0 / (1.0 + 10f64.powf((loser - winner) / 400.0));
+    let change = k * (1.0 - expected);
+    (winner + change, loser - change)
+}
+
+/// A homie's learned preference for a restaurant.
+#[derive(Debug, PartialEq, Clone)]
+pub struct EloRating {
+    pub restaurant_id: RestaurantId,
+    pub rating: f64,
+    pub matches: i64,
+}
+
+#[derive(Debug, FromRow)]
+pub struct EloRatingRow {
+    restaurant_id: i32,
+    rating: f64,
+    matches: i64,
+}
+
+impl From<EloRatingRow> for EloRating {
+    fn from(row: EloRatingRow) -> Self {
+        Self {
+            restaurant_id: row.restaurant_id.into(),
+            rating: row.rating,
+            matches: row.matches,
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::elo_update;
+    use super::ELO_K;
+    use super::INITIAL_ELO;
+
+    #[test]
+    fn even_matchups_move_ratings_by_half_of_k() {
+        assert_eq!(
+            (INITIAL_ELO + ELO_K / 2.0, INITIAL_ELO - ELO_K / 2.0),
+            elo_update(INITIAL_ELO, INITIAL_ELO, ELO_K)
+        );
+    }
+
+    #[test]
+    fn expected_wins_move_ratings_less() {
+        let (winner, _) = elo_update(1700.0, 1300.0, ELO_K);
+
+        assert!(winner - 1700.0 < ELO_K / 10.0);
+    }
+}
@@ -0,0 +1,137 @@
+use std::fmt::Debug;
+
+use sqlx::Pool;
+
+use sqlx::Sqlite;
+use thiserror::Error;
+use tracing::Instrument;
+
+use crate::features::HomieId;
+use crate::user::UserId;
+
+use super::apply_matchups;
+use super::Matchup;
+use super::RecordedMatchup;
+use super::RecordedMatchupRow;
+use super::ELO_K;
+
+/// Records the outcomes of a bracket on behalf of the home homies, updating their
+/// learned ratings of the restaurants.
+#[tracing::instrument(skip(db))]
+pub async fn record_restaurant_matchups<T, Y>(
+    homie_ids: T,
+    matchups: &[Matchup],
+    user_id: impl Into<UserId> + Debug,
+    db: &impl RestaurantMatchups,
+) -> Result<(), RestaurantMatchupsError>
+where
+    T: IntoIterator<Item = Y> + Debug,
+    Y: Into<HomieId> + Debug,
+{
+    let homie_ids: Vec<HomieId> = homie_ids.into_iter().map(|id| id.into()).collect();
+
+    db.record_restaurant_matchups(&homie_ids, matchups, user_id.into())
+        .await?;
+
+    Ok(())
+}
+
+/// Every recorded matchup, oldest first.
+#[tracing::instrument(skip(db))]
+pub async fn get_restaurant_matchups(
+    user_id: impl Into<UserId> + Debug,
+    db: &impl RestaurantMatchups,
+) -> Result<Vec<RecordedMatchup>, RestaurantMatchupsError> {
+    Ok(db.get_restaurant_matchups(user_id.into()).await?)
+}
+
+#[derive(Error, Debug)]
+pub enum RestaurantMatchupsError {
+    #[error(transparent)]
+    DbError(#[from] sqlx::Error),
+}
+
+pub trait RestaurantMatchups {
+    async fn record_restaurant_matchups(
+        &self,
+        homie_ids: &[HomieId],
+        matchups: &[Matchup],
+        user_id: UserId,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn get_restaurant_matchups(
+        &self,
+        user_id: UserId,
+    ) -> Result<Vec<RecordedMatchup>, sqlx::Error>;
+}
+
+impl RestaurantMatchups for Pool<Sqlite> {
+    #[tracing::instrument(skip(self))]
+    async fn record_restaurant_matchups(
+        &self,
+        homie_ids: &[HomieId],
+        matchups: &[Matchup],
+        user_id: UserId,
+    ) -> Result<(), sqlx::Error> {
+        let homie_ids_json =
+            serde_json::to_string(&homie_ids.iter().map(|h| h.as_i32()).collect::<Vec<i32>>())
+                .expect("unable to serialize list of home homie ids as json");
+
+        let mut tx = self.begin().await?;
+        for matchup in matchups {
+            let matchup_id: i64 = sqlx::query(
+                r#"insert into restaurant_matchups (user_id, winner_id, loser_id) values (?, ?, ?)"#,
+            )
+            .bind(user_id.as_i32())
+            .bind(matchup.winner.as_i32())
+            .bind(matchup.loser.as_i32())
+            .execute(&mut *tx)
+            .await?
+            .last_insert_rowid();
+            sqlx::query(
+                r#"
+insert into restaurant_matchup_homies (matchup_id, homie_id, user_id)
+select ?, h.id, h.user_id
+from json_each(?) hh
+         join homies h on h.id = hh.value
+where h.user_id = ?
+                "#,
+            )
+            .bind(matchup_id)
+            .bind(&homie_ids_json)
+            .bind(user_id.as_i32())
+            .execute(&mut *tx)
+            .await?;
+        }
+        apply_matchups(&mut tx, homie_ids, matchups, ELO_K, user_id).await?;
+
+        tx.commit()
+            .instrument(tracing::info_span!(
+                "Recording restaurant matchups db query"
+            ))
+            .await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_restaurant_matchups(
+        &self,
+        user_id: UserId,
+    ) -> Result<Vec<RecordedMatchup>, sqlx::Error> {
+        let rows: Vec<RecordedMatchupRow> = sqlx::query_as(
+            r#"
+select m.winner_id, m.loser_id, group_concat(mh.homie_id) as homie_ids
+from restaurant_matchups m
+         left join restaurant_matchup_homies mh on mh.matchup_id = m.id
+where m.user_id = ?
+group by m.id
+order by m.id
+            "#,
+        )
+        .bind(user_id.as_i32())
+        .fetch_all(self)
+        .instrument(tracing::info_span!("Getting restaurant matchups db query"))
+        .await?;
+
+        Ok(rows.into_iter().map(|row| row.into()).collect())
+    }
+}
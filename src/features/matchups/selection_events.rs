@@ -0,0 +1,119 @@
+use std::fmt::Debug;
+
+use sqlx::Pool;
+
+use sqlx::Sqlite;
+use thiserror::Error;
+use tracing::Instrument;
+
+use crate::features::HomieId;
+use crate::features::RestaurantId;
+use crate::user::UserId;
+
+use super::apply_matchups;
+use super::Matchup;
+use super::ELO_K;
+
+/// Records that the home homies chose `chosen` out of the `shown` restaurants.
+///
+/// The choice counts as the chosen restaurant winning a matchup against every other
+/// restaurant shown, with the rating change split between them so that long lists
+/// don't inflate the ratings.
+#[tracing::instrument(skip(db))]
+pub async fn record_selection_event<T, Y>(
+    homie_ids: T,
+    shown: &[RestaurantId],
+    chosen: RestaurantId,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl RecordSelectionEvent,
+) -> Result<(), RecordSelectionEventError>
+where
+    T: IntoIterator<Item = Y> + Debug,
+    Y: Into<HomieId> + Debug,
+{
+    let homie_ids: Vec<HomieId> = homie_ids.into_iter().map(|id| id.into()).collect();
+
+    db.record_selection_event(&homie_ids, shown, chosen, user_id.into())
+        .await?;
+
+    Ok(())
+}
+
+#[derive(Error, Debug)]
+pub enum RecordSelectionEventError {
+    #[error(transparent)]
+    DbError(#[from] sqlx::Error),
+}
+
+pub trait RecordSelectionEvent {
+    async fn record_selection_event(
+        &self,
+        homie_ids: &[HomieId],
+        shown: &[RestaurantId],
+        chosen: RestaurantId,
+        user_id: UserId,
+    ) -> Result<(), sqlx::Error>;
+}
+
+impl RecordSelectionEvent for Pool<Sqlite> {
+    #[tracing::instrument(skip(self))]
+    async fn record_selection_event(
+        &self,
+        homie_ids: &[HomieId],
+        shown: &[RestaurantId],
+        chosen: RestaurantId,
+        user_id: UserId,
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let event_id =
+            sqlx::query(r#"insert into selection_events (user_id, chosen_id) values (?, ?)"#)
+                .bind(user_id.as_i32())
+                .bind(chosen.as_i32())
+                .execute(&mut *tx)
+                .await?
+                .last_insert_rowid();
+        for (position, restaurant_id) in shown.iter().enumerate() {
+            sqlx::query(
+                r#"insert or ignore into selection_event_restaurants (event_id, restaurant_id, user_id, position) values (?, ?, ?, ?)"#,
+            )
+            .bind(event_id)
+            .bind(restaurant_id.as_i32())
+            .bind(user_id.as_i32())
+            .bind(position as i64)
+            .execute(&mut *tx)
+            .await?;
+        }
+        sqlx::query(
+            r#"
+insert into selection_event_homies (event_id, homie_id, user_id)
+select ?, h.id, h.user_id
+from json_each(?) hh
+         join homies h on h.id = hh.value
+where h.user_id = ?
+            "#,
+        )
+        .bind(event_id)
+        .bind(
+            serde_json::to_string(&homie_ids.iter().map(|h| h.as_i32()).collect::<Vec<i32>>())
+                .expect("unable to serialize list of home homie ids as json"),
+        )
+        .bind(user_id.as_i32())
+        .execute(&mut *tx)
+        .await?;
+
+        let matchups = shown
+            .iter()
+            .filter(|&&loser| loser != chosen)
+            .map(|&loser| Matchup {
+                winner: chosen,
+                loser,
+            })
+            .collect::<Vec<Matchup>>();
+        let k = ELO_K / matchups.len().max(1) as f64;
+        apply_matchups(&mut tx, homie_ids, &matchups, k, user_id).await?;
+
+        tx.commit()
+            .instrument(tracing::info_span!("Recording selection event db query"))
+            .await
+    }
+}
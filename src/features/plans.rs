@@ -0,0 +1,4 @@
+mod lunch_plans;
+mod models;
+pub use lunch_plans::*;
+pub use models::*;
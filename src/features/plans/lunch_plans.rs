@@ -0,0 +1,250 @@
+use std::collections::HashSet;
+use std::fmt::Debug;
+
+use anyhow::Result;
+use chrono::NaiveDate;
+use chrono::NaiveTime;
+use sqlx::Pool;
+
+use sqlx::Sqlite;
+use thiserror::Error;
+use tracing::Instrument;
+
+use crate::features::get_all_homies;
+use crate::features::get_available_homies;
+use crate::features::get_candidate_restaurants;
+use crate::features::get_homies_favorite_restaurants;
+use crate::features::CandidateOptions;
+use crate::features::GetAllHomies;
+use crate::features::GetCandidates;
+use crate::features::GetHomiesFavoriteRestaurants;
+use crate::features::HomieAvailability;
+use crate::features::HomieId;
+use crate::features::UserLocation;
+use crate::user::UserId;
+
+use super::plan_lunches;
+use super::plan_weekdays;
+use super::LunchPlan;
+use super::PlannedLunch;
+use super::PlannedLunchRow;
+
+/// Plans lunch for the home homies on the weekdays of the week `week` falls in,
+/// replacing any earlier plan for those days.
+///
+/// Days before `today` are skipped. Each day's candidates are picked the same way as
+/// for `pick-lunch`, at noon, for the homies who aren't away that day, so recent
+/// lunches and opening hours are respected. Only the restaurants of `location` are
+/// planned when given.
+#[tracing::instrument(skip(db))]
+pub async fn generate_lunch_plan<T, Y>(
+    homie_ids: T,
+    week: NaiveDate,
+    today: NaiveDate,
+    location: Option<&UserLocation>,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl LunchPlans,
+) -> Result<LunchPlan>
+where
+    T: IntoIterator<Item = Y> + Debug,
+    Y: Into<HomieId> + Debug,
+{
+    let homie_ids: Vec<HomieId> = homie_ids.into_iter().map(|id| id.into()).collect();
+    let user_id = user_id.into();
+    let lunch_time = NaiveTime::from_hms_opt(12, 0, 0).expect("noon is a valid time");
+
+    let days = plan_weekdays(week)
+        .into_iter()
+        .filter(|&date| date >= today)
+        .collect::<Vec<_>>();
+    let mut candidates = Vec::new();
+    let mut names = Vec::new();
+    for date in &days {
+        let available = get_available_homies(homie_ids.clone(), *date, user_id, db).await?;
+        let options = CandidateOptions {
+            at: Some(date.and_time(lunch_time)),
+            party_size: Some(available.len() as u32),
+            location_id: location.map(|l| l.id),
+            origin: location.map(|l| l.coordinates),
+            ..Default::default()
+        };
+        let restaurants = match available.is_empty() {
+            true => Vec::new(),
+            false => get_candidate_restaurants(available, &options, user_id, db).await?,
+        };
+        candidates.push(restaurants.iter().map(|r| r.id).collect::<Vec<_>>());
+        names.extend(
+            restaurants
+                .into_iter()
+                .map(|r| (r.id, r.name.as_str().to_string())),
+        );
+    }
+
+    let homies = get_all_homies(user_id, db)
+        .await?
+        .into_iter()
+        .filter(|h| homie_ids.contains(&h.id))
+        .collect::<Vec<_>>();
+    let mut favorites = Vec::new();
+    for homie in &homies {
+        favorites.push(
+            get_homies_favorite_restaurants(user_id, homie.id, db)
+                .await?
+                .into_iter()
+                .map(|f| f.restaurant.id)
+                .collect::<HashSet<_>>(),
+        );
+    }
+
+    let lunches = days
+        .iter()
+        .zip(plan_lunches(&candidates, &favorites))
+        .filter_map(|(&date, restaurant_id)| {
+            let restaurant_id = restaurant_id?;
+            let (_, restaurant_name) = names.iter().find(|(id, _)| *id == restaurant_id)?;
+            Some(PlannedLunch {
+                date,
+                restaurant_id,
+                restaurant_name: restaurant_name.clone(),
+            })
+        })
+        .collect::<Vec<_>>();
+    let without_favorite = homies
+        .iter()
+        .zip(&favorites)
+        .filter(|(_, f)| !lunches.iter().any(|l| f.contains(&l.restaurant_id)))
+        .map(|(h, _)| h.name.as_str().to_string())
+        .collect();
+
+    db.save_lunch_plan(&days, &lunches, user_id).await?;
+
+    Ok(LunchPlan {
+        lunches,
+        without_favorite,
+    })
+}
+
+/// The stored plan for the weekdays of the week `week` falls in.
+#[tracing::instrument(skip(db))]
+pub async fn get_lunch_plan(
+    week: NaiveDate,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl LunchPlans,
+) -> Result<LunchPlan, LunchPlanError> {
+    let days = plan_weekdays(week);
+    let lunches = db
+        .get_planned_lunches(days[0], days[days.len() - 1], user_id.into())
+        .await?;
+
+    Ok(LunchPlan {
+        lunches,
+        ..Default::default()
+    })
+}
+
+/// The lunch planned for `date`, if any.
+#[tracing::instrument(skip(db))]
+pub async fn get_planned_lunch(
+    date: NaiveDate,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl LunchPlans,
+) -> Result<Option<PlannedLunch>, LunchPlanError> {
+    Ok(db
+        .get_planned_lunches(date, date, user_id.into())
+        .await?
+        .pop())
+}
+
+#[derive(Error, Debug)]
+pub enum LunchPlanError {
+    #[error(transparent)]
+    DbError(#[from] sqlx::Error),
+}
+
+pub trait LunchPlans:
+    GetCandidates + GetAllHomies + GetHomiesFavoriteRestaurants + HomieAvailability
+{
+    /// Replaces whatever was planned on `days` with `lunches`.
+    async fn save_lunch_plan(
+        &self,
+        days: &[NaiveDate],
+        lunches: &[PlannedLunch],
+        user_id: UserId,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn get_planned_lunches(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        user_id: UserId,
+    ) -> Result<Vec<PlannedLunch>, sqlx::Error>;
+}
+
+impl LunchPlans for Pool<Sqlite> {
+    #[tracing::instrument(skip(self))]
+    async fn save_lunch_plan(
+        &self,
+        days: &[NaiveDate],
+        lunches: &[PlannedLunch],
+        user_id: UserId,
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.begin().await?;
+        sqlx::query(
+            r#"
+delete
+from planned_lunches
+where user_id = ?
+  and date in (select value from json_each(?))
+            "#,
+        )
+        .bind(user_id.as_i32())
+        .bind(
+            serde_json::to_string(&days.iter().map(|d| d.to_string()).collect::<Vec<_>>())
+                .expect("unable to serialize list of dates as json"),
+        )
+        .execute(&mut *tx)
+        .instrument(tracing::info_span!("Clearing lunch plan db query"))
+        .await?;
+        for lunch in lunches {
+            sqlx::query(
+                r#"
+insert into planned_lunches (date, restaurant_id, user_id)
+values (?, ?, ?)
+                "#,
+            )
+            .bind(lunch.date)
+            .bind(lunch.restaurant_id.as_i32())
+            .bind(user_id.as_i32())
+            .execute(&mut *tx)
+            .instrument(tracing::info_span!("Planning lunch db query"))
+            .await?;
+        }
+        tx.commit().await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_planned_lunches(
+        &self,
+        from: NaiveDate,
+        to: NaiveDate,
+        user_id: UserId,
+    ) -> Result<Vec<PlannedLunch>, sqlx::Error> {
+        let lunches: Vec<PlannedLunchRow> = sqlx::query_as(
+            r#"
+select p.date, r.id as restaurant_id, r.name as restaurant_name
+from planned_lunches p
+         join restaurants r on r.id = p.restaurant_id and r.user_id = p.user_id
+where p.user_id = ?
+  and p.date between ? and ?
+order by p.date
+            "#,
+        )
+        .bind(user_id.as_i32())
+        .bind(from)
+        .bind(to)
+        .fetch_all(self)
+        .instrument(tracing::info_span!("Getting planned lunches db query"))
+        .await?;
+        Ok(lunches.into_iter().map(|l| l.into()).collect())
+    }
+}
@@ -0,0 +1,315 @@
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+use chrono::Datelike;
+use chrono::Days;
+use chrono::NaiveDate;
+use chrono::Weekday;
+use sqlx::FromRow;
+use thiserror::Error;
+
+use crate::features::RestaurantId;
+
+#[derive(Error, Debug)]
+pub enum PlanFormatParseError {
+    #[error("Unknown plan format: {:?}, expected text or json", format)]
+    UnknownFormat { format: String },
+}
+
+/// How a lunch plan is exported.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum PlanFormat {
+    #[default]
+    Text,
+    Json,
+}
+
+impl PlanFormat {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            PlanFormat::Text => "text",
+            PlanFormat::Json => "json",
+        }
+    }
+}
+
+impl Display for PlanFormat {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for PlanFormat {
+    type Err = PlanFormatParseError;
+
+    fn from_str(format: &str) -> Result<Self, Self::Err> {
+        match format.trim().to_lowercase().as_str() {
+            "text" | "txt" => Ok(PlanFormat::Text),
+            "json" => Ok(PlanFormat::Json),
+            _ => Err(PlanFormatParseError::UnknownFormat {
+                format: format.to_string(),
+            }),
+        }
+    }
+}
+
+/// Monday to Friday of the week `date` falls in.
+pub fn plan_weekdays(date: NaiveDate) -> Vec<NaiveDate> {
+    let monday = date.week(Weekday::Mon).first_day();
+    (0..5).map(|day| monday + Days::new(day)).collect()
+}
+
+/// A tentative lunch, offered first when picking lunch on the day.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct PlannedLunch {
+    pub date: NaiveDate,
+    pub restaurant_id: RestaurantId,
+    pub restaurant_name: String,
+}
+
+#[derive(Debug, FromRow)]
+pub struct PlannedLunchRow {
+    date: NaiveDate,
+    restaurant_id: i32,
+    restaurant_name: String,
+}
+
+impl From<PlannedLunchRow> for PlannedLunch {
+    fn from(row: PlannedLunchRow) -> Self {
+        Self {
+            date: row.date,
+            restaurant_id: row.restaurant_id.into(),
+            restaurant_name: row.restaurant_name,
+        }
+    }
+}
+
+/// The planned lunches of a week; days without candidates are left out.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct LunchPlan {
+    pub lunches: Vec<PlannedLunch>,
+    /// Home homies without a favorite in the plan, only known right after generating it
+    pub without_favorite: Vec<String>,
+}
+
+impl LunchPlan {
+    pub fn to_json(&self) -> serde_json::Value {
+        serde_json::Value::Array(
+            self.lunches
+                .iter()
+                .map(|l| {
+                    serde_json::json!({
+                        "date": l.date.to_string(),
+                        "weekday": l.date.weekday().to_string(),
+                        "restaurant_id": l.restaurant_id.as_i32(),
+                        "restaurant": l.restaurant_name,
+                    })
+                })
+                .collect(),
+        )
+    }
+
+    pub fn export(&self, format: PlanFormat) -> String {
+        match format {
+            PlanFormat::Text => self.to_string(),
+            PlanFormat::Json => serde_json::to_string_pretty(&self.to_json())
+                .expect("unable to serialize lunch plan as json"),
+        }
+    }
+}
+
+impl Display for LunchPlan {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for lunch in &self.lunches {
+            writeln!(
+                f,
+                "{} {} {}",
+                lunch.date.weekday(),
+                lunch.date,
+                lunch.restaurant_name
+            )?;
+        }
+        if !self.without_favorite.is_empty() {
+            writeln!(f, "No favorite for {}", self.without_favorite.join(", "))?;
+        }
+        Ok(())
+    }
+}
+
+/// Picks a restaurant for each day out of that day's candidates, best first.
+///
+/// A restaurant is planned at most once. The days are searched for a plan that gives
+/// every homie one of their favorites, as far as any of the candidates is one, with the
+/// remaining days taking the best candidate left. When there is no such plan, each day
+/// the candidate that is a favorite of the most homies still without one is taken,
+/// otherwise the best one left. `favorites` holds the favorite restaurants of each home
+/// homie.
+pub fn plan_lunches(
+    candidates: &[Vec<RestaurantId>],
+    favorites: &[HashSet<RestaurantId>],
+) -> Vec<Option<RestaurantId>> {
+    let wanting = favorites
+        .iter()
+        .map(|f| candidates.iter().flatten().any(|r| f.contains(r)))
+        .collect::<Vec<_>>();
+    let mut planned = vec![None; candidates.len()];
+    if !cover_favorites(candidates, favorites, &wanting, 0, &mut planned) {
+        return plan_greedily(candidates, favorites);
+    }
+    for (day, restaurants) in candidates.iter().enumerate() {
+        if planned[day].is_none() {
+            planned[day] = restaurants
+                .iter()
+                .find(|r| !planned.contains(&Some(**r)))
+                .copied();
+        }
+    }
+    planned
+}
+
+/// Whether the days from `day` on can give a favorite to every homie still `wanting`
+/// one, planning those favorites and leaving the other days empty.
+fn cover_favorites(
+    candidates: &[Vec<RestaurantId>],
+    favorites: &[HashSet<RestaurantId>],
+    wanting: &[bool],
+    day: usize,
+    planned: &mut [Option<RestaurantId>],
+) -> bool {
+    if !wanting.contains(&true) {
+        return true;
+    }
+    let reachable = |f: &HashSet<RestaurantId>| {
+        candidates[day..]
+            .iter()
+            .flatten()
+            .any(|r| f.contains(r) && !planned.contains(&Some(*r)))
+    };
+    if favorites
+        .iter()
+        .zip(wanting)
+        .any(|(f, &w)| w && !reachable(f))
+    {
+        return false;
+    }
+
+    for restaurant in &candidates[day] {
+        if planned.contains(&Some(*restaurant)) {
+            continue;
+        }
+        let still_wanting = favorites
+            .iter()
+            .zip(wanting)
+            .map(|(f, &w)| w && !f.contains(restaurant))
+            .collect::<Vec<_>>();
+        if still_wanting == wanting {
+            continue;
+        }
+        planned[day] = Some(*restaurant);
+        if cover_favorites(candidates, favorites, &still_wanting, day + 1, planned) {
+            return true;
+        }
+        planned[day] = None;
+    }
+    // the day goes to whatever is best once everyone has a favorite
+    cover_favorites(candidates, favorites, wanting, day + 1, planned)
+}
+
+fn plan_greedily(
+    candidates: &[Vec<RestaurantId>],
+    favorites: &[HashSet<RestaurantId>],
+) -> Vec<Option<RestaurantId>> {
+    let mut planned: Vec<Option<RestaurantId>> = Vec::new();
+    let mut satisfied = vec![false; favorites.len()];
+    for day in candidates {
+        let unplanned = day
+            .iter()
+            .filter(|r| !planned.contains(&Some(**r)))
+            .collect::<Vec<_>>();
+        let newly_satisfied = |restaurant: &RestaurantId| {
+            favorites
+                .iter()
+                .zip(&satisfied)
+                .filter(|(f, &s)| !s && f.contains(restaurant))
+                .count()
+        };
+        // max_by_key keeps the last of equals, so the best candidates go last
+        let chosen = unplanned
+            .iter()
+            .rev()
+            .max_by_key(|r| newly_satisfied(r))
+            .map(|&&r| r);
+        if let Some(chosen) = chosen {
+            for (f, s) in favorites.iter().zip(satisfied.iter_mut()) {
+                *s |= f.contains(&chosen);
+            }
+        }
+        planned.push(chosen);
+    }
+    planned
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashSet;
+
+    use chrono::NaiveDate;
+
+    use super::plan_lunches;
+    use super::plan_weekdays;
+    use crate::features::RestaurantId;
+
+    fn ids(ids: &[i32]) -> Vec<RestaurantId> {
+        ids.iter().map(|&id| id.into()).collect()
+    }
+
+    #[test]
+    fn plans_monday_to_friday() {
+        // 2026-10-22 is a thursday
+        let days = plan_weekdays(NaiveDate::from_ymd_opt(2026, 10, 22).unwrap());
+
+        assert_eq!(5, days.len());
+        assert_eq!(NaiveDate::from_ymd_opt(2026, 10, 19).unwrap(), days[0]);
+        assert_eq!(NaiveDate::from_ymd_opt(2026, 10, 23).unwrap(), days[4]);
+    }
+
+    #[test]
+    fn never_repeats_and_gives_everyone_a_favorite() {
+        let candidates = vec![ids(&[1, 2, 3]), ids(&[1, 2, 3]), ids(&[1, 2, 4])];
+        let favorites = vec![
+            ids(&[1, 2]).into_iter().collect::<HashSet<_>>(),
+            ids(&[4]).into_iter().collect::<HashSet<_>>(),
+        ];
+
+        let planned = plan_lunches(&candidates, &favorites);
+
+        assert_eq!(
+            vec![Some(1.into()), Some(2.into()), Some(4.into())],
+            planned
+        );
+    }
+
+    #[test]
+    fn looks_ahead_when_the_first_favorite_is_needed_later() {
+        let candidates = vec![ids(&[1, 2]), ids(&[1])];
+        let favorites = vec![
+            ids(&[1]).into_iter().collect::<HashSet<_>>(),
+            ids(&[2]).into_iter().collect::<HashSet<_>>(),
+        ];
+
+        let planned = plan_lunches(&candidates, &favorites);
+
+        assert_eq!(vec![Some(2.into()), Some(1.into())], planned);
+    }
+
+    #[test]
+    fn days_without_candidates_stay_empty() {
+        let candidates = vec![ids(&[1]), ids(&[1]), ids(&[])];
+
+        let planned = plan_lunches(&candidates, &[]);
+
+        assert_eq!(vec![Some(1.into()), None, None], planned);
+    }
+}
@@ -1,2 +1,10 @@
 mod add_recent_restaurant;
+mod get_recent_visits;
+mod models;
+mod pick_events;
+mod rate_recent_restaurant;
 pub use add_recent_restaurant::*;
+pub use get_recent_visits::*;
+pub use models::*;
+pub use pick_events::*;
+pub use rate_recent_restaurant::*;
@@ -1,5 +1,7 @@
 use std::fmt::Debug;
 
+use chrono::Local;
+use chrono::NaiveDate;
 use sqlx::Pool;
 
 use sqlx::Sqlite;
@@ -16,10 +18,14 @@ use crate::features::RestaurantName;
 use crate::features::RestaurantNameValidationError;
 use crate::user::UserId;
 
+use super::MealSlot;
+use super::PickEvents;
+
 #[tracing::instrument(skip(db))]
 pub async fn add_recent_restaurant_for_homie(
     homie_name: impl TryInto<HomiesName, Error = HomieNameValidationError> + Debug,
     restaurant_name: impl TryInto<RestaurantName, Error = RestaurantNameValidationError> + Debug,
+    meal: MealSlot,
     user_id: impl Into<UserId> + Debug,
     db: &impl AddRecentRestaurantToHomie,
 ) -> Result<(), AddHomiesRecentRestaurantError> {
@@ -27,6 +33,8 @@ pub async fn add_recent_restaurant_for_homie(
         user_id.into(),
         homie_name.try_into()?,
         restaurant_name.try_into()?,
+        meal,
+        Local::now().date_naive(),
     );
 
     db.add_recent_restaurant_for_homie(&add_recent_to_homie_params)
@@ -58,10 +66,18 @@ pub async fn add_recent_restaurant_for_homie(
     Ok(())
 }
 
+/// Records the pick of a restaurant for the homies' `meal` on `date`, usually today.
+///
+/// Picking the same restaurant again changes nothing. When some of the homies already
+/// went elsewhere for the meal that day, their visits are replaced with `replace_today`,
+/// otherwise the pick is refused.
 #[tracing::instrument(skip(db))]
-pub async fn add_recent_restaurant_for_homies<'a, T, Y>(
+pub async fn add_recent_restaurant_for_homies<T, Y>(
     homie_ids: T,
     restaurant_id: impl Into<RestaurantId> + Debug,
+    meal: MealSlot,
+    date: NaiveDate,
+    replace_today: bool,
     user_id: impl Into<UserId> + Debug,
     db: &impl AddRecentRestaurantToHomie,
 ) -> Result<(), AddHomiesRecentRestaurantError>
@@ -75,8 +91,22 @@ where
     let h: Vec<_> = homie_ids.iter().collect();
     let user_id = user_id.into();
 
+    if !replace_today {
+        if let Some(other) = db
+            .get_todays_picks(&homie_ids, meal, date, user_id)
+            .await?
+            .into_iter()
+            .find(|p| p.restaurant_id != restaurant_id)
+        {
+            return Err(AddHomiesRecentRestaurantError::AlreadyPickedToday {
+                restaurant_name: other.restaurant_name,
+                meal,
+            });
+        }
+    }
+
     let add_recent_to_homies_params =
-        AddRecentRestaurantToHomiesParams::new(&user_id, h.as_slice(), &restaurant_id);
+        AddRecentRestaurantToHomiesParams::new(&user_id, h.as_slice(), &restaurant_id, meal, date);
 
     db.add_recent_restaurant_for_homies(&add_recent_to_homies_params)
         .await?;
@@ -85,17 +115,20 @@ where
         Level::INFO,
         name = "Recent restaurant added for home homies",
         homie_ids = ?&homie_ids,
-        restaurant_id = &restaurant_id.as_i32()
+        restaurant_id = &restaurant_id.as_i32(),
+        meal = meal.as_str()
     );
 
     Ok(())
 }
 
 #[derive(Debug)]
-struct AddRecentRestaurantToHomiesParams<'a> {
+pub struct AddRecentRestaurantToHomiesParams<'a> {
     user_id: &'a UserId,
     homies_ids: &'a [&'a HomieId],
     restaurant_id: &'a RestaurantId,
+    meal: MealSlot,
+    date: NaiveDate,
 }
 
 impl<'a> AddRecentRestaurantToHomiesParams<'a> {
@@ -103,28 +136,42 @@ impl<'a> AddRecentRestaurantToHomiesParams<'a> {
         user_id: &'a UserId,
         homies_ids: &'a [&'a HomieId],
         restaurant_id: &'a RestaurantId,
+        meal: MealSlot,
+        date: NaiveDate,
     ) -> Self {
         Self {
             user_id,
             homies_ids,
             restaurant_id,
+            meal,
+            date,
         }
     }
 }
 
 #[derive(Debug)]
-struct AddRecentRestaurantToHomieParams {
+pub struct AddRecentRestaurantToHomieParams {
     user_id: UserId,
     name: HomiesName,
     restaurant_name: RestaurantName,
+    meal: MealSlot,
+    date: NaiveDate,
 }
 
 impl AddRecentRestaurantToHomieParams {
-    fn new(user_id: UserId, name: HomiesName, restaurant_name: RestaurantName) -> Self {
+    fn new(
+        user_id: UserId,
+        name: HomiesName,
+        restaurant_name: RestaurantName,
+        meal: MealSlot,
+        date: NaiveDate,
+    ) -> Self {
         Self {
             user_id,
             name,
             restaurant_name,
+            meal,
+            date,
         }
     }
 }
@@ -149,6 +196,12 @@ pub enum AddHomiesRecentRestaurantError {
         restaurant_name: String,
     },
 
+    #[error("{} was already picked for today's {}", restaurant_name, meal)]
+    AlreadyPickedToday {
+        restaurant_name: String,
+        meal: MealSlot,
+    },
+
     #[error("Unknown db error")]
     UnknownDbError(#[from] sqlx::Error),
 
@@ -156,8 +209,8 @@ pub enum AddHomiesRecentRestaurantError {
     Unknown,
 }
 
-trait AddRecentRestaurantToHomie {
-    async fn add_recent_restaurant_for_homie<'a>(
+pub trait AddRecentRestaurantToHomie: PickEvents {
+    async fn add_recent_restaurant_for_homie(
         &self,
         params: &AddRecentRestaurantToHomieParams,
     ) -> Result<(), sqlx::Error>;
@@ -170,37 +223,41 @@ trait AddRecentRestaurantToHomie {
 
 impl AddRecentRestaurantToHomie for Pool<Sqlite> {
     #[tracing::instrument(skip(self))]
-    async fn add_recent_restaurant_for_homie<'a>(
+    async fn add_recent_restaurant_for_homie(
         &self,
         params: &AddRecentRestaurantToHomieParams,
     ) -> Result<(), sqlx::Error> {
         let user_id = params.user_id.as_i32();
-        let restaurant_name = params.restaurant_name.as_str();
-        let homie_name = params.name.as_str();
-        _ = sqlx::query!(
+        let result = sqlx::query(
             r#"
-                insert into recent_restaurants (homie_id, user_id, restaurant_id)
-                select 
-                    h.id, 
-                    ?,
-                    r.id
-                from homies h
-                join restaurants r on r.name = ? and r.user_id =? 
-                where h.name = ? and h.user_id =? 
-                limit 1
-                returning *;
+insert into recent_restaurants (homie_id, user_id, restaurant_id, date, meal)
+select h.id,
+       ?,
+       r.id,
+       ?,
+       ?
+from homies h
+         join restaurants r on r.name = ? and r.user_id = ?
+where h.name = ?
+  and h.user_id = ?
+limit 1
             "#,
-            user_id,
-            restaurant_name,
-            user_id,
-            homie_name,
-            user_id,
         )
-        .fetch_one(self)
+        .bind(user_id)
+        .bind(params.date)
+        .bind(params.meal.as_str())
+        .bind(params.restaurant_name.as_str())
+        .bind(user_id)
+        .bind(params.name.as_str())
+        .bind(user_id)
+        .execute(self)
         .instrument(tracing::info_span!(
             "Adding recent restaurant to homie db query"
         ))
         .await?;
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
         Ok(())
     }
 
@@ -216,28 +273,110 @@ impl AddRecentRestaurantToHomie for Pool<Sqlite> {
         let homie_ids = serde_json::to_string(&homie_ids)
             .expect("unable to serialize list of home homie ids as json");
 
-        _ = sqlx::query!(
+        let mut tx = self.begin().await?;
+        let new_visits: i64 = sqlx::query_scalar(
+            r#"
+select count(*)
+from json_each(?) hh
+         join homies h on h.id = hh.value
+where h.user_id = ?
+  and not exists (select 1
+                  from recent_restaurants rr
+                  where rr.homie_id = h.id
+                    and rr.restaurant_id = ?
+                    and rr.date = ?
+                    and rr.meal = ?)
+            "#,
+        )
+        .bind(&homie_ids)
+        .bind(user_id)
+        .bind(restaurant_id)
+        .bind(params.date)
+        .bind(params.meal.as_str())
+        .fetch_one(&mut *tx)
+        .instrument(tracing::info_span!("Counting new visits db query"))
+        .await?;
+        // picking the same restaurant again isn't a pick of its own, so undoing stays put
+        if new_visits == 0 {
+            return Ok(());
+        }
+
+        let pick_event_id = sqlx::query(
+            r#"insert into pick_events (user_id, restaurant_id, date, meal) values (?, ?, ?, ?)"#,
+        )
+        .bind(user_id)
+        .bind(restaurant_id)
+        .bind(params.date)
+        .bind(params.meal.as_str())
+        .execute(&mut *tx)
+        .instrument(tracing::info_span!("Recording pick db query"))
+        .await?
+        .last_insert_rowid();
+
+        sqlx::query(
+            r#"
+delete
+from recent_restaurants
+where homie_id in (select value from json_each(?))
+  and user_id = ?
+  and date = ?
+  and meal = ?
+  and restaurant_id != ?
+            "#,
+        )
+        .bind(&homie_ids)
+        .bind(user_id)
+        .bind(params.date)
+        .bind(params.meal.as_str())
+        .bind(restaurant_id)
+        .execute(&mut *tx)
+        .instrument(tracing::info_span!("Replacing today's picks db query"))
+        .await?;
+
+        sqlx::query(
             r#"
 with home_homies AS (SELECT value as homie_id FROM json_each(?))
 insert
-into recent_restaurants (homie_id, user_id, restaurant_id)
+into recent_restaurants (homie_id, user_id, restaurant_id, date, meal, pick_event_id)
 select h.id,
        ?,
-       r.id
+       r.id,
+       ?,
+       ?,
+       ?
 from home_homies hh
          join homies h on h.id = hh.homie_id
-         join restaurants r on r.id = ?;
-
+         join restaurants r on r.id = ?
+where true
+on conflict (homie_id, restaurant_id, date, meal) do nothing
         "#,
-            homie_ids,
-            user_id,
-            restaurant_id
         )
-        .execute(self)
+        .bind(&homie_ids)
+        .bind(user_id)
+        .bind(params.date)
+        .bind(params.meal.as_str())
+        .bind(pick_event_id)
+        .bind(restaurant_id)
+        .execute(&mut *tx)
         .instrument(tracing::info_span!(
             "Adding recent restaurant to homie db query"
         ))
         .await?;
+
+        // a pick whose visits were all replaced can't be undone anymore
+        sqlx::query(
+            r#"
+delete
+from pick_events
+where user_id = ?
+  and not exists (select 1 from recent_restaurants rr where rr.pick_event_id = pick_events.id)
+            "#,
+        )
+        .bind(user_id)
+        .execute(&mut *tx)
+        .instrument(tracing::info_span!("Removing replaced picks db query"))
+        .await?;
+        tx.commit().await?;
         Ok(())
     }
 }
@@ -0,0 +1,128 @@
+use std::fmt::Debug;
+
+use sqlx::Pool;
+
+use sqlx::Sqlite;
+use thiserror::Error;
+use tracing::Instrument;
+
+use crate::user::UserId;
+
+use super::RecentVisit;
+use super::RecentVisitRow;
+
+/// Every homie's visits over the last `days` days, newest first.
+#[tracing::instrument(skip(db))]
+pub async fn get_recent_visits(
+    days: u32,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl GetRecentVisits,
+) -> Result<Vec<RecentVisit>, GetRecentVisitsError> {
+    Ok(db.get_recent_visits(days, user_id.into()).await?)
+}
+
+/// The visits of the latest lunch within the last week that someone hasn't rated yet.
+///
+/// Today's lunch is only included with `include_today`, so that starting a new
+/// pick doesn't ask about a lunch that hasn't been eaten.
+#[tracing::instrument(skip(db))]
+pub async fn get_unrated_visits(
+    include_today: bool,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl GetRecentVisits,
+) -> Result<Vec<RecentVisit>, GetRecentVisitsError> {
+    Ok(db.get_unrated_visits(include_today, user_id.into()).await?)
+}
+
+#[derive(Error, Debug)]
+pub enum GetRecentVisitsError {
+    #[error(transparent)]
+    DbError(#[from] sqlx::Error),
+}
+
+pub trait GetRecentVisits {
+    async fn get_recent_visits(
+        &self,
+        days: u32,
+        user_id: UserId,
+    ) -> Result<Vec<RecentVisit>, sqlx::Error>;
+
+    async fn get_unrated_visits(
+        &self,
+        include_today: bool,
+        user_id: UserId,
+    ) -> Result<Vec<RecentVisit>, sqlx::Error>;
+}
+
+impl GetRecentVisits for Pool<Sqlite> {
+    #[tracing::instrument(skip(self))]
+    async fn get_recent_visits(
+        &self,
+        days: u32,
+        user_id: UserId,
+    ) -> Result<Vec<RecentVisit>, sqlx::Error> {
+        let rows: Vec<RecentVisitRow> = sqlx::query_as(
+            r#"
+select rr.date, rr.meal, r.name as restaurant_name, h.name as homie_name, rating.rating, rating.note
+from recent_restaurants rr
+         join homies h on h.id = rr.homie_id and h.user_id = rr.user_id
+         join restaurants r on r.id = rr.restaurant_id and r.user_id = rr.user_id
+         left join recent_restaurant_ratings rating
+                   on rating.homie_id = rr.homie_id
+                       and rating.restaurant_id = rr.restaurant_id
+                       and rating.date = rr.date
+                       and rating.meal = rr.meal
+where rr.user_id = ?
+  and rr.date >= date('now', '-' || ? || ' days')
+order by rr.date desc, r.name, h.name
+            "#,
+        )
+        .bind(user_id.as_i32())
+        .bind(days)
+        .fetch_all(self)
+        .instrument(tracing::info_span!("Getting recent visits db query"))
+        .await?;
+
+        Ok(rows.into_iter().map(|row| row.into()).collect())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_unrated_visits(
+        &self,
+        include_today: bool,
+        user_id: UserId,
+    ) -> Result<Vec<RecentVisit>, sqlx::Error> {
+        let rows: Vec<RecentVisitRow> = sqlx::query_as(
+            r#"
+with unrated as (select rr.*
+                 from recent_restaurants rr
+                 where rr.user_id = ?
+                   and rr.date >= date('now', '-7 days')
+                   and (? or rr.date < current_date)
+                   and not exists (select 1
+                                   from recent_restaurant_ratings rating
+                                   where rating.homie_id = rr.homie_id
+                                     and rating.restaurant_id = rr.restaurant_id
+                                     and rating.date = rr.date
+                                     and rating.meal = rr.meal)),
+     latest as (select restaurant_id, date, meal
+                from unrated
+                order by date desc, created_at desc
+                limit 1)
+select u.date, u.meal, r.name as restaurant_name, h.name as homie_name, null as rating, null as note
+from unrated u
+         join latest l on l.restaurant_id = u.restaurant_id and l.date = u.date and l.meal = u.meal
+         join homies h on h.id = u.homie_id and h.user_id = u.user_id
+         join restaurants r on r.id = u.restaurant_id and r.user_id = u.user_id
+order by h.name
+            "#,
+        )
+        .bind(user_id.as_i32())
+        .bind(include_today)
+        .fetch_all(self)
+        .instrument(tracing::info_span!("Getting unrated visits db query"))
+        .await?;
+
+        Ok(rows.into_iter().map(|row| row.into()).collect())
+    }
+}
@@ -0,0 +1,178 @@
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+use chrono::NaiveDate;
+use sqlx::FromRow;
+use thiserror::Error;
+
+use crate::features::RestaurantId;
+
+#[derive(Error, Debug)]
+pub enum MealSlotParseError {
+    #[error("Unknown meal: {:?}, expected breakfast, lunch or dinner", meal)]
+    UnknownMeal { meal: String },
+}
+
+/// Which meal of the day a visit was for; each meal keeps its own recent history.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash, Default)]
+pub enum MealSlot {
+    Breakfast,
+    #[default]
+    Lunch,
+    Dinner,
+}
+
+impl MealSlot {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            MealSlot::Breakfast => "breakfast",
+            MealSlot::Lunch => "lunch",
+            MealSlot::Dinner => "dinner",
+        }
+    }
+}
+
+impl Display for MealSlot {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for MealSlot {
+    type Err = MealSlotParseError;
+
+    fn from_str(meal: &str) -> Result<Self, Self::Err> {
+        match meal.trim().to_lowercase().as_str() {
+            "breakfast" => Ok(MealSlot::Breakfast),
+            "lunch" => Ok(MealSlot::Lunch),
+            "dinner" => Ok(MealSlot::Dinner),
+            _ => Err(MealSlotParseError::UnknownMeal {
+                meal: meal.to_string(),
+            }),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum VisitRatingValidationError {
+    #[error("Rating must be between 1 and 5, got {}", rating)]
+    OutOfRange { rating: i32 },
+}
+
+/// How a homie scored a lunch, from 1 (never again) to 5 (great).
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct VisitRating(u8);
+
+impl VisitRating {
+    pub fn as_u8(&self) -> u8 {
+        self.0
+    }
+}
+
+impl TryFrom<i32> for VisitRating {
+    type Error = VisitRatingValidationError;
+
+    fn try_from(rating: i32) -> Result<Self, Self::Error> {
+        match rating {
+            1..=5 => Ok(VisitRating(rating as u8)),
+            _ => Err(VisitRatingValidationError::OutOfRange { rating }),
+        }
+    }
+}
+
+impl TryFrom<u8> for VisitRating {
+    type Error = VisitRatingValidationError;
+
+    fn try_from(rating: u8) -> Result<Self, Self::Error> {
+        i32::from(rating).try_into()
+    }
+}
+
+/// A single homie's visit to a restaurant, with their rating if they left one.
+#[derive(Debug, PartialEq, Eq)]
+pub struct RecentVisit {
+    pub date: NaiveDate,
+    pub meal: MealSlot,
+    pub restaurant_name: String,
+    pub homie_name: String,
+    pub rating: Option<VisitRating>,
+    pub note: Option<String>,
+}
+
+#[derive(Debug, FromRow)]
+pub struct RecentVisitRow {
+    date: NaiveDate,
+    meal: String,
+    restaurant_name: String,
+    homie_name: String,
+    rating: Option<i32>,
+    note: Option<String>,
+}
+
+impl From<RecentVisitRow> for RecentVisit {
+    fn from(row: RecentVisitRow) -> Self {
+        Self {
+            date: row.date,
+            meal: row.meal.parse().unwrap_or_default(),
+            restaurant_name: row.restaurant_name,
+            homie_name: row.homie_name,
+            rating: row.rating.and_then(|r| r.try_into().ok()),
+            note: row.note,
+        }
+    }
+}
+
+/// A homie's visit already recorded for today's meal.
+#[derive(Debug, PartialEq, Eq)]
+pub struct TodaysPick {
+    pub restaurant_id: RestaurantId,
+    pub restaurant_name: String,
+    pub homie_name: String,
+}
+
+#[derive(Debug, FromRow)]
+pub struct TodaysPickRow {
+    restaurant_id: i32,
+    restaurant_name: String,
+    homie_name: String,
+}
+
+impl From<TodaysPickRow> for TodaysPick {
+    fn from(row: TodaysPickRow) -> Self {
+        Self {
+            restaurant_id: row.restaurant_id.into(),
+            restaurant_name: row.restaurant_name,
+            homie_name: row.homie_name,
+        }
+    }
+}
+
+/// A pick that was taken back, with the homies it had been recorded for.
+#[derive(Debug, PartialEq, Eq)]
+pub struct UndonePick {
+    pub date: NaiveDate,
+    pub meal: MealSlot,
+    pub restaurant_name: String,
+    pub homie_names: Vec<String>,
+}
+
+#[cfg(test)]
+mod tests {
+    use super::MealSlot;
+    use super::VisitRating;
+
+    #[test]
+    fn rating_must_be_between_one_and_five() {
+        assert!(VisitRating::try_from(0).is_err());
+        assert!(VisitRating::try_from(6).is_err());
+        assert_eq!(5, VisitRating::try_from(5).unwrap().as_u8());
+    }
+
+    #[test]
+    fn parses_meal_slots() {
+        assert_eq!(MealSlot::Dinner, " Dinner".parse().unwrap());
+        assert_eq!(MealSlot::Lunch, MealSlot::default());
+        assert!("brunch".parse::<MealSlot>().is_err());
+    }
+}
@@ -0,0 +1,173 @@
+use std::fmt::Debug;
+
+use chrono::Local;
+use chrono::NaiveDate;
+use sqlx::Pool;
+use sqlx::Sqlite;
+use thiserror::Error;
+use tracing::event;
+use tracing::Instrument;
+use tracing::Level;
+
+use crate::features::HomieId;
+use crate::user::UserId;
+
+use super::MealSlot;
+use super::TodaysPick;
+use super::TodaysPickRow;
+use super::UndonePick;
+
+/// The visits already recorded for today's `meal` of any of the homies.
+#[tracing::instrument(skip(db))]
+pub async fn get_todays_picks<T, Y>(
+    homie_ids: T,
+    meal: MealSlot,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl PickEvents,
+) -> Result<Vec<TodaysPick>, PickEventsError>
+where
+    T: IntoIterator<Item = Y> + Debug,
+    Y: Into<HomieId> + Debug,
+{
+    let homie_ids: Vec<HomieId> = homie_ids.into_iter().map(|id| id.into()).collect();
+    Ok(db
+        .get_todays_picks(&homie_ids, meal, Local::now().date_naive(), user_id.into())
+        .await?)
+}
+
+/// Removes the most recently recorded pick together with its visits and their ratings.
+///
+/// Visits the pick replaced aren't brought back, and neither are visits added by hand.
+#[tracing::instrument(skip(db))]
+pub async fn undo_last_pick(
+    user_id: impl Into<UserId> + Debug,
+    db: &impl PickEvents,
+) -> Result<UndonePick, PickEventsError> {
+    let undone = db
+        .undo_last_pick(user_id.into())
+        .await?
+        .ok_or(PickEventsError::NothingToUndo)?;
+
+    event!(
+        Level::INFO,
+        name = "Pick undone",
+        restaurant_name = undone.restaurant_name.as_str(),
+        date = %undone.date,
+        meal = undone.meal.as_str()
+    );
+
+    Ok(undone)
+}
+
+#[derive(Error, Debug)]
+pub enum PickEventsError {
+    #[error("There is no pick to undo")]
+    NothingToUndo,
+
+    #[error(transparent)]
+    DbError(#[from] sqlx::Error),
+}
+
+pub trait PickEvents {
+    async fn get_todays_picks(
+        &self,
+        homie_ids: &[HomieId],
+        meal: MealSlot,
+        date: NaiveDate,
+        user_id: UserId,
+    ) -> Result<Vec<TodaysPick>, sqlx::Error>;
+
+    async fn undo_last_pick(&self, user_id: UserId) -> Result<Option<UndonePick>, sqlx::Error>;
+}
+
+impl PickEvents for Pool<Sqlite> {
+    #[tracing::instrument(skip(self))]
+    async fn get_todays_picks(
+        &self,
+        homie_ids: &[HomieId],
+        meal: MealSlot,
+        date: NaiveDate,
+        user_id: UserId,
+    ) -> Result<Vec<TodaysPick>, sqlx::Error> {
+        let rows: Vec<TodaysPickRow> = sqlx::query_as(
+            r#"
+select rr.restaurant_id,
+       r.name as restaurant_name,
+       h.name as homie_name
+from recent_restaurants rr
+         join json_each(?) hh on hh.value = rr.homie_id
+         join homies h on h.id = rr.homie_id
+         join restaurants r on r.id = rr.restaurant_id
+where rr.user_id = ?
+  and rr.date = ?
+  and rr.meal = ?
+order by r.name, h.name
+            "#,
+        )
+        .bind(
+            serde_json::to_string(&homie_ids.iter().map(|h| h.as_i32()).collect::<Vec<i32>>())
+                .expect("unable to serialize list of home homie ids as json"),
+        )
+        .bind(user_id.as_i32())
+        .bind(date)
+        .bind(meal.as_str())
+        .fetch_all(self)
+        .instrument(tracing::info_span!("Getting today's picks db query"))
+        .await?;
+        Ok(rows.into_iter().map(TodaysPick::from).collect())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn undo_last_pick(&self, user_id: UserId) -> Result<Option<UndonePick>, sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let last: Option<(i64, NaiveDate, String, String)> = sqlx::query_as(
+            r#"
+select p.id, p.date, p.meal, r.name
+from pick_events p
+         join restaurants r on r.id = p.restaurant_id
+where p.user_id = ?
+order by p.id desc
+limit 1
+            "#,
+        )
+        .bind(user_id.as_i32())
+        .fetch_optional(&mut *tx)
+        .instrument(tracing::info_span!("Getting last pick db query"))
+        .await?;
+        let Some((id, date, meal, restaurant_name)) = last else {
+            return Ok(None);
+        };
+
+        let homie_names: Vec<String> = sqlx::query_scalar(
+            r#"
+select h.name
+from recent_restaurants rr
+         join homies h on h.id = rr.homie_id
+where rr.pick_event_id = ?
+order by h.name
+            "#,
+        )
+        .bind(id)
+        .fetch_all(&mut *tx)
+        .instrument(tracing::info_span!("Getting homies of last pick db query"))
+        .await?;
+        sqlx::query("delete from recent_restaurants where pick_event_id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .instrument(tracing::info_span!("Removing visits of last pick db query"))
+            .await?;
+        sqlx::query("delete from pick_events where id = ?")
+            .bind(id)
+            .execute(&mut *tx)
+            .instrument(tracing::info_span!("Removing last pick db query"))
+            .await?;
+        tx.commit().await?;
+
+        Ok(Some(UndonePick {
+            date,
+            meal: meal.parse().unwrap_or_default(),
+            restaurant_name,
+            homie_names,
+        }))
+    }
+}
@@ -0,0 +1,140 @@
+use std::fmt::Debug;
+
+use chrono::NaiveDate;
+use sqlx::Pool;
+
+use sqlx::Sqlite;
+use thiserror::Error;
+use tracing::Instrument;
+
+use crate::features::HomieNameValidationError;
+use crate::features::HomiesName;
+use crate::features::RestaurantName;
+use crate::features::RestaurantNameValidationError;
+use crate::user::UserId;
+
+use super::MealSlot;
+use super::VisitRating;
+
+/// Rates a homie's visit to a restaurant, replacing any earlier rating of the same visit.
+///
+/// Without a `date` the homie's latest visit to the restaurant is rated, and without a
+/// `meal` the latest meal there that day.
+#[allow(clippy::too_many_arguments)]
+#[tracing::instrument(skip(db))]
+pub async fn rate_recent_restaurant(
+    homie_name: impl TryInto<HomiesName, Error = HomieNameValidationError> + Debug,
+    restaurant_name: impl TryInto<RestaurantName, Error = RestaurantNameValidationError> + Debug,
+    date: Option<NaiveDate>,
+    meal: Option<MealSlot>,
+    rating: VisitRating,
+    note: Option<String>,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl RateRecentRestaurant,
+) -> Result<(), RateRecentRestaurantError> {
+    let params = RateRecentRestaurantParams {
+        user_id: user_id.into(),
+        name: homie_name.try_into()?,
+        restaurant_name: restaurant_name.try_into()?,
+        date,
+        meal,
+        rating,
+        note: note.map(|n| n.trim().to_string()).filter(|n| !n.is_empty()),
+    };
+
+    db.rate_recent_restaurant(&params)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => RateRecentRestaurantError::VisitNotFound {
+                name: params.name.as_str().to_string(),
+                restaurant_name: params.restaurant_name.as_str().to_string(),
+            },
+            _ => RateRecentRestaurantError::UnknownDbError(e),
+        })?;
+
+    Ok(())
+}
+
+#[derive(Debug)]
+pub struct RateRecentRestaurantParams {
+    user_id: UserId,
+    name: HomiesName,
+    restaurant_name: RestaurantName,
+    date: Option<NaiveDate>,
+    meal: Option<MealSlot>,
+    rating: VisitRating,
+    note: Option<String>,
+}
+
+#[derive(Error, Debug)]
+pub enum RateRecentRestaurantError {
+    #[error(transparent)]
+    HomieNameValidationError(#[from] HomieNameValidationError),
+
+    #[error(transparent)]
+    RestaurantNameValidationError(#[from] RestaurantNameValidationError),
+
+    #[error("{:?} has no visit to {:?} to rate", name, restaurant_name)]
+    VisitNotFound {
+        name: String,
+        restaurant_name: String,
+    },
+
+    #[error("Unknown db error")]
+    UnknownDbError(#[from] sqlx::Error),
+}
+
+pub trait RateRecentRestaurant {
+    async fn rate_recent_restaurant(
+        &self,
+        params: &RateRecentRestaurantParams,
+    ) -> Result<(), sqlx::Error>;
+}
+
+impl RateRecentRestaurant for Pool<Sqlite> {
+    #[tracing::instrument(skip(self))]
+    async fn rate_recent_restaurant(
+        &self,
+        params: &RateRecentRestaurantParams,
+    ) -> Result<(), sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+insert into recent_restaurant_ratings (homie_id, restaurant_id, date, meal, user_id, rating, note)
+select rr.homie_id, rr.restaurant_id, rr.date, rr.meal, rr.user_id, ?, ?
+from recent_restaurants rr
+         join homies h on h.id = rr.homie_id and h.user_id = rr.user_id
+         join restaurants r on r.id = rr.restaurant_id and r.user_id = rr.user_id
+where rr.user_id = ?
+  and h.name = ?
+  and r.name = ?
+  and rr.date = coalesce(?, (select max(date)
+                             from recent_restaurants latest
+                             where latest.homie_id = rr.homie_id
+                               and latest.restaurant_id = rr.restaurant_id))
+  and rr.meal = coalesce(?, (select latest.meal
+                             from recent_restaurants latest
+                             where latest.homie_id = rr.homie_id
+                               and latest.restaurant_id = rr.restaurant_id
+                               and latest.date = rr.date
+                             order by latest.created_at desc, latest.meal = 'dinner' desc
+                             limit 1))
+on conflict (homie_id, restaurant_id, date, meal) do update set rating = excluded.rating,
+                                                                note   = excluded.note
+            "#,
+        )
+        .bind(i32::from(params.rating.as_u8()))
+        .bind(params.note.as_deref())
+        .bind(params.user_id.as_i32())
+        .bind(params.name.as_str())
+        .bind(params.restaurant_name.as_str())
+        .bind(params.date)
+        .bind(params.meal.map(|m| m.as_str()))
+        .execute(self)
+        .instrument(tracing::info_span!("Rating recent restaurant db query"))
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,2 @@
+mod reject_restaurants;
+pub use reject_restaurants::*;
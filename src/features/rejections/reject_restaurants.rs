@@ -0,0 +1,115 @@
+use std::fmt::Debug;
+
+use chrono::Local;
+use sqlx::Pool;
+
+use sqlx::Sqlite;
+use thiserror::Error;
+use tracing::Instrument;
+
+use crate::features::RestaurantId;
+use crate::user::UserId;
+
+/// How many days a rejected restaurant is down-weighted when picking candidates.
+pub const REJECTION_DAYS: u32 = 3;
+
+/// Records that the group turned these restaurants down.
+#[tracing::instrument(skip(db))]
+pub async fn reject_restaurants(
+    restaurant_ids: &[RestaurantId],
+    user_id: impl Into<UserId> + Debug,
+    db: &impl RejectRestaurants,
+) -> Result<(), RejectRestaurantsError> {
+    db.reject_restaurants(restaurant_ids, user_id.into())
+        .await?;
+
+    Ok(())
+}
+
+/// Restaurants rejected within the last [`REJECTION_DAYS`] days.
+#[tracing::instrument(skip(db))]
+pub async fn get_recently_rejected_restaurants(
+    user_id: impl Into<UserId> + Debug,
+    db: &impl RejectRestaurants,
+) -> Result<Vec<RestaurantId>, RejectRestaurantsError> {
+    Ok(db.get_recently_rejected_restaurants(user_id.into()).await?)
+}
+
+#[derive(Error, Debug)]
+pub enum RejectRestaurantsError {
+    #[error(transparent)]
+    DbError(#[from] sqlx::Error),
+}
+
+pub trait RejectRestaurants {
+    async fn reject_restaurants(
+        &self,
+        restaurant_ids: &[RestaurantId],
+        user_id: UserId,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn get_recently_rejected_restaurants(
+        &self,
+        user_id: UserId,
+    ) -> Result<Vec<RestaurantId>, sqlx::Error>;
+}
+
+impl RejectRestaurants for Pool<Sqlite> {
+    #[tracing::instrument(skip(self))]
+    async fn reject_restaurants(
+        &self,
+        restaurant_ids: &[RestaurantId],
+        user_id: UserId,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+insert into restaurant_rejections (restaurant_id, user_id, rejected_at)
+select r.id, r.user_id, ?
+from json_each(?) rejected
+         join restaurants r on r.id = rejected.value
+where r.user_id = ?
+            "#,
+        )
+        // local time like the lunch times they are compared with
+        .bind(Local::now().naive_local())
+        .bind(
+            serde_json::to_string(
+                &restaurant_ids
+                    .iter()
+                    .map(|r| *r.as_i32())
+                    .collect::<Vec<i32>>(),
+            )
+            .expect("unable to serialize list of restaurant ids as json"),
+        )
+        .bind(user_id.as_i32())
+        .execute(self)
+        .instrument(tracing::info_span!("Rejecting restaurants db query"))
+        .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_recently_rejected_restaurants(
+        &self,
+        user_id: UserId,
+    ) -> Result<Vec<RestaurantId>, sqlx::Error> {
+        let rejected: Vec<i32> = sqlx::query_scalar(
+            r#"
+select distinct restaurant_id
+from restaurant_rejections
+where user_id = ?
+  and rejected_at >= datetime(?, '-' || ? || ' days')
+order by restaurant_id
+            "#,
+        )
+        .bind(user_id.as_i32())
+        .bind(Local::now().naive_local())
+        .bind(REJECTION_DAYS)
+        .fetch_all(self)
+        .instrument(tracing::info_span!(
+            "Getting recently rejected restaurants db query"
+        ))
+        .await?;
+        Ok(rejected.into_iter().map(|id| id.into()).collect())
+    }
+}
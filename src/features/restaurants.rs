@@ -1,9 +1,17 @@
 mod create_restaurant;
 mod get_all_restaurants;
 mod get_candidates;
+mod get_restaurant;
 mod models;
+mod opening_hours;
+mod restaurant_hours;
+mod update_attributes;
 
 pub use create_restaurant::*;
 pub use get_all_restaurants::*;
 pub use get_candidates::*;
+pub use get_restaurant::*;
 pub use models::*;
+pub use opening_hours::*;
+pub use restaurant_hours::*;
+pub use update_attributes::*;
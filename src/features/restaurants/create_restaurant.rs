@@ -24,7 +24,7 @@ pub async fn create_restaurant(
 }
 
 #[derive(Debug)]
-struct CreateRestaurantParams<'a> {
+pub struct CreateRestaurantParams<'a> {
     user_id: i32,
     name: &'a str,
 }
@@ -34,7 +34,7 @@ impl GetAllRestaurants for Pool<Sqlite> {
     #[tracing::instrument(name = "Getting all Restaurants", skip(self))]
     async fn get_all_restaurants(&self, params: UserId) -> Result<Vec<Restaurant>, sqlx::Error> {
         let restaurant: Vec<RestaurantRow> =
-            sqlx::query_as(r#"select id, user_id, name from restaurants where user_id = ?"#)
+            sqlx::query_as(r#"select * from restaurants_view where user_id = ?"#)
                 .bind(params.as_i32())
                 .fetch_all(self)
                 .instrument(tracing::info_span!("Querying all restaurants"))
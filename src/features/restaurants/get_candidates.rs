@@ -1,4 +1,9 @@
 use anyhow::Result;
+use chrono::Datelike;
+use chrono::Local;
+use chrono::NaiveDateTime;
+use sqlx::query::QueryAs;
+use sqlx::sqlite::SqliteArguments;
 use sqlx::Pool;
 use sqlx::Sqlite;
 
@@ -6,15 +11,133 @@ use std::fmt::Debug;
 use tracing::event;
 use tracing::Instrument;
 
+use crate::features::apply_lunch_rules;
+use crate::features::get_all_restaurants;
+use crate::features::get_fairness_boosts;
+use crate::features::get_lunch_rules_on;
+use crate::features::Coordinates;
+use crate::features::DietaryRestriction;
+use crate::features::FairnessBoost;
+use crate::features::GetHomiesSatisfaction;
 use crate::features::HomieId;
+use crate::features::LunchRules;
+use crate::features::MealSlot;
+use crate::features::RestaurantId;
+use crate::features::REJECTION_DAYS;
 use crate::user::UserId;
 
+use super::GetAllRestaurants;
+use super::PriceTier;
 use super::Restaurant;
 use super::RestaurantRow;
+use super::RestaurantTag;
+use super::ServiceMode;
+
+/// Narrows down which restaurants are offered when picking lunch.
+///
+/// Restaurants without a price tier or without any service modes recorded are
+/// not excluded by `max_price_tier` or `service_mode`, since we don't know better.
+/// Restaurants that can't accommodate the dietary restrictions of every home homie
+/// and of `dietary_restrictions`, or that any home homie has vetoed, are always
+/// excluded.
+///
+/// When `at` is given, restaurants closed at that time are excluded; restaurants
+/// without any opening hours recorded are assumed to be open. Likewise restaurants
+/// that can't seat `party_size` are excluded, unless their size isn't known, and
+/// restaurants further than `max_km` from `origin` as the crow flies, unless we don't
+/// know where they are.
+///
+/// A favorite counts for less when that homie has rated it below 3 on average
+/// over at least two visits, and for more when that homie has had fewer lunches at
+/// their favorites than the rest of the table lately (see
+/// [`fairness_boosts`](crate::features::fairness_boosts)). It is also weighted by
+/// how often that homie chose it over other restaurants, learned as an Elo rating,
+/// unless `ignore_elo` is set.
+/// Restaurants the group rejected in the last few days count for half.
+///
+/// Restaurants on the shared wishlist or a home homie's wishlist come first until
+/// they have been visited.
+///
+/// The [lunch rules](crate::features::LunchRule) that apply on the day are folded in
+/// when picking, see [`apply_lunch_rules`](crate::features::apply_lunch_rules).
+#[derive(Debug, Default, Clone)]
+pub struct CandidateOptions {
+    /// Every tag has to be present on the restaurant
+    pub tags: Vec<RestaurantTag>,
+    pub max_price_tier: Option<PriceTier>,
+    pub service_mode: Option<ServiceMode>,
+    /// When lunch is going to be, in local time; recent lunches, rejections and
+    /// fairness are counted back from it, or from now unless given
+    pub at: Option<NaiveDateTime>,
+    /// Only visits for the same meal keep a restaurant off the list
+    pub meal: MealSlot,
+    /// Restaurants already shown and turned down, e.g. when rerolling
+    pub exclude: Vec<RestaurantId>,
+    /// Restaurants with any of these tags are never suggested
+    pub avoid_tags: Vec<RestaurantTag>,
+    /// Candidates with any of these tags count for more
+    pub prefer_tags: Vec<RestaurantTag>,
+    /// Candidates that count for more
+    pub prefer: Vec<RestaurantId>,
+    /// When any of these passes the other filters they are the only candidates
+    pub force: Vec<RestaurantId>,
+    /// Restrictions to accommodate on top of the home homies' own, e.g. for guests
+    pub dietary_restrictions: Vec<DietaryRestriction>,
+    /// How many people are coming, guests included
+    pub party_size: Option<u32>,
+    /// Where lunch starts from
+    pub origin: Option<Coordinates>,
+    /// How far from `origin` a restaurant may be
+    pub max_km: Option<f64>,
+    /// Restaurants that belong to another location are never suggested
+    pub location_id: Option<i64>,
+    /// How many of each homie's latest lunches keep a restaurant off the list,
+    /// [`RECENCY_WINDOW`] unless given
+    pub recency_window: Option<u32>,
+    /// Favorites count the same no matter how fairly lunches were shared lately
+    pub ignore_fairness: bool,
+    /// Favorites count the same no matter how often they won head-to-head matchups
+    pub ignore_elo: bool,
+}
+
+/// How many of each homie's latest lunches keep a restaurant off the list by default.
+pub const RECENCY_WINDOW: u32 = 5;
+
+/// How much more a candidate preferred by a lunch rule counts.
+const PREFERENCE_BOOST: f64 = 2.0;
+
+/// The options with the lunch rules that apply on the day of `options.at` folded in,
+/// and with the restaurants beyond `max_km` excluded.
+async fn prepare_options(
+    options: &CandidateOptions,
+    user_id: UserId,
+    db: &impl GetCandidates,
+) -> Result<CandidateOptions> {
+    let date = options
+        .at
+        .map_or_else(|| Local::now().date_naive(), |at| at.date());
+    let mut options = options.clone();
+    apply_lunch_rules(&mut options, &get_lunch_rules_on(date, user_id, db).await?);
+    // sqlite has no trigonometry built in, so distances are measured here
+    if let (Some(origin), Some(max_km)) = (options.origin, options.max_km) {
+        options.exclude.extend(
+            get_all_restaurants(user_id, db)
+                .await?
+                .into_iter()
+                .filter(|r| {
+                    r.coordinates
+                        .is_some_and(|c| origin.distance_km(&c) > max_km)
+                })
+                .map(|r| r.id),
+        );
+    }
+    Ok(options)
+}
 
 #[tracing::instrument(skip(db))]
-pub async fn get_candidate_restaurants<'a, T, Y>(
+pub async fn get_candidate_restaurants<T, Y>(
     homie_ids: T,
+    options: &CandidateOptions,
     user_id: impl Into<UserId> + Debug,
     db: &impl GetCandidates,
 ) -> Result<Vec<Restaurant>>
@@ -26,72 +149,480 @@ where
 
     let h: Vec<_> = homie_ids.iter().collect();
     let user_id = user_id.into();
+    let options = &prepare_options(options, user_id, db).await?;
+
+    let forced = options
+        .force
+        .iter()
+        .filter(|r| !options.exclude.contains(r))
+        .copied()
+        .collect::<Vec<_>>();
+    if !forced.is_empty() {
+        let forced = db
+            .get_forced_candidates(h.as_slice(), &forced, options, user_id)
+            .await?;
+        if !forced.is_empty() {
+            return Ok(forced);
+        }
+    }
+
+    let boosts = match options.ignore_fairness {
+        true => Vec::new(),
+        false => {
+            let date = options
+                .at
+                .map_or_else(|| Local::now().date_naive(), |at| at.date());
+            get_fairness_boosts(homie_ids.clone(), date, user_id, db).await?
+        }
+    };
 
-    let created_restaurant = db.get_candidates(h.as_slice(), user_id).await;
+    let mut candidates = db
+        .get_wishlist_candidates(h.as_slice(), options, user_id)
+        .await?;
+    let favorites = db
+        .get_candidates(h.as_slice(), options, &boosts, user_id)
+        .await;
+    candidates.extend(
+        favorites
+            .into_iter()
+            .filter(|f| candidates.iter().all(|c| c.id != f.id))
+            .collect::<Vec<_>>(),
+    );
 
     event!(
         tracing::Level::INFO,
         "Got candidates restaurants for homies"
     );
 
-    Ok(created_restaurant)
+    Ok(candidates)
 }
 
-trait GetCandidates {
-    async fn get_candidates(&self, home_homies: &[&HomieId], user_id: UserId) -> Vec<Restaurant>;
+/// How often a pick gets a slot for something nobody has tried yet, when not asked for.
+pub const EXPLORATION_RATE: f64 = 0.2;
+
+/// A restaurant nobody has favorited or visited yet that passes the same filters as
+/// the candidates, to try something new.
+#[tracing::instrument(skip(db))]
+pub async fn get_exploration_restaurant<T, Y>(
+    homie_ids: T,
+    options: &CandidateOptions,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl GetCandidates,
+) -> Result<Option<Restaurant>>
+where
+    T: IntoIterator<Item = Y> + Debug,
+    Y: Into<HomieId> + Debug,
+{
+    let homie_ids: Vec<HomieId> = homie_ids.into_iter().map(|id| id.into()).collect();
+    let h: Vec<_> = homie_ids.iter().collect();
+    let user_id = user_id.into();
+    let options = prepare_options(options, user_id, db).await?;
+    if !options.force.is_empty()
+        && !db
+            .get_forced_candidates(h.as_slice(), &options.force, &options, user_id)
+            .await?
+            .is_empty()
+    {
+        return Ok(None);
+    }
+
+    Ok(db
+        .get_exploration_candidate(h.as_slice(), &options, user_id)
+        .await?)
 }
 
-impl GetCandidates for Pool<Sqlite> {
-    async fn get_candidates(&self, home_homies: &[&HomieId], user_id: UserId) -> Vec<Restaurant> {
-        let candidates: Vec<RestaurantRow> = sqlx::query_as(
-            r#"
+pub trait GetCandidates: GetHomiesSatisfaction + LunchRules + GetAllRestaurants {
+    async fn get_candidates(
+        &self,
+        home_homies: &[&HomieId],
+        options: &CandidateOptions,
+        boosts: &[FairnessBoost],
+        user_id: UserId,
+    ) -> Vec<Restaurant>;
+
+    async fn get_wishlist_candidates(
+        &self,
+        home_homies: &[&HomieId],
+        options: &CandidateOptions,
+        user_id: UserId,
+    ) -> Result<Vec<Restaurant>, sqlx::Error>;
+
+    async fn get_exploration_candidate(
+        &self,
+        home_homies: &[&HomieId],
+        options: &CandidateOptions,
+        user_id: UserId,
+    ) -> Result<Option<Restaurant>, sqlx::Error>;
+
+    /// The forced restaurants that pass the same filters as the candidates.
+    async fn get_forced_candidates(
+        &self,
+        home_homies: &[&HomieId],
+        restaurant_ids: &[RestaurantId],
+        options: &CandidateOptions,
+        user_id: UserId,
+    ) -> Result<Vec<Restaurant>, sqlx::Error>;
+}
+
+/// Restaurants passing every filter in the options for the home homies, shared by
+/// the favorites and the exploration queries. Bind it with [`bind_eligible`].
+const ELIGIBLE_RESTAURANTS: &str = r#"
 with home_homies AS (SELECT value as homie_id FROM json_each(?)),
+     pick_time as (select ? as date, ? as weekday, ? as time, ? as meal),
+     eligible as (select r.*
+                  from restaurants r
+                  where r.user_id = ?
+                    and r.id not in (select value from json_each(?))
+                    and not exists (select 1
+                                    from json_each(?) wanted
+                                    where wanted.value not in (select tag
+                                                               from restaurant_tags rt
+                                                               where rt.restaurant_id = r.id))
+                    and not exists (select 1
+                                    from restaurant_tags rt
+                                    where rt.restaurant_id = r.id
+                                      and rt.tag in (select value from json_each(?)))
+                    and (? is null or r.price_tier is null or r.price_tier <= ?)
+                    and (? is null or r.max_party_size is null or r.max_party_size >= ?)
+                    and (? is null or r.location_id is null or r.location_id = ?)
+                    and (? is null
+                        or not exists (select 1
+                                       from restaurant_service_modes m
+                                       where m.restaurant_id = r.id)
+                        or exists (select 1
+                                   from restaurant_service_modes m
+                                   where m.restaurant_id = r.id
+                                     and m.service_mode = ?))
+                    and not exists (select 1
+                                    from homies_dietary_restrictions d
+                                             join home_homies hh on hh.homie_id = d.homie_id
+                                    where d.user_id = r.user_id
+                                      and d.restriction not in (select a.restriction
+                                                                from restaurant_dietary_accommodations a
+                                                                where a.restaurant_id = r.id))
+                    and not exists (select 1
+                                    from json_each(?) d
+                                    where d.value not in (select a.restriction
+                                                          from restaurant_dietary_accommodations a
+                                                          where a.restaurant_id = r.id))
+                    and not exists (select 1
+                                    from homies_vetoed_restaurants hvr
+                                             join home_homies hh on hh.homie_id = hvr.homie_id
+                                    where hvr.restaurant_id = r.id
+                                      and hvr.user_id = r.user_id)
+                    and ((select time from pick_time) is null
+                        or case
+                               when exists (select 1
+                                            from restaurant_hours_exceptions e
+                                                     join pick_time p on e.date = p.date
+                                            where e.restaurant_id = r.id)
+                                   then exists (select 1
+                                                from restaurant_hours_exceptions e
+                                                         join pick_time p on e.date = p.date
+                                                where e.restaurant_id = r.id
+                                                  and e.opens <= p.time
+                                                  and p.time < e.closes)
+                               when exists (select 1
+                                            from restaurant_opening_hours o
+                                            where o.restaurant_id = r.id)
+                                   then exists (select 1
+                                                from restaurant_opening_hours o
+                                                         join pick_time p on o.weekday = p.weekday
+                                                where o.restaurant_id = r.id
+                                                  and o.opens <= p.time
+                                                  and p.time < o.closes)
+                               else 1 end))"#;
+
+fn bind_eligible<'q, O>(
+    query: QueryAs<'q, Sqlite, O, SqliteArguments<'q>>,
+    home_homies: &[&HomieId],
+    options: &CandidateOptions,
+    user_id: UserId,
+) -> QueryAs<'q, Sqlite, O, SqliteArguments<'q>> {
+    let exclude = options
+        .exclude
+        .iter()
+        .map(|r| *r.as_i32())
+        .collect::<Vec<i32>>();
+    let tags = options
+        .tags
+        .iter()
+        .map(|t| t.as_str())
+        .collect::<Vec<&str>>();
+    let avoid_tags = options
+        .avoid_tags
+        .iter()
+        .map(|t| t.as_str())
+        .collect::<Vec<&str>>();
+    let max_price_tier = options.max_price_tier.map(|p| p.as_u8());
+    let service_mode = options.service_mode.map(|m| m.as_str());
+    let dietary_restrictions = options
+        .dietary_restrictions
+        .iter()
+        .map(|d| d.as_str())
+        .collect::<Vec<&str>>();
+    query
+        .bind(
+            serde_json::to_string(&home_homies.iter().map(|h| h.as_i32()).collect::<Vec<i32>>())
+                .expect("unable to serialize list of home homie ids as json"),
+        )
+        .bind(options.at.map(|at| at.date()))
+        .bind(options.at.map(|at| at.weekday().num_days_from_monday()))
+        .bind(options.at.map(|at| at.time().format("%H:%M").to_string()))
+        .bind(options.meal.as_str())
+        .bind(*user_id.as_i32())
+        .bind(
+            serde_json::to_string(&exclude)
+                .expect("unable to serialize list of excluded restaurants as json"),
+        )
+        .bind(serde_json::to_string(&tags).expect("unable to serialize list of tags as json"))
+        .bind(
+            serde_json::to_string(&avoid_tags)
+                .expect("unable to serialize list of avoided tags as json"),
+        )
+        .bind(max_price_tier)
+        .bind(max_price_tier)
+        .bind(options.party_size)
+        .bind(options.party_size)
+        .bind(options.location_id)
+        .bind(options.location_id)
+        .bind(service_mode)
+        .bind(service_mode)
+        .bind(
+            serde_json::to_string(&dietary_restrictions)
+                .expect("unable to serialize list of dietary restrictions as json"),
+        )
+}
+
+impl GetCandidates for Pool<Sqlite> {
+    async fn get_candidates(
+        &self,
+        home_homies: &[&HomieId],
+        options: &CandidateOptions,
+        boosts: &[FairnessBoost],
+        user_id: UserId,
+    ) -> Vec<Restaurant> {
+        let reference = options.at.unwrap_or_else(|| Local::now().naive_local());
+        let boosts = boosts
+            .iter()
+            .map(|b| (b.satisfaction.homie_id.as_i32(), b.boost))
+            .collect::<Vec<(i32, f64)>>();
+        let sql = format!(
+            r#"{ELIGIBLE_RESTAURANTS},
+     reference as (select ? as date, ? as time),
+     latest_lunches as (select restaurant_id, homie_id, user_id, date, meal
+                        from (select *, rank() over (partition by homie_id, meal order by date desc) as rank
+                              from recent_restaurants
+                              where date <= (select date from reference))
+                        where rank <= ?),
      recents as (select restaurant_id, count(distinct homie_id) as occurrences
-                 from homies_recents_restaurants_view v
+                 from latest_lunches v
                           join home_homies using (homie_id)
                  where v.user_id = ?
+                   and v.meal = (select meal from pick_time)
                  group by v.restaurant_id
                  order by occurrences desc),
      most_recents as (select restaurant_id
                       from recents
                       where occurrences = (select max(occurrences) from recents)),
-     home_homies_favorites as (select r.id as restaurant_id, r.user_id as user_id, h.id as homie_id
-                               from restaurants r
+     home_homies_favorites as (select r.id as restaurant_id, r.user_id as user_id, h.id as homie_id, hfr.strength
+                               from eligible r
                                         join homies_favorite_restaurants hfr
                                              on r.user_id = hfr.user_id and r.id = hfr.restaurant_id
                                         join homies h on r.user_id = h.user_id and h.id = hfr.homie_id
                                         join home_homies hh on hh.homie_id = h.id
-                               where r.user_id = 1
-                                 and not exists (select 1
-                                                 from homies_recents_restaurants_view v
+                               where not exists (select 1
+                                                 from latest_lunches v
                                                           join home_homies hh on v.homie_id = hh.homie_id
                                                  where v.restaurant_id = r.id
-                                                   and (date = current_date or v.homie_id = h.id)
-                                                   and v.user_id = r.user_id))
+                                                   and v.meal = (select meal from pick_time)
+                                                   and (date = (select date from reference) or v.homie_id = h.id)
+                                                   and v.user_id = r.user_id)),
+     rating_decay as (select homie_id, restaurant_id, min(1.0, avg(rating) / 3.0) as decay
+                      from recent_restaurant_ratings
+                      where user_id = ?
+                      group by homie_id, restaurant_id
+                      having count(*) >= 2),
+     fairness as (select json_extract(value, '$[0]') as homie_id, json_extract(value, '$[1]') as boost
+                  from json_each(?)),
+     learned as (select homie_id, restaurant_id, max(0.5, min(2.0, 1.0 + (rating - 1500) / 400.0)) as factor
+                 from homies_restaurant_elo
+                 where user_id = ?
+                   and not ?),
+     rejected as (select distinct restaurant_id
+                  from restaurant_rejections
+                  where user_id = ?
+                    and rejected_at >= datetime((select time from reference), '-' || ? || ' days')),
+     preferred as (select r.id as restaurant_id
+                   from eligible r
+                   where r.id in (select value from json_each(?))
+                      or exists (select 1
+                                 from restaurant_tags rt
+                                 where rt.restaurant_id = r.id
+                                   and rt.tag in (select value from json_each(?))))
 
 select r.*
 from (select *
-      from (select restaurant_id, count(distinct homie_id) as occurrences
+      from (select restaurant_id, sum(strength * coalesce(d.decay, 1.0) * coalesce(f.boost, 1.0) * coalesce(l.factor, 1.0))
+                       * (case when restaurant_id in (select restaurant_id from rejected) then 0.5 else 1.0 end)
+                       * (case when restaurant_id in (select restaurant_id from preferred) then ? else 1.0 end) as score
             from home_homies_favorites
+                     left join rating_decay d using (homie_id, restaurant_id)
+                     left join fairness f using (homie_id)
+                     left join learned l using (homie_id, restaurant_id)
             group by restaurant_id
 --       having occurrences > max(select occurrences from t)
-            order by occurrences desc) as t
+            order by score desc) as t
       where not exists(select 1
                        from most_recents
                        where t.restaurant_id = restaurant_id)
 
-      order by t.occurrences * random() desc
+      order by t.score * random() desc
       limit 25) t
-         join restaurants r on t.restaurant_id = r.id
-            "#,
-        )
-        .bind(&serde_json::to_string(&home_homies.iter().map(|h| h.as_i32()).collect::<Vec<i32>>()).expect("unable to serialize list of home homie ids as json"))
-            .bind(user_id.as_i32())
-        .fetch_all(self)
-        .instrument(tracing::info_span!("Getting candidates restaurants for homies", { "count of home homies" } = home_homies.len()) )
-        .await
-        .unwrap();
+         join restaurants_view r on t.restaurant_id = r.id
+"#
+        );
+        let candidates: Vec<RestaurantRow> =
+            bind_eligible(sqlx::query_as(&sql), home_homies, options, user_id)
+                .bind(reference.date())
+                .bind(reference)
+                .bind(options.recency_window.unwrap_or(RECENCY_WINDOW))
+                .bind(user_id.as_i32())
+                .bind(user_id.as_i32())
+                .bind(
+                    serde_json::to_string(&boosts)
+                        .expect("unable to serialize fairness boosts as json"),
+                )
+                .bind(user_id.as_i32())
+                .bind(options.ignore_elo)
+                .bind(user_id.as_i32())
+                .bind(REJECTION_DAYS)
+                .bind(
+                    serde_json::to_string(
+                        &options
+                            .prefer
+                            .iter()
+                            .map(|r| *r.as_i32())
+                            .collect::<Vec<i32>>(),
+                    )
+                    .expect("unable to serialize list of preferred restaurants as json"),
+                )
+                .bind(
+                    serde_json::to_string(
+                        &options
+                            .prefer_tags
+                            .iter()
+                            .map(|t| t.as_str())
+                            .collect::<Vec<&str>>(),
+                    )
+                    .expect("unable to serialize list of preferred tags as json"),
+                )
+                .bind(PREFERENCE_BOOST)
+                .fetch_all(self)
+                .instrument(tracing::info_span!(
+                    "Getting candidates restaurants for homies",
+                    { "count of home homies" } = home_homies.len()
+                ))
+                .await
+                .unwrap();
         // todo stream rows
         candidates.into_iter().map(|r| r.into()).collect()
     }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_wishlist_candidates(
+        &self,
+        home_homies: &[&HomieId],
+        options: &CandidateOptions,
+        user_id: UserId,
+    ) -> Result<Vec<Restaurant>, sqlx::Error> {
+        let sql = format!(
+            r#"{ELIGIBLE_RESTAURANTS}
+select v.*
+from eligible r
+         join restaurants_view v on v.id = r.id
+where exists (select 1
+              from restaurant_wishlist w
+              where w.restaurant_id = r.id
+                and (w.homie_id is null or w.homie_id in (select homie_id from home_homies))
+                and not exists (select 1
+                                from recent_restaurants rr
+                                where rr.restaurant_id = w.restaurant_id
+                                  and rr.date >= date(w.created_at)))
+order by random()
+            "#
+        );
+        let candidates: Vec<RestaurantRow> =
+            bind_eligible(sqlx::query_as(&sql), home_homies, options, user_id)
+                .fetch_all(self)
+                .instrument(tracing::info_span!("Getting wishlist candidates"))
+                .await?;
+        Ok(candidates.into_iter().map(|r| r.into()).collect())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_exploration_candidate(
+        &self,
+        home_homies: &[&HomieId],
+        options: &CandidateOptions,
+        user_id: UserId,
+    ) -> Result<Option<Restaurant>, sqlx::Error> {
+        let sql = format!(
+            r#"{ELIGIBLE_RESTAURANTS}
+select v.*
+from eligible r
+         join restaurants_view v on v.id = r.id
+where not exists (select 1
+                  from homies_favorite_restaurants hfr
+                  where hfr.restaurant_id = r.id)
+  and not exists (select 1
+                  from recent_restaurants rr
+                  where rr.restaurant_id = r.id)
+  and not exists (select 1
+                  from restaurant_wishlist w
+                  where w.restaurant_id = r.id)
+order by random()
+limit 1
+            "#
+        );
+        let candidate: Option<RestaurantRow> =
+            bind_eligible(sqlx::query_as(&sql), home_homies, options, user_id)
+                .fetch_optional(self)
+                .instrument(tracing::info_span!("Getting exploration candidate"))
+                .await?;
+        Ok(candidate.map(|r| r.into()))
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_forced_candidates(
+        &self,
+        home_homies: &[&HomieId],
+        restaurant_ids: &[RestaurantId],
+        options: &CandidateOptions,
+        user_id: UserId,
+    ) -> Result<Vec<Restaurant>, sqlx::Error> {
+        let sql = format!(
+            r#"{ELIGIBLE_RESTAURANTS}
+select v.*
+from eligible r
+         join restaurants_view v on v.id = r.id
+where r.id in (select value from json_each(?))
+            "#
+        );
+        let candidates: Vec<RestaurantRow> =
+            bind_eligible(sqlx::query_as(&sql), home_homies, options, user_id)
+                .bind(
+                    serde_json::to_string(
+                        &restaurant_ids
+                            .iter()
+                            .map(|r| *r.as_i32())
+                            .collect::<Vec<i32>>(),
+                    )
+                    .expect("unable to serialize list of restaurant ids as json"),
+                )
+                .fetch_all(self)
+                .instrument(tracing::info_span!("Getting forced candidates"))
+                .await?;
+        Ok(candidates.into_iter().map(|r| r.into()).collect())
+    }
 }
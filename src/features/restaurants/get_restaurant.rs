@@ -0,0 +1,70 @@
+use std::fmt::Debug;
+
+use sqlx::Pool;
+
+use sqlx::Sqlite;
+use thiserror::Error;
+use tracing::Instrument;
+
+use crate::user::UserId;
+
+use super::Restaurant;
+use super::RestaurantName;
+use super::RestaurantNameValidationError;
+use super::RestaurantRow;
+
+#[tracing::instrument(skip(db))]
+pub async fn get_restaurant(
+    restaurant_name: impl TryInto<RestaurantName, Error = RestaurantNameValidationError> + Debug,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl GetRestaurant,
+) -> Result<Restaurant, GetRestaurantError> {
+    let restaurant_name = restaurant_name.try_into()?;
+
+    db.get_restaurant(&restaurant_name, user_id.into())
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => GetRestaurantError::RestaurantNotFound {
+                name: restaurant_name.as_str().to_string(),
+            },
+            _ => GetRestaurantError::DbError(e),
+        })
+}
+
+#[derive(Error, Debug)]
+pub enum GetRestaurantError {
+    #[error(transparent)]
+    RestaurantNameValidationError(#[from] RestaurantNameValidationError),
+
+    #[error("Restaurant not found: {:?}", name)]
+    RestaurantNotFound { name: String },
+
+    #[error(transparent)]
+    DbError(#[from] sqlx::Error),
+}
+
+pub trait GetRestaurant {
+    async fn get_restaurant(
+        &self,
+        restaurant_name: &RestaurantName,
+        user_id: UserId,
+    ) -> Result<Restaurant, sqlx::Error>;
+}
+
+impl GetRestaurant for Pool<Sqlite> {
+    #[tracing::instrument(skip(self))]
+    async fn get_restaurant(
+        &self,
+        restaurant_name: &RestaurantName,
+        user_id: UserId,
+    ) -> Result<Restaurant, sqlx::Error> {
+        let restaurant: RestaurantRow =
+            sqlx::query_as(r#"select * from restaurants_view where user_id = ? and name = ?"#)
+                .bind(user_id.as_i32())
+                .bind(restaurant_name.as_str())
+                .fetch_one(self)
+                .instrument(tracing::info_span!("Querying restaurant by name"))
+                .await?;
+        Ok(restaurant.into())
+    }
+}
@@ -1,11 +1,36 @@
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
 use sqlx::prelude::FromRow;
 use thiserror::Error;
 
-#[derive(Debug, PartialEq, Eq, FromRow)]
+use crate::features::Coordinates;
+use crate::features::DietaryRestriction;
+
+#[derive(Debug, PartialEq, FromRow)]
 pub struct RestaurantRow {
     id: i32,
     user_id: i32,
     name: String,
+    #[sqlx(default)]
+    price_tier: Option<i32>,
+    #[sqlx(default)]
+    max_party_size: Option<i64>,
+    #[sqlx(default)]
+    reservations_above: Option<i64>,
+    #[sqlx(default)]
+    latitude: Option<f64>,
+    #[sqlx(default)]
+    longitude: Option<f64>,
+    #[sqlx(default)]
+    location_id: Option<i64>,
+    #[sqlx(default)]
+    tags: Option<String>,
+    #[sqlx(default)]
+    service_modes: Option<String>,
+    #[sqlx(default)]
+    accommodations: Option<String>,
 }
 
 impl RestaurantRow {
@@ -16,7 +41,37 @@ impl RestaurantRow {
 
 impl From<RestaurantRow> for Restaurant {
     fn from(row: RestaurantRow) -> Self {
-        Self::new_unchecked(row.id, row.name)
+        let mut restaurant = Self::new_unchecked(row.id, row.name);
+        restaurant.price_tier = row.price_tier.map(PriceTier::from_i32_unchecked);
+        restaurant.max_party_size = row.max_party_size.map(|size| size as u32);
+        restaurant.reservations_above = row.reservations_above.map(|size| size as u32);
+        restaurant.coordinates = row
+            .latitude
+            .zip(row.longitude)
+            .and_then(|(latitude, longitude)| Coordinates::new(latitude, longitude));
+        restaurant.location_id = row.location_id;
+        restaurant.tags = row
+            .tags
+            .iter()
+            .flat_map(|tags| tags.split(','))
+            .map(|tag| RestaurantTag::from_string_unchecked(tag.to_string()))
+            .collect();
+        restaurant.tags.sort();
+        restaurant.service_modes = row
+            .service_modes
+            .iter()
+            .flat_map(|modes| modes.split(','))
+            .filter_map(|mode| mode.parse().ok())
+            .collect();
+        restaurant.service_modes.sort();
+        restaurant.accommodations = row
+            .accommodations
+            .iter()
+            .flat_map(|restrictions| restrictions.split(','))
+            .filter_map(|restriction| restriction.parse().ok())
+            .collect();
+        restaurant.accommodations.sort();
+        restaurant
     }
 }
 
@@ -24,6 +79,19 @@ impl From<RestaurantRow> for Restaurant {
 pub struct Restaurant {
     pub id: RestaurantId,
     pub name: RestaurantName,
+    pub tags: Vec<RestaurantTag>,
+    pub price_tier: Option<PriceTier>,
+    pub service_modes: Vec<ServiceMode>,
+    /// Dietary restrictions the restaurant is able to cater for
+    pub accommodations: Vec<DietaryRestriction>,
+    /// The most people that can be seated together
+    pub max_party_size: Option<u32>,
+    /// Parties larger than this should call ahead
+    pub reservations_above: Option<u32>,
+    /// Where the restaurant is
+    pub coordinates: Option<Coordinates>,
+    /// The [location](crate::features::UserLocation) it's picked from, or any when `None`
+    pub location_id: Option<i64>,
 }
 
 impl Restaurant {
@@ -31,6 +99,14 @@ impl Restaurant {
         Self {
             id: id.into(),
             name: name.into(),
+            tags: vec![],
+            price_tier: None,
+            service_modes: vec![],
+            accommodations: vec![],
+            max_party_size: None,
+            reservations_above: None,
+            coordinates: None,
+            location_id: None,
         }
     }
 
@@ -38,13 +114,37 @@ impl Restaurant {
         Self {
             id: RestaurantId(id),
             name: RestaurantName::from_string_unchecked(name),
+            tags: vec![],
+            price_tier: None,
+            service_modes: vec![],
+            accommodations: vec![],
+            max_party_size: None,
+            reservations_above: None,
+            coordinates: None,
+            location_id: None,
         }
     }
 
-    pub fn as_view(&self) -> RestaurantView {
+    /// Whether it belongs to the restaurants of a location, all of them do when no
+    /// location is given.
+    pub fn is_available_at(&self, location_id: Option<i64>) -> bool {
+        location_id.is_none() || self.location_id.is_none() || self.location_id == location_id
+    }
+
+    /// Whether a party this size should call ahead.
+    pub fn needs_reservation(&self, party_size: usize) -> bool {
+        self.reservations_above
+            .is_some_and(|above| party_size > above as usize)
+    }
+
+    pub fn as_view(&self) -> RestaurantView<'_> {
         RestaurantView {
             id: self.id.0,
             name: &self.name.0,
+            tags: self.tags.iter().map(|t| t.as_str()).collect(),
+            price_tier: self.price_tier.map(|p| p.as_u8()),
+            service_modes: &self.service_modes,
+            accommodations: &self.accommodations,
         }
     }
 }
@@ -53,6 +153,10 @@ impl Restaurant {
 pub struct RestaurantView<'a> {
     id: i32,
     pub name: &'a str,
+    pub tags: Vec<&'a str>,
+    pub price_tier: Option<u8>,
+    pub service_modes: &'a [ServiceMode],
+    pub accommodations: &'a [DietaryRestriction],
 }
 
 #[derive(Error, Debug)]
@@ -112,9 +216,156 @@ impl TryFrom<String> for RestaurantName {
     }
 }
 
+#[derive(Error, Debug)]
+pub enum RestaurantTagValidationError {
+    #[error("Invalid tag: {:?}", tag)]
+    InvalidTag { tag: String },
+
+    #[error("No tag provided")]
+    EmptyTag,
+}
+
+/// A free-form label such as a cuisine (`thai`) or a quality (`vegan-friendly`, `quick`).
+/// Tags are stored lowercased so filtering is case-insensitive.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Hash)]
+pub struct RestaurantTag(String);
+
+impl RestaurantTag {
+    pub fn as_str(&self) -> &str {
+        &self.0
+    }
+
+    fn from_string_unchecked(tag: String) -> Self {
+        Self(tag)
+    }
+}
+
+impl TryFrom<String> for RestaurantTag {
+    type Error = RestaurantTagValidationError;
+
+    fn try_from(tag: String) -> Result<Self, Self::Error> {
+        let tr = tag.trim();
+        if tr.is_empty() {
+            return Err(RestaurantTagValidationError::EmptyTag);
+        }
+        if tr.contains(',') {
+            return Err(RestaurantTagValidationError::InvalidTag {
+                tag: tr.to_string(),
+            });
+        }
+        Ok(RestaurantTag(tr.to_lowercase()))
+    }
+}
+
+impl FromStr for RestaurantTag {
+    type Err = RestaurantTagValidationError;
+
+    fn from_str(tag: &str) -> Result<Self, Self::Err> {
+        tag.to_string().try_into()
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum PriceTierValidationError {
+    #[error("Price tier must be between 1 and 4, got {}", tier)]
+    OutOfRange { tier: i32 },
+}
+
+/// How expensive a restaurant is, from 1 (`$`) to 4 (`$$$$`).
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct PriceTier(u8);
+
+impl PriceTier {
+    pub fn as_u8(&self) -> u8 {
+        self.0
+    }
+
+    fn from_i32_unchecked(tier: i32) -> Self {
+        Self(tier as u8)
+    }
+}
+
+impl TryFrom<i32> for PriceTier {
+    type Error = PriceTierValidationError;
+
+    fn try_from(tier: i32) -> Result<Self, Self::Error> {
+        match tier {
+            1..=4 => Ok(PriceTier(tier as u8)),
+            _ => Err(PriceTierValidationError::OutOfRange { tier }),
+        }
+    }
+}
+
+impl TryFrom<u8> for PriceTier {
+    type Error = PriceTierValidationError;
+
+    fn try_from(tier: u8) -> Result<Self, Self::Error> {
+        i32::from(tier).try_into()
+    }
+}
+
+impl Display for PriceTier {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}", "$".repeat(self.0 as usize))
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum ServiceModeParseError {
+    #[error("Unknown service mode: {:?}", mode)]
+    UnknownMode { mode: String },
+}
+
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy, Hash)]
+pub enum ServiceMode {
+    DineIn,
+    Takeout,
+    Delivery,
+}
+
+impl ServiceMode {
+    pub const ALL: [ServiceMode; 3] = [
+        ServiceMode::DineIn,
+        ServiceMode::Takeout,
+        ServiceMode::Delivery,
+    ];
+
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            ServiceMode::DineIn => "dine-in",
+            ServiceMode::Takeout => "takeout",
+            ServiceMode::Delivery => "delivery",
+        }
+    }
+}
+
+impl Display for ServiceMode {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for ServiceMode {
+    type Err = ServiceModeParseError;
+
+    fn from_str(mode: &str) -> Result<Self, Self::Err> {
+        match mode.trim().to_lowercase().as_str() {
+            "dine-in" | "dinein" => Ok(ServiceMode::DineIn),
+            "takeout" | "take-out" => Ok(ServiceMode::Takeout),
+            "delivery" => Ok(ServiceMode::Delivery),
+            _ => Err(ServiceModeParseError::UnknownMode {
+                mode: mode.to_string(),
+            }),
+        }
+    }
+}
+
 #[cfg(test)]
 mod tests {
-    use super::{RestaurantName, RestaurantNameValidationError};
+    use super::{
+        PriceTier, RestaurantName, RestaurantNameValidationError, RestaurantTag,
+        RestaurantTagValidationError, ServiceMode,
+    };
 
     #[test]
     fn homie_name_validation_fails_on_empty_string() {
@@ -131,4 +382,30 @@ mod tests {
         let h: Result<RestaurantName, RestaurantNameValidationError> = "Bob".to_string().try_into();
         assert_eq!("Bob", h.unwrap().as_str());
     }
+
+    #[test]
+    fn tags_are_lowercased() {
+        let t: RestaurantTag = "  Vegan-Friendly ".parse().unwrap();
+        assert_eq!("vegan-friendly", t.as_str());
+    }
+
+    #[test]
+    fn tags_cannot_contain_commas() {
+        let t: Result<RestaurantTag, RestaurantTagValidationError> = "thai,quick".parse();
+        assert!(t.is_err());
+    }
+
+    #[test]
+    fn price_tier_must_be_between_one_and_four() {
+        assert!(PriceTier::try_from(0).is_err());
+        assert!(PriceTier::try_from(5).is_err());
+        assert_eq!("$$", PriceTier::try_from(2).unwrap().to_string());
+    }
+
+    #[test]
+    fn service_modes_round_trip() {
+        for mode in ServiceMode::ALL {
+            assert_eq!(mode, mode.as_str().parse().unwrap());
+        }
+    }
 }
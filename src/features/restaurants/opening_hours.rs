@@ -0,0 +1,358 @@
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+use chrono::Datelike;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use chrono::NaiveTime;
+use chrono::Weekday;
+use thiserror::Error;
+
+const TIME_FORMAT: &str = "%H:%M";
+const DAYS: [&str; 7] = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
+
+#[derive(Error, Debug, PartialEq, Eq)]
+pub enum OpeningHoursParseError {
+    #[error(
+        "Invalid opening hours rule {:?}, expected something like \"Mo-Fr 11:00-15:00\"",
+        rule
+    )]
+    InvalidRule { rule: String },
+
+    #[error("Invalid day {:?}, expected one of Mo, Tu, We, Th, Fr, Sa, Su", day)]
+    InvalidDay { day: String },
+
+    #[error(
+        "Invalid time range {:?}, expected something like \"11:00-15:00\"",
+        range
+    )]
+    InvalidTimeRange { range: String },
+}
+
+/// A span of time within a single day; `closes` is always after `opens`, so ranges
+/// past midnight like `18:00-01:00` are rejected.
+#[derive(Debug, PartialEq, Eq, PartialOrd, Ord, Clone, Copy)]
+pub struct TimeRange {
+    pub opens: NaiveTime,
+    pub closes: NaiveTime,
+}
+
+impl TimeRange {
+    pub fn contains(&self, time: NaiveTime) -> bool {
+        self.opens <= time && time < self.closes
+    }
+}
+
+impl Display for TimeRange {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "{}-{}",
+            self.opens.format(TIME_FORMAT),
+            self.closes.format(TIME_FORMAT)
+        )
+    }
+}
+
+impl FromStr for TimeRange {
+    type Err = OpeningHoursParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || OpeningHoursParseError::InvalidTimeRange {
+            range: s.to_string(),
+        };
+        let (opens, closes) = s.trim().split_once('-').ok_or_else(invalid)?;
+        let opens = NaiveTime::parse_from_str(opens.trim(), TIME_FORMAT).map_err(|_| invalid())?;
+        let closes =
+            NaiveTime::parse_from_str(closes.trim(), TIME_FORMAT).map_err(|_| invalid())?;
+        if closes <= opens {
+            return Err(invalid());
+        }
+        Ok(Self { opens, closes })
+    }
+}
+
+/// Hours on a day of the week, `None` meaning closed all day.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct WeeklyHours {
+    pub weekday: Weekday,
+    pub hours: Option<TimeRange>,
+}
+
+/// Hours for a specific date that replace the weekly schedule, `None` meaning closed all day.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub struct HoursException {
+    pub date: NaiveDate,
+    pub hours: Option<TimeRange>,
+}
+
+/// When a restaurant is open, parsed from rules like `Mo-Fr 11:00-15:00; Sa 12:00-14:00`.
+///
+/// Days can be listed (`Mo,We`) or given as ranges (`Mo-Fr`), several time ranges are
+/// separated by commas and `off` marks a closed day. A rule for a date such as
+/// `2026-12-25 off` is a holiday exception. Later rules override earlier ones for the
+/// same day. Late nights have to be split at midnight, e.g. `Fr 18:00-23:59; Sa
+/// 00:00-01:00`. A restaurant without any hours, not even closed days, is assumed to
+/// always be open.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct OpeningHours {
+    pub weekly: Vec<WeeklyHours>,
+    pub exceptions: Vec<HoursException>,
+}
+
+impl OpeningHours {
+    pub fn is_empty(&self) -> bool {
+        self.weekly.is_empty() && self.exceptions.is_empty()
+    }
+
+    pub fn is_open_at(&self, at: NaiveDateTime) -> bool {
+        let exceptions = self
+            .exceptions
+            .iter()
+            .filter(|e| e.date == at.date())
+            .collect::<Vec<_>>();
+        if !exceptions.is_empty() {
+            return exceptions
+                .iter()
+                .any(|e| e.hours.is_some_and(|h| h.contains(at.time())));
+        }
+        self.weekly.is_empty()
+            || self.weekly.iter().any(|w| {
+                w.weekday == at.weekday() && w.hours.is_some_and(|h| h.contains(at.time()))
+            })
+    }
+
+    /// `None` when nothing is known about the day, empty when it's closed.
+    fn weekly_hours_on(&self, weekday: Weekday) -> Option<Vec<TimeRange>> {
+        let on_day = self
+            .weekly
+            .iter()
+            .filter(|w| w.weekday == weekday)
+            .collect::<Vec<_>>();
+        if on_day.is_empty() {
+            return None;
+        }
+        let mut hours = on_day.iter().filter_map(|w| w.hours).collect::<Vec<_>>();
+        hours.sort();
+        Some(hours)
+    }
+}
+
+fn parse_day(day: &str) -> Result<Weekday, OpeningHoursParseError> {
+    DAYS.iter()
+        .position(|d| d.eq_ignore_ascii_case(day.trim()))
+        .map(|index| Weekday::try_from(index as u8).expect("index of a day is a weekday"))
+        .ok_or_else(|| OpeningHoursParseError::InvalidDay {
+            day: day.to_string(),
+        })
+}
+
+fn parse_days(days: &str) -> Result<Vec<Weekday>, OpeningHoursParseError> {
+    let mut parsed = Vec::new();
+    for part in days.split(',') {
+        match part.split_once('-') {
+            Some((from, to)) => {
+                let (mut day, to) = (parse_day(from)?, parse_day(to)?);
+                parsed.push(day);
+                while day != to {
+                    day = day.succ();
+                    parsed.push(day);
+                }
+            }
+            None => parsed.push(parse_day(part)?),
+        }
+    }
+    Ok(parsed)
+}
+
+fn parse_ranges(ranges: &str) -> Result<Vec<TimeRange>, OpeningHoursParseError> {
+    match ranges.trim() {
+        "off" | "closed" => Ok(Vec::new()),
+        ranges => ranges.split(',').map(|r| r.parse()).collect(),
+    }
+}
+
+impl FromStr for OpeningHours {
+    type Err = OpeningHoursParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let mut opening_hours = OpeningHours::default();
+        for rule in s.split(';').map(str::trim).filter(|r| !r.is_empty()) {
+            let (selector, ranges) =
+                rule.split_once(' ')
+                    .ok_or_else(|| OpeningHoursParseError::InvalidRule {
+                        rule: rule.to_string(),
+                    })?;
+            let ranges = parse_ranges(ranges)?;
+
+            if let Ok(date) = NaiveDate::parse_from_str(selector, "%Y-%m-%d") {
+                opening_hours.exceptions.retain(|e| e.date != date);
+                match ranges.is_empty() {
+                    true => opening_hours
+                        .exceptions
+                        .push(HoursException { date, hours: None }),
+                    false => opening_hours.exceptions.extend(ranges.iter().map(|&hours| {
+                        HoursException {
+                            date,
+                            hours: Some(hours),
+                        }
+                    })),
+                }
+                continue;
+            }
+
+            let days = parse_days(selector).map_err(|_| OpeningHoursParseError::InvalidRule {
+                rule: rule.to_string(),
+            })?;
+            opening_hours.weekly.retain(|w| !days.contains(&w.weekday));
+            for weekday in days {
+                match ranges.is_empty() {
+                    true => opening_hours.weekly.push(WeeklyHours {
+                        weekday,
+                        hours: None,
+                    }),
+                    false => opening_hours
+                        .weekly
+                        .extend(ranges.iter().map(|&hours| WeeklyHours {
+                            weekday,
+                            hours: Some(hours),
+                        })),
+                }
+            }
+        }
+        opening_hours
+            .weekly
+            .sort_by_key(|w| (w.weekday.num_days_from_monday(), w.hours));
+        opening_hours.exceptions.sort_by_key(|e| (e.date, e.hours));
+        Ok(opening_hours)
+    }
+}
+
+fn format_ranges(ranges: &[TimeRange]) -> String {
+    ranges
+        .iter()
+        .map(|r| r.to_string())
+        .collect::<Vec<String>>()
+        .join(",")
+}
+
+impl Display for OpeningHours {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let mut rules = Vec::new();
+
+        // consecutive days with the same hours are collapsed into a range like Mo-Fr
+        let mut day = 0;
+        while day < DAYS.len() {
+            let weekday = Weekday::try_from(day as u8).expect("index of a day is a weekday");
+            let hours = self.weekly_hours_on(weekday);
+            let mut last = day;
+            while last + 1 < DAYS.len()
+                && self.weekly_hours_on(
+                    Weekday::try_from((last + 1) as u8).expect("index of a day is a weekday"),
+                ) == hours
+            {
+                last += 1;
+            }
+            if let Some(hours) = hours {
+                let hours = match hours.is_empty() {
+                    true => "off".to_string(),
+                    false => format_ranges(&hours),
+                };
+                match last == day {
+                    true => rules.push(format!("{} {}", DAYS[day], hours)),
+                    false => rules.push(format!("{}-{} {}", DAYS[day], DAYS[last], hours)),
+                }
+            }
+            day = last + 1;
+        }
+
+        let mut dates = self.exceptions.iter().map(|e| e.date).collect::<Vec<_>>();
+        dates.dedup();
+        for date in dates {
+            let hours = self
+                .exceptions
+                .iter()
+                .filter(|e| e.date == date)
+                .filter_map(|e| e.hours)
+                .collect::<Vec<_>>();
+            match hours.is_empty() {
+                true => rules.push(format!("{} off", date)),
+                false => rules.push(format!("{} {}", date, format_ranges(&hours))),
+            }
+        }
+
+        write!(f, "{}", rules.join("; "))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+
+    use super::OpeningHours;
+
+    fn at(date: &str, time: &str) -> chrono::NaiveDateTime {
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .unwrap()
+            .and_time(chrono::NaiveTime::parse_from_str(time, "%H:%M").unwrap())
+    }
+
+    #[test]
+    fn parses_and_formats_compact_rules() {
+        let hours: OpeningHours = "Mo-Fr 11:00-15:00; Sa 12:00-14:00,18:00-22:00; 2026-12-25 off"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            "Mo-Fr 11:00-15:00; Sa 12:00-14:00,18:00-22:00; 2026-12-25 off",
+            hours.to_string()
+        );
+    }
+
+    #[test]
+    fn later_rules_override_earlier_days() {
+        let hours: OpeningHours = "Mo-Fr 11:00-15:00; We off".parse().unwrap();
+
+        assert_eq!(
+            "Mo-Tu 11:00-15:00; We off; Th-Fr 11:00-15:00",
+            hours.to_string()
+        );
+    }
+
+    #[test]
+    fn only_closed_days_are_still_known_hours() {
+        // 2026-10-19 is a monday
+        let hours: OpeningHours = "Mo off".parse().unwrap();
+
+        assert_eq!("Mo off", hours.to_string());
+        assert!(!hours.is_open_at(at("2026-10-19", "12:00")));
+        assert!(!hours.is_open_at(at("2026-10-20", "12:00")));
+    }
+
+    #[test]
+    fn closed_on_mondays_and_holidays() {
+        // 2026-10-19 is a monday
+        let hours: OpeningHours = "Tu-Su 11:00-15:00; 2026-10-21 off".parse().unwrap();
+
+        assert!(!hours.is_open_at(at("2026-10-19", "12:00")));
+        assert!(hours.is_open_at(at("2026-10-20", "12:00")));
+        assert!(!hours.is_open_at(at("2026-10-20", "15:00")));
+        assert!(!hours.is_open_at(at("2026-10-21", "12:00")));
+    }
+
+    #[test]
+    fn invalid_rules_are_rejected() {
+        assert!("Mo-Fr".parse::<OpeningHours>().is_err());
+        assert!("Xy 11:00-15:00".parse::<OpeningHours>().is_err());
+        assert!("Mo 15:00-11:00".parse::<OpeningHours>().is_err());
+    }
+
+    #[test]
+    fn overnight_ranges_are_rejected() {
+        assert!("Fr 18:00-01:00".parse::<OpeningHours>().is_err());
+        assert!("Fr 18:00-23:59; Sa 00:00-01:00"
+            .parse::<OpeningHours>()
+            .is_ok());
+    }
+}
@@ -0,0 +1,239 @@
+use std::fmt::Debug;
+
+use chrono::NaiveDate;
+use chrono::NaiveTime;
+use chrono::Weekday;
+use sqlx::FromRow;
+use sqlx::Pool;
+
+use sqlx::Sqlite;
+use sqlx::SqliteConnection;
+use thiserror::Error;
+use tracing::Instrument;
+
+use crate::user::UserId;
+
+use super::HoursException;
+use super::OpeningHours;
+use super::RestaurantName;
+use super::RestaurantNameValidationError;
+use super::TimeRange;
+use super::WeeklyHours;
+
+const TIME_FORMAT: &str = "%H:%M";
+
+/// Replaces a restaurant's weekly schedule and holiday exceptions.
+#[tracing::instrument(skip(db))]
+pub async fn set_restaurant_opening_hours(
+    restaurant_name: impl TryInto<RestaurantName, Error = RestaurantNameValidationError> + Debug,
+    opening_hours: &OpeningHours,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl RestaurantOpeningHours,
+) -> Result<(), RestaurantOpeningHoursError> {
+    let params = RestaurantOpeningHoursParams::new(user_id.into(), restaurant_name.try_into()?);
+
+    db.set_restaurant_opening_hours(&params, opening_hours)
+        .await
+        .map_err(|e| params.map_db_error(e))?;
+
+    Ok(())
+}
+
+#[tracing::instrument(skip(db))]
+pub async fn get_restaurant_opening_hours(
+    restaurant_name: impl TryInto<RestaurantName, Error = RestaurantNameValidationError> + Debug,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl RestaurantOpeningHours,
+) -> Result<OpeningHours, RestaurantOpeningHoursError> {
+    let params = RestaurantOpeningHoursParams::new(user_id.into(), restaurant_name.try_into()?);
+
+    db.get_restaurant_opening_hours(&params)
+        .await
+        .map_err(|e| params.map_db_error(e))
+}
+
+#[derive(Debug)]
+pub struct RestaurantOpeningHoursParams {
+    user_id: UserId,
+    restaurant_name: RestaurantName,
+}
+
+impl RestaurantOpeningHoursParams {
+    fn new(user_id: UserId, restaurant_name: RestaurantName) -> Self {
+        Self {
+            user_id,
+            restaurant_name,
+        }
+    }
+
+    fn map_db_error(&self, e: sqlx::Error) -> RestaurantOpeningHoursError {
+        match e {
+            sqlx::Error::RowNotFound => RestaurantOpeningHoursError::RestaurantNotFound {
+                name: self.restaurant_name.as_str().to_string(),
+            },
+            _ => RestaurantOpeningHoursError::UnknownDbError(e),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum RestaurantOpeningHoursError {
+    #[error(transparent)]
+    RestaurantNameValidationError(#[from] RestaurantNameValidationError),
+
+    #[error("Restaurant not found: {:?}", name)]
+    RestaurantNotFound { name: String },
+
+    #[error("Unknown db error")]
+    UnknownDbError(#[from] sqlx::Error),
+}
+
+#[derive(Debug, FromRow)]
+struct WeeklyHoursRow {
+    weekday: i32,
+    opens: Option<String>,
+    closes: Option<String>,
+}
+
+#[derive(Debug, FromRow)]
+struct HoursExceptionRow {
+    date: NaiveDate,
+    opens: Option<String>,
+    closes: Option<String>,
+}
+
+fn parse_time_range(opens: &str, closes: &str) -> Option<TimeRange> {
+    Some(TimeRange {
+        opens: NaiveTime::parse_from_str(opens, TIME_FORMAT).ok()?,
+        closes: NaiveTime::parse_from_str(closes, TIME_FORMAT).ok()?,
+    })
+}
+
+pub trait RestaurantOpeningHours {
+    async fn set_restaurant_opening_hours(
+        &self,
+        params: &RestaurantOpeningHoursParams,
+        opening_hours: &OpeningHours,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn get_restaurant_opening_hours(
+        &self,
+        params: &RestaurantOpeningHoursParams,
+    ) -> Result<OpeningHours, sqlx::Error>;
+}
+
+async fn get_restaurant_id(
+    conn: &mut SqliteConnection,
+    params: &RestaurantOpeningHoursParams,
+) -> Result<i32, sqlx::Error> {
+    sqlx::query_scalar(r#"select id from restaurants where user_id = ? and name = ?"#)
+        .bind(params.user_id.as_i32())
+        .bind(params.restaurant_name.as_str())
+        .fetch_one(conn)
+        .await
+}
+
+impl RestaurantOpeningHours for Pool<Sqlite> {
+    #[tracing::instrument(skip(self))]
+    async fn set_restaurant_opening_hours(
+        &self,
+        params: &RestaurantOpeningHoursParams,
+        opening_hours: &OpeningHours,
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let restaurant_id = get_restaurant_id(&mut tx, params).await?;
+        sqlx::query(
+            r#"delete from restaurant_opening_hours where restaurant_id = ? and user_id = ?"#,
+        )
+        .bind(restaurant_id)
+        .bind(params.user_id.as_i32())
+        .execute(&mut *tx)
+        .await?;
+        sqlx::query(
+            r#"delete from restaurant_hours_exceptions where restaurant_id = ? and user_id = ?"#,
+        )
+        .bind(restaurant_id)
+        .bind(params.user_id.as_i32())
+        .execute(&mut *tx)
+        .await?;
+        for weekly in &opening_hours.weekly {
+            sqlx::query(
+                r#"insert into restaurant_opening_hours (restaurant_id, user_id, weekday, opens, closes) values (?, ?, ?, ?, ?)"#,
+            )
+            .bind(restaurant_id)
+            .bind(params.user_id.as_i32())
+            .bind(weekly.weekday.num_days_from_monday())
+            .bind(weekly.hours.map(|h| h.opens.format(TIME_FORMAT).to_string()))
+            .bind(weekly.hours.map(|h| h.closes.format(TIME_FORMAT).to_string()))
+            .execute(&mut *tx)
+            .await?;
+        }
+        for exception in &opening_hours.exceptions {
+            sqlx::query(
+                r#"insert into restaurant_hours_exceptions (restaurant_id, user_id, date, opens, closes) values (?, ?, ?, ?, ?)"#,
+            )
+            .bind(restaurant_id)
+            .bind(params.user_id.as_i32())
+            .bind(exception.date)
+            .bind(exception.hours.map(|h| h.opens.format(TIME_FORMAT).to_string()))
+            .bind(exception.hours.map(|h| h.closes.format(TIME_FORMAT).to_string()))
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit()
+            .instrument(tracing::info_span!(
+                "Replacing restaurant opening hours db query"
+            ))
+            .await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_restaurant_opening_hours(
+        &self,
+        params: &RestaurantOpeningHoursParams,
+    ) -> Result<OpeningHours, sqlx::Error> {
+        let mut conn = self.acquire().await?;
+        let restaurant_id = get_restaurant_id(&mut conn, params).await?;
+        let weekly: Vec<WeeklyHoursRow> = sqlx::query_as(
+            r#"select weekday, opens, closes from restaurant_opening_hours where restaurant_id = ? order by weekday, opens"#,
+        )
+        .bind(restaurant_id)
+        .fetch_all(&mut *conn)
+        .instrument(tracing::info_span!("Getting restaurant opening hours db query"))
+        .await?;
+        let exceptions: Vec<HoursExceptionRow> = sqlx::query_as(
+            r#"select date, opens, closes from restaurant_hours_exceptions where restaurant_id = ? order by date, opens"#,
+        )
+        .bind(restaurant_id)
+        .fetch_all(&mut *conn)
+        .instrument(tracing::info_span!(
+            "Getting restaurant hours exceptions db query"
+        ))
+        .await?;
+
+        Ok(OpeningHours {
+            weekly: weekly
+                .into_iter()
+                .filter_map(|row| {
+                    Some(WeeklyHours {
+                        weekday: Weekday::try_from(u8::try_from(row.weekday).ok()?).ok()?,
+                        hours: row
+                            .opens
+                            .zip(row.closes)
+                            .and_then(|(opens, closes)| parse_time_range(&opens, &closes)),
+                    })
+                })
+                .collect(),
+            exceptions: exceptions
+                .into_iter()
+                .map(|row| HoursException {
+                    date: row.date,
+                    hours: row
+                        .opens
+                        .zip(row.closes)
+                        .and_then(|(opens, closes)| parse_time_range(&opens, &closes)),
+                })
+                .collect(),
+        })
+    }
+}
@@ -0,0 +1,654 @@
+use std::fmt::Debug;
+
+use sqlx::Pool;
+
+use sqlx::Sqlite;
+use sqlx::SqliteConnection;
+use thiserror::Error;
+use tracing::Instrument;
+
+use crate::features::Coordinates;
+use crate::features::DietaryRestriction;
+use crate::user::UserId;
+
+use super::PriceTier;
+use super::RestaurantName;
+use super::RestaurantNameValidationError;
+use super::RestaurantTag;
+use super::RestaurantTagValidationError;
+use super::ServiceMode;
+
+#[tracing::instrument(skip(db))]
+pub async fn add_restaurant_tag(
+    restaurant_name: impl TryInto<RestaurantName, Error = RestaurantNameValidationError> + Debug,
+    tag: impl TryInto<RestaurantTag, Error = RestaurantTagValidationError> + Debug,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl UpdateRestaurantAttributes,
+) -> Result<(), UpdateRestaurantAttributesError> {
+    let params = UpdateRestaurantAttributesParams::new(user_id.into(), restaurant_name.try_into()?);
+    let tag = tag.try_into()?;
+
+    db.add_restaurant_tag(&params, &tag)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db_error) if db_error.is_unique_violation() => {
+                UpdateRestaurantAttributesError::RestaurantAlreadyHasTag {
+                    name: params.restaurant_name.as_str().to_string(),
+                    tag: tag.as_str().to_string(),
+                }
+            }
+            e => params.map_db_error(e),
+        })?;
+
+    Ok(())
+}
+
+#[tracing::instrument(skip(db))]
+pub async fn remove_restaurant_tag(
+    restaurant_name: impl TryInto<RestaurantName, Error = RestaurantNameValidationError> + Debug,
+    tag: impl TryInto<RestaurantTag, Error = RestaurantTagValidationError> + Debug,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl UpdateRestaurantAttributes,
+) -> Result<(), UpdateRestaurantAttributesError> {
+    let params = UpdateRestaurantAttributesParams::new(user_id.into(), restaurant_name.try_into()?);
+    let tag = tag.try_into()?;
+
+    let removed = db
+        .remove_restaurant_tag(&params, &tag)
+        .await
+        .map_err(|e| params.map_db_error(e))?;
+
+    match removed {
+        true => Ok(()),
+        false => Err(UpdateRestaurantAttributesError::NoTagRemoved),
+    }
+}
+
+#[tracing::instrument(skip(db))]
+pub async fn set_restaurant_tags(
+    restaurant_name: impl TryInto<RestaurantName, Error = RestaurantNameValidationError> + Debug,
+    tags: &[RestaurantTag],
+    user_id: impl Into<UserId> + Debug,
+    db: &impl UpdateRestaurantAttributes,
+) -> Result<(), UpdateRestaurantAttributesError> {
+    let params = UpdateRestaurantAttributesParams::new(user_id.into(), restaurant_name.try_into()?);
+
+    db.set_restaurant_tags(&params, tags)
+        .await
+        .map_err(|e| params.map_db_error(e))?;
+
+    Ok(())
+}
+
+#[tracing::instrument(skip(db))]
+pub async fn set_restaurant_price_tier(
+    restaurant_name: impl TryInto<RestaurantName, Error = RestaurantNameValidationError> + Debug,
+    price_tier: Option<PriceTier>,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl UpdateRestaurantAttributes,
+) -> Result<(), UpdateRestaurantAttributesError> {
+    let params = UpdateRestaurantAttributesParams::new(user_id.into(), restaurant_name.try_into()?);
+
+    db.set_restaurant_price_tier(&params, price_tier)
+        .await
+        .map_err(|e| params.map_db_error(e))?;
+
+    Ok(())
+}
+
+/// Sets or clears where a restaurant is.
+#[tracing::instrument(skip(db))]
+pub async fn set_restaurant_location(
+    restaurant_name: impl TryInto<RestaurantName, Error = RestaurantNameValidationError> + Debug,
+    coordinates: Option<Coordinates>,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl UpdateRestaurantAttributes,
+) -> Result<(), UpdateRestaurantAttributesError> {
+    let params = UpdateRestaurantAttributesParams::new(user_id.into(), restaurant_name.try_into()?);
+
+    db.set_restaurant_location(&params, coordinates)
+        .await
+        .map_err(|e| params.map_db_error(e))?;
+
+    Ok(())
+}
+
+/// Ties a restaurant to the [location](crate::features::UserLocation) it's picked
+/// from, or to every location with `None`.
+#[tracing::instrument(skip(db))]
+pub async fn set_restaurant_scope(
+    restaurant_name: impl TryInto<RestaurantName, Error = RestaurantNameValidationError> + Debug,
+    location_id: Option<i64>,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl UpdateRestaurantAttributes,
+) -> Result<(), UpdateRestaurantAttributesError> {
+    let params = UpdateRestaurantAttributesParams::new(user_id.into(), restaurant_name.try_into()?);
+
+    db.set_restaurant_scope(&params, location_id)
+        .await
+        .map_err(|e| params.map_db_error(e))?;
+
+    Ok(())
+}
+
+/// Sets how many people a restaurant seats and above how many to call ahead, `None`
+/// meaning unknown.
+#[tracing::instrument(skip(db))]
+pub async fn set_restaurant_party_size(
+    restaurant_name: impl TryInto<RestaurantName, Error = RestaurantNameValidationError> + Debug,
+    max_party_size: Option<u32>,
+    reservations_above: Option<u32>,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl UpdateRestaurantAttributes,
+) -> Result<(), UpdateRestaurantAttributesError> {
+    let params = UpdateRestaurantAttributesParams::new(user_id.into(), restaurant_name.try_into()?);
+    if max_party_size == Some(0) || reservations_above == Some(0) {
+        return Err(UpdateRestaurantAttributesError::InvalidPartySize);
+    }
+
+    db.set_restaurant_party_size(&params, max_party_size, reservations_above)
+        .await
+        .map_err(|e| params.map_db_error(e))?;
+
+    Ok(())
+}
+
+#[tracing::instrument(skip(db))]
+pub async fn set_restaurant_service_modes(
+    restaurant_name: impl TryInto<RestaurantName, Error = RestaurantNameValidationError> + Debug,
+    service_modes: &[ServiceMode],
+    user_id: impl Into<UserId> + Debug,
+    db: &impl UpdateRestaurantAttributes,
+) -> Result<(), UpdateRestaurantAttributesError> {
+    let params = UpdateRestaurantAttributesParams::new(user_id.into(), restaurant_name.try_into()?);
+
+    db.set_restaurant_service_modes(&params, service_modes)
+        .await
+        .map_err(|e| params.map_db_error(e))?;
+
+    Ok(())
+}
+
+#[tracing::instrument(skip(db))]
+pub async fn add_restaurant_accommodation(
+    restaurant_name: impl TryInto<RestaurantName, Error = RestaurantNameValidationError> + Debug,
+    restriction: DietaryRestriction,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl UpdateRestaurantAttributes,
+) -> Result<(), UpdateRestaurantAttributesError> {
+    let params = UpdateRestaurantAttributesParams::new(user_id.into(), restaurant_name.try_into()?);
+
+    db.add_restaurant_accommodation(&params, restriction)
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::Database(db_error) if db_error.is_unique_violation() => {
+                UpdateRestaurantAttributesError::RestaurantAlreadyAccommodates {
+                    name: params.restaurant_name.as_str().to_string(),
+                    restriction,
+                }
+            }
+            e => params.map_db_error(e),
+        })?;
+
+    Ok(())
+}
+
+#[tracing::instrument(skip(db))]
+pub async fn remove_restaurant_accommodation(
+    restaurant_name: impl TryInto<RestaurantName, Error = RestaurantNameValidationError> + Debug,
+    restriction: DietaryRestriction,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl UpdateRestaurantAttributes,
+) -> Result<(), UpdateRestaurantAttributesError> {
+    let params = UpdateRestaurantAttributesParams::new(user_id.into(), restaurant_name.try_into()?);
+
+    let removed = db
+        .remove_restaurant_accommodation(&params, restriction)
+        .await
+        .map_err(|e| params.map_db_error(e))?;
+
+    match removed {
+        true => Ok(()),
+        false => Err(UpdateRestaurantAttributesError::NoAccommodationRemoved),
+    }
+}
+
+#[tracing::instrument(skip(db))]
+pub async fn set_restaurant_accommodations(
+    restaurant_name: impl TryInto<RestaurantName, Error = RestaurantNameValidationError> + Debug,
+    restrictions: &[DietaryRestriction],
+    user_id: impl Into<UserId> + Debug,
+    db: &impl UpdateRestaurantAttributes,
+) -> Result<(), UpdateRestaurantAttributesError> {
+    let params = UpdateRestaurantAttributesParams::new(user_id.into(), restaurant_name.try_into()?);
+
+    db.set_restaurant_accommodations(&params, restrictions)
+        .await
+        .map_err(|e| params.map_db_error(e))?;
+
+    Ok(())
+}
+
+#[derive(Debug)]
+pub struct UpdateRestaurantAttributesParams {
+    user_id: UserId,
+    restaurant_name: RestaurantName,
+}
+
+impl UpdateRestaurantAttributesParams {
+    fn new(user_id: UserId, restaurant_name: RestaurantName) -> Self {
+        Self {
+            user_id,
+            restaurant_name,
+        }
+    }
+
+    fn map_db_error(&self, e: sqlx::Error) -> UpdateRestaurantAttributesError {
+        match e {
+            sqlx::Error::RowNotFound => UpdateRestaurantAttributesError::RestaurantNotFound {
+                name: self.restaurant_name.as_str().to_string(),
+            },
+            _ => UpdateRestaurantAttributesError::UnknownDbError(e),
+        }
+    }
+}
+
+#[derive(Error, Debug)]
+pub enum UpdateRestaurantAttributesError {
+    #[error(transparent)]
+    RestaurantNameValidationError(#[from] RestaurantNameValidationError),
+
+    #[error(transparent)]
+    RestaurantTagValidationError(#[from] RestaurantTagValidationError),
+
+    #[error("Restaurant not found: {:?}", name)]
+    RestaurantNotFound { name: String },
+
+    #[error("{:?} is already tagged {:?}", name, tag)]
+    RestaurantAlreadyHasTag { name: String, tag: String },
+
+    #[error("No tag removed")]
+    NoTagRemoved,
+
+    #[error("{:?} already accommodates {}", name, restriction)]
+    RestaurantAlreadyAccommodates {
+        name: String,
+        restriction: DietaryRestriction,
+    },
+
+    #[error("No accommodation removed")]
+    NoAccommodationRemoved,
+
+    #[error("A party size has to be at least 1")]
+    InvalidPartySize,
+
+    #[error("Unknown db error")]
+    UnknownDbError(#[from] sqlx::Error),
+}
+
+pub trait UpdateRestaurantAttributes {
+    async fn add_restaurant_tag(
+        &self,
+        params: &UpdateRestaurantAttributesParams,
+        tag: &RestaurantTag,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn remove_restaurant_tag(
+        &self,
+        params: &UpdateRestaurantAttributesParams,
+        tag: &RestaurantTag,
+    ) -> Result<bool, sqlx::Error>;
+
+    async fn set_restaurant_tags(
+        &self,
+        params: &UpdateRestaurantAttributesParams,
+        tags: &[RestaurantTag],
+    ) -> Result<(), sqlx::Error>;
+
+    async fn set_restaurant_price_tier(
+        &self,
+        params: &UpdateRestaurantAttributesParams,
+        price_tier: Option<PriceTier>,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn set_restaurant_party_size(
+        &self,
+        params: &UpdateRestaurantAttributesParams,
+        max_party_size: Option<u32>,
+        reservations_above: Option<u32>,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn set_restaurant_location(
+        &self,
+        params: &UpdateRestaurantAttributesParams,
+        coordinates: Option<Coordinates>,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn set_restaurant_scope(
+        &self,
+        params: &UpdateRestaurantAttributesParams,
+        location_id: Option<i64>,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn set_restaurant_service_modes(
+        &self,
+        params: &UpdateRestaurantAttributesParams,
+        service_modes: &[ServiceMode],
+    ) -> Result<(), sqlx::Error>;
+
+    async fn add_restaurant_accommodation(
+        &self,
+        params: &UpdateRestaurantAttributesParams,
+        restriction: DietaryRestriction,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn remove_restaurant_accommodation(
+        &self,
+        params: &UpdateRestaurantAttributesParams,
+        restriction: DietaryRestriction,
+    ) -> Result<bool, sqlx::Error>;
+
+    async fn set_restaurant_accommodations(
+        &self,
+        params: &UpdateRestaurantAttributesParams,
+        restrictions: &[DietaryRestriction],
+    ) -> Result<(), sqlx::Error>;
+}
+
+async fn get_restaurant_id(
+    conn: &mut SqliteConnection,
+    params: &UpdateRestaurantAttributesParams,
+) -> Result<i32, sqlx::Error> {
+    sqlx::query_scalar(r#"select id from restaurants where user_id = ? and name = ?"#)
+        .bind(params.user_id.as_i32())
+        .bind(params.restaurant_name.as_str())
+        .fetch_one(conn)
+        .await
+}
+
+impl UpdateRestaurantAttributes for Pool<Sqlite> {
+    #[tracing::instrument(skip(self))]
+    async fn add_restaurant_tag(
+        &self,
+        params: &UpdateRestaurantAttributesParams,
+        tag: &RestaurantTag,
+    ) -> Result<(), sqlx::Error> {
+        let mut conn = self.acquire().await?;
+        let restaurant_id = get_restaurant_id(&mut conn, params).await?;
+        sqlx::query(
+            r#"insert into restaurant_tags (restaurant_id, user_id, tag) values (?, ?, ?)"#,
+        )
+        .bind(restaurant_id)
+        .bind(params.user_id.as_i32())
+        .bind(tag.as_str())
+        .execute(&mut *conn)
+        .instrument(tracing::info_span!("Adding tag to restaurant db query"))
+        .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn remove_restaurant_tag(
+        &self,
+        params: &UpdateRestaurantAttributesParams,
+        tag: &RestaurantTag,
+    ) -> Result<bool, sqlx::Error> {
+        let mut conn = self.acquire().await?;
+        let restaurant_id = get_restaurant_id(&mut conn, params).await?;
+        let result = sqlx::query(
+            r#"delete from restaurant_tags where restaurant_id = ? and user_id = ? and tag = ?"#,
+        )
+        .bind(restaurant_id)
+        .bind(params.user_id.as_i32())
+        .bind(tag.as_str())
+        .execute(&mut *conn)
+        .instrument(tracing::info_span!("Removing tag from restaurant db query"))
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn set_restaurant_tags(
+        &self,
+        params: &UpdateRestaurantAttributesParams,
+        tags: &[RestaurantTag],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let restaurant_id = get_restaurant_id(&mut tx, params).await?;
+        sqlx::query(r#"delete from restaurant_tags where restaurant_id = ? and user_id = ?"#)
+            .bind(restaurant_id)
+            .bind(params.user_id.as_i32())
+            .execute(&mut *tx)
+            .await?;
+        for tag in tags {
+            sqlx::query(
+                r#"insert or ignore into restaurant_tags (restaurant_id, user_id, tag) values (?, ?, ?)"#,
+            )
+            .bind(restaurant_id)
+            .bind(params.user_id.as_i32())
+            .bind(tag.as_str())
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit()
+            .instrument(tracing::info_span!("Replacing restaurant tags db query"))
+            .await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn set_restaurant_price_tier(
+        &self,
+        params: &UpdateRestaurantAttributesParams,
+        price_tier: Option<PriceTier>,
+    ) -> Result<(), sqlx::Error> {
+        let result = sqlx::query(
+            r#"update restaurants set price_tier = ?, updated_at = current_timestamp where user_id = ? and name = ?"#,
+        )
+        .bind(price_tier.map(|p| p.as_u8()))
+        .bind(params.user_id.as_i32())
+        .bind(params.restaurant_name.as_str())
+        .execute(self)
+        .instrument(tracing::info_span!("Setting restaurant price tier db query"))
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn set_restaurant_party_size(
+        &self,
+        params: &UpdateRestaurantAttributesParams,
+        max_party_size: Option<u32>,
+        reservations_above: Option<u32>,
+    ) -> Result<(), sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+update restaurants
+set max_party_size     = ?,
+    reservations_above = ?,
+    updated_at         = current_timestamp
+where user_id = ?
+  and name = ?
+            "#,
+        )
+        .bind(max_party_size)
+        .bind(reservations_above)
+        .bind(params.user_id.as_i32())
+        .bind(params.restaurant_name.as_str())
+        .execute(self)
+        .instrument(tracing::info_span!(
+            "Setting restaurant party size db query"
+        ))
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn set_restaurant_location(
+        &self,
+        params: &UpdateRestaurantAttributesParams,
+        coordinates: Option<Coordinates>,
+    ) -> Result<(), sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+update restaurants
+set latitude   = ?,
+    longitude  = ?,
+    updated_at = current_timestamp
+where user_id = ?
+  and name = ?
+            "#,
+        )
+        .bind(coordinates.map(|c| c.latitude()))
+        .bind(coordinates.map(|c| c.longitude()))
+        .bind(params.user_id.as_i32())
+        .bind(params.restaurant_name.as_str())
+        .execute(self)
+        .instrument(tracing::info_span!("Setting restaurant location db query"))
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn set_restaurant_scope(
+        &self,
+        params: &UpdateRestaurantAttributesParams,
+        location_id: Option<i64>,
+    ) -> Result<(), sqlx::Error> {
+        let result = sqlx::query(
+            r#"
+update restaurants
+set location_id = ?,
+    updated_at  = current_timestamp
+where user_id = ?
+  and name = ?
+            "#,
+        )
+        .bind(location_id)
+        .bind(params.user_id.as_i32())
+        .bind(params.restaurant_name.as_str())
+        .execute(self)
+        .instrument(tracing::info_span!("Setting restaurant scope db query"))
+        .await?;
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn set_restaurant_service_modes(
+        &self,
+        params: &UpdateRestaurantAttributesParams,
+        service_modes: &[ServiceMode],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let restaurant_id = get_restaurant_id(&mut tx, params).await?;
+        sqlx::query(
+            r#"delete from restaurant_service_modes where restaurant_id = ? and user_id = ?"#,
+        )
+        .bind(restaurant_id)
+        .bind(params.user_id.as_i32())
+        .execute(&mut *tx)
+        .await?;
+        for service_mode in service_modes {
+            sqlx::query(
+                r#"insert or ignore into restaurant_service_modes (restaurant_id, user_id, service_mode) values (?, ?, ?)"#,
+            )
+            .bind(restaurant_id)
+            .bind(params.user_id.as_i32())
+            .bind(service_mode.as_str())
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit()
+            .instrument(tracing::info_span!(
+                "Replacing restaurant service modes db query"
+            ))
+            .await
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn add_restaurant_accommodation(
+        &self,
+        params: &UpdateRestaurantAttributesParams,
+        restriction: DietaryRestriction,
+    ) -> Result<(), sqlx::Error> {
+        let mut conn = self.acquire().await?;
+        let restaurant_id = get_restaurant_id(&mut conn, params).await?;
+        sqlx::query(
+            r#"insert into restaurant_dietary_accommodations (restaurant_id, user_id, restriction) values (?, ?, ?)"#,
+        )
+        .bind(restaurant_id)
+        .bind(params.user_id.as_i32())
+        .bind(restriction.as_str())
+        .execute(&mut *conn)
+        .instrument(tracing::info_span!(
+            "Adding dietary accommodation to restaurant db query"
+        ))
+        .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn remove_restaurant_accommodation(
+        &self,
+        params: &UpdateRestaurantAttributesParams,
+        restriction: DietaryRestriction,
+    ) -> Result<bool, sqlx::Error> {
+        let mut conn = self.acquire().await?;
+        let restaurant_id = get_restaurant_id(&mut conn, params).await?;
+        let result = sqlx::query(
+            r#"delete from restaurant_dietary_accommodations where restaurant_id = ? and user_id = ? and restriction = ?"#,
+        )
+        .bind(restaurant_id)
+        .bind(params.user_id.as_i32())
+        .bind(restriction.as_str())
+        .execute(&mut *conn)
+        .instrument(tracing::info_span!(
+            "Removing dietary accommodation from restaurant db query"
+        ))
+        .await?;
+        Ok(result.rows_affected() > 0)
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn set_restaurant_accommodations(
+        &self,
+        params: &UpdateRestaurantAttributesParams,
+        restrictions: &[DietaryRestriction],
+    ) -> Result<(), sqlx::Error> {
+        let mut tx = self.begin().await?;
+        let restaurant_id = get_restaurant_id(&mut tx, params).await?;
+        sqlx::query(
+            r#"delete from restaurant_dietary_accommodations where restaurant_id = ? and user_id = ?"#,
+        )
+        .bind(restaurant_id)
+        .bind(params.user_id.as_i32())
+        .execute(&mut *tx)
+        .await?;
+        for restriction in restrictions {
+            sqlx::query(
+                r#"insert or ignore into restaurant_dietary_accommodations (restaurant_id, user_id, restriction) values (?, ?, ?)"#,
+            )
+            .bind(restaurant_id)
+            .bind(params.user_id.as_i32())
+            .bind(restriction.as_str())
+            .execute(&mut *tx)
+            .await?;
+        }
+        tx.commit()
+            .instrument(tracing::info_span!(
+                "Replacing restaurant dietary accommodations db query"
+            ))
+            .await
+    }
+}
@@ -0,0 +1,4 @@
+mod lunch_rules;
+mod models;
+pub use lunch_rules::*;
+pub use models::*;
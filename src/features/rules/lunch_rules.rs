@@ -0,0 +1,171 @@
+use std::fmt::Debug;
+
+use chrono::NaiveDate;
+use sqlx::Pool;
+
+use sqlx::Sqlite;
+use thiserror::Error;
+use tracing::Instrument;
+
+use crate::features::get_restaurant;
+use crate::features::GetRestaurant;
+use crate::features::GetRestaurantError;
+use crate::features::RestaurantId;
+use crate::user::UserId;
+
+use super::LunchRule;
+use super::RuleSchedule;
+use super::RuleTarget;
+use super::StoredLunchRule;
+use super::StoredLunchRuleRow;
+
+/// Stores a rule, the restaurant it names has to exist.
+#[tracing::instrument(skip(db))]
+pub async fn add_lunch_rule(
+    rule: &LunchRule,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl LunchRules,
+) -> Result<StoredLunchRule, LunchRulesError> {
+    let user_id = user_id.into();
+    let restaurant_id = match &rule.target {
+        RuleTarget::Restaurant(name) => Some(get_restaurant(name.clone(), user_id, db).await?.id),
+        RuleTarget::Tag(_) => None,
+    };
+
+    let id = db.add_lunch_rule(rule, restaurant_id, user_id).await?;
+
+    Ok(StoredLunchRule {
+        id,
+        rule: rule.clone(),
+        restaurant_id,
+    })
+}
+
+#[tracing::instrument(skip(db))]
+pub async fn get_lunch_rules(
+    user_id: impl Into<UserId> + Debug,
+    db: &impl LunchRules,
+) -> Result<Vec<StoredLunchRule>, LunchRulesError> {
+    Ok(db.get_lunch_rules(user_id.into()).await?)
+}
+
+/// The rules that apply on `date`.
+#[tracing::instrument(skip(db))]
+pub async fn get_lunch_rules_on(
+    date: NaiveDate,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl LunchRules,
+) -> Result<Vec<StoredLunchRule>, LunchRulesError> {
+    Ok(db
+        .get_lunch_rules(user_id.into())
+        .await?
+        .into_iter()
+        .filter(|r| r.rule.applies_on(date))
+        .collect())
+}
+
+#[tracing::instrument(skip(db))]
+pub async fn remove_lunch_rule(
+    id: i64,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl LunchRules,
+) -> Result<(), LunchRulesError> {
+    db.remove_lunch_rule(id, user_id.into())
+        .await
+        .map_err(|e| match e {
+            sqlx::Error::RowNotFound => LunchRulesError::RuleNotFound { id },
+            _ => LunchRulesError::DbError(e),
+        })
+}
+
+#[derive(Error, Debug)]
+pub enum LunchRulesError {
+    #[error(transparent)]
+    GetRestaurantError(#[from] GetRestaurantError),
+
+    #[error("Rule not found: {}", id)]
+    RuleNotFound { id: i64 },
+
+    #[error(transparent)]
+    DbError(#[from] sqlx::Error),
+}
+
+pub trait LunchRules: GetRestaurant {
+    async fn add_lunch_rule(
+        &self,
+        rule: &LunchRule,
+        restaurant_id: Option<RestaurantId>,
+        user_id: UserId,
+    ) -> Result<i64, sqlx::Error>;
+
+    async fn get_lunch_rules(&self, user_id: UserId) -> Result<Vec<StoredLunchRule>, sqlx::Error>;
+
+    async fn remove_lunch_rule(&self, id: i64, user_id: UserId) -> Result<(), sqlx::Error>;
+}
+
+impl LunchRules for Pool<Sqlite> {
+    #[tracing::instrument(skip(self))]
+    async fn add_lunch_rule(
+        &self,
+        rule: &LunchRule,
+        restaurant_id: Option<RestaurantId>,
+        user_id: UserId,
+    ) -> Result<i64, sqlx::Error> {
+        let week_of_month = match rule.schedule {
+            RuleSchedule::Weekly(_) => None,
+            RuleSchedule::Monthly { nth, .. } => Some(nth),
+        };
+        let tag = match &rule.target {
+            RuleTarget::Tag(tag) => Some(tag.as_str()),
+            RuleTarget::Restaurant(_) => None,
+        };
+        let result = sqlx::query(
+            r#"
+insert into lunch_rules (user_id, weekday, week_of_month, effect, tag, restaurant_id)
+values (?, ?, ?, ?, ?, ?)
+            "#,
+        )
+        .bind(user_id.as_i32())
+        .bind(rule.schedule.weekday().num_days_from_monday())
+        .bind(week_of_month)
+        .bind(rule.effect.as_str())
+        .bind(tag)
+        .bind(restaurant_id.map(|r| *r.as_i32()))
+        .execute(self)
+        .instrument(tracing::info_span!("Adding lunch rule db query"))
+        .await?;
+        Ok(result.last_insert_rowid())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_lunch_rules(&self, user_id: UserId) -> Result<Vec<StoredLunchRule>, sqlx::Error> {
+        let rules: Vec<StoredLunchRuleRow> = sqlx::query_as(
+            r#"
+select lr.id, lr.weekday, lr.week_of_month, lr.effect, lr.tag, lr.restaurant_id, r.name as restaurant_name
+from lunch_rules lr
+         left join restaurants r on r.id = lr.restaurant_id and r.user_id = lr.user_id
+where lr.user_id = ?
+order by lr.id
+            "#,
+        )
+        .bind(user_id.as_i32())
+        .fetch_all(self)
+        .instrument(tracing::info_span!("Getting lunch rules db query"))
+        .await?;
+        Ok(rules.into_iter().map(|r| r.into()).collect())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn remove_lunch_rule(&self, id: i64, user_id: UserId) -> Result<(), sqlx::Error> {
+        let result = sqlx::query("delete from lunch_rules where id = ? and user_id = ?")
+            .bind(id)
+            .bind(user_id.as_i32())
+            .execute(self)
+            .instrument(tracing::info_span!("Removing lunch rule db query"))
+            .await?;
+        if result.rows_affected() == 0 {
+            return Err(sqlx::Error::RowNotFound);
+        }
+        Ok(())
+    }
+}
@@ -0,0 +1,439 @@
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+use chrono::Datelike;
+use chrono::Days;
+use chrono::NaiveDate;
+use chrono::Weekday;
+use sqlx::FromRow;
+use thiserror::Error;
+
+use crate::features::CandidateOptions;
+use crate::features::RestaurantId;
+use crate::features::RestaurantTag;
+use crate::features::RestaurantTagValidationError;
+
+const WEEKDAYS: [&str; 7] = [
+    "monday",
+    "tuesday",
+    "wednesday",
+    "thursday",
+    "friday",
+    "saturday",
+    "sunday",
+];
+const ORDINALS: [&str; 4] = ["first", "second", "third", "fourth"];
+
+#[derive(Error, Debug)]
+pub enum LunchRuleParseError {
+    #[error(
+        "Invalid lunch rule {:?}, expected something like \"on tuesdays prefer tag=tacos\"",
+        rule
+    )]
+    InvalidRule { rule: String },
+
+    #[error(
+        "Invalid days {:?}, expected something like \"tuesdays\" or \"the first friday of the month\"",
+        schedule
+    )]
+    InvalidSchedule { schedule: String },
+
+    #[error(transparent)]
+    InvalidTag(#[from] RestaurantTagValidationError),
+}
+
+/// Which days a rule applies on.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RuleSchedule {
+    Weekly(Weekday),
+    /// The nth of these days in a month, with -1 being the last one
+    Monthly {
+        nth: i8,
+        weekday: Weekday,
+    },
+}
+
+impl RuleSchedule {
+    pub fn weekday(&self) -> Weekday {
+        match self {
+            RuleSchedule::Weekly(weekday) | RuleSchedule::Monthly { weekday, .. } => *weekday,
+        }
+    }
+
+    pub fn applies_on(&self, date: NaiveDate) -> bool {
+        if date.weekday() != self.weekday() {
+            return false;
+        }
+        match self {
+            RuleSchedule::Weekly(_) => true,
+            RuleSchedule::Monthly { nth: -1, .. } => (date + Days::new(7)).month() != date.month(),
+            RuleSchedule::Monthly { nth, .. } => (date.day0() / 7 + 1) as i8 == *nth,
+        }
+    }
+}
+
+impl Display for RuleSchedule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        let weekday = WEEKDAYS[self.weekday().num_days_from_monday() as usize];
+        match self {
+            RuleSchedule::Weekly(_) => write!(f, "{}s", weekday),
+            RuleSchedule::Monthly { nth: -1, .. } => {
+                write!(f, "the last {} of the month", weekday)
+            }
+            RuleSchedule::Monthly { nth, .. } => write!(
+                f,
+                "the {} {} of the month",
+                ORDINALS[(*nth - 1) as usize],
+                weekday
+            ),
+        }
+    }
+}
+
+fn parse_weekday(day: &str) -> Option<Weekday> {
+    let day = day.to_lowercase();
+    let day = day.strip_suffix('s').unwrap_or(&day);
+    WEEKDAYS
+        .iter()
+        .position(|w| *w == day || (day.len() >= 2 && w.starts_with(day)))
+        .map(|index| Weekday::try_from(index as u8).expect("index of a day is a weekday"))
+}
+
+impl FromStr for RuleSchedule {
+    type Err = LunchRuleParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || LunchRuleParseError::InvalidSchedule {
+            schedule: s.to_string(),
+        };
+        let words = s
+            .split_whitespace()
+            .map(|w| w.to_lowercase())
+            .filter(|w| w != "the" && w != "every")
+            .collect::<Vec<_>>();
+        match words.as_slice() {
+            [day] => parse_weekday(day)
+                .map(RuleSchedule::Weekly)
+                .ok_or_else(invalid),
+            [nth, day, rest @ ..] if rest.iter().all(|w| w == "of" || w == "month") => {
+                let nth = match nth.as_str() {
+                    "last" => -1,
+                    "1st" => 1,
+                    "2nd" => 2,
+                    "3rd" => 3,
+                    "4th" => 4,
+                    nth => {
+                        ORDINALS
+                            .iter()
+                            .position(|o| *o == nth)
+                            .ok_or_else(invalid)? as i8
+                            + 1
+                    }
+                };
+                let weekday = parse_weekday(day).ok_or_else(invalid)?;
+                Ok(RuleSchedule::Monthly { nth, weekday })
+            }
+            _ => Err(invalid()),
+        }
+    }
+}
+
+/// What a rule does to the candidates on the days it applies.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum RuleEffect {
+    /// Matching candidates are weighted more
+    Prefer,
+    /// Matching restaurants are never suggested
+    Avoid,
+    /// Only matching restaurants are suggested, whether they are anyone's favorite or not
+    Force,
+}
+
+impl RuleEffect {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            RuleEffect::Prefer => "prefer",
+            RuleEffect::Avoid => "avoid",
+            RuleEffect::Force => "force",
+        }
+    }
+
+    /// How the effect is written in a rule.
+    pub fn keyword(&self) -> &'static str {
+        match self {
+            RuleEffect::Prefer => "prefer",
+            RuleEffect::Avoid => "no",
+            RuleEffect::Force => "always",
+        }
+    }
+}
+
+impl FromStr for RuleEffect {
+    type Err = LunchRuleParseError;
+
+    fn from_str(effect: &str) -> Result<Self, Self::Err> {
+        match effect.trim().to_lowercase().as_str() {
+            "prefer" => Ok(RuleEffect::Prefer),
+            "avoid" | "no" | "never" => Ok(RuleEffect::Avoid),
+            "force" | "always" => Ok(RuleEffect::Force),
+            _ => Err(LunchRuleParseError::InvalidRule {
+                rule: effect.to_string(),
+            }),
+        }
+    }
+}
+
+/// The restaurants a rule is about.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub enum RuleTarget {
+    Tag(RestaurantTag),
+    Restaurant(String),
+}
+
+impl Display for RuleTarget {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            RuleTarget::Tag(tag) => write!(f, "tag={}", tag.as_str()),
+            RuleTarget::Restaurant(name) => write!(f, "restaurant={}", name),
+        }
+    }
+}
+
+/// A recurring rule such as `on tuesdays prefer tag=tacos`, `on mondays no tag=pizza` or
+/// `on the first friday of the month always restaurant=Big Thai`.
+///
+/// Rules apply on a weekday every week or on the first to fourth or last of a weekday in
+/// a month. A restaurant can be named without `restaurant=`, and a trailing
+/// `with everyone` is allowed since rules apply no matter who is home.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct LunchRule {
+    pub schedule: RuleSchedule,
+    pub effect: RuleEffect,
+    pub target: RuleTarget,
+}
+
+impl LunchRule {
+    pub fn applies_on(&self, date: NaiveDate) -> bool {
+        self.schedule.applies_on(date)
+    }
+}
+
+impl Display for LunchRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "on {} {} {}",
+            self.schedule,
+            self.effect.keyword(),
+            self.target
+        )
+    }
+}
+
+impl FromStr for LunchRule {
+    type Err = LunchRuleParseError;
+
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        let invalid = || LunchRuleParseError::InvalidRule {
+            rule: s.to_string(),
+        };
+        let mut words = s.split_whitespace().collect::<Vec<_>>();
+        // "no tag=pizza on mondays" reads the same as "on mondays no tag=pizza"
+        if words.first().is_some_and(|w| !w.eq_ignore_ascii_case("on")) {
+            let on = words
+                .iter()
+                .rposition(|w| w.eq_ignore_ascii_case("on"))
+                .ok_or_else(invalid)?;
+            let schedule = words.split_off(on);
+            words = schedule.into_iter().chain(words).collect();
+        }
+        let Some((_, words)) = words.split_first() else {
+            return Err(invalid());
+        };
+        let position = words
+            .iter()
+            .position(|w| w.parse::<RuleEffect>().is_ok())
+            .ok_or_else(invalid)?;
+        let schedule = words[..position]
+            .iter()
+            .filter(|w| !w.eq_ignore_ascii_case("is"))
+            .copied()
+            .collect::<Vec<_>>()
+            .join(" ")
+            .parse()?;
+        let effect = words[position].parse()?;
+
+        let mut target = words[position + 1..].join(" ");
+        if target.to_lowercase().ends_with(" with everyone") {
+            target.truncate(target.len() - " with everyone".len());
+        }
+        let target = match target.split_once('=') {
+            Some((kind, tag)) if kind.trim().eq_ignore_ascii_case("tag") => {
+                RuleTarget::Tag(tag.parse()?)
+            }
+            Some((kind, name)) if kind.trim().eq_ignore_ascii_case("restaurant") => {
+                RuleTarget::Restaurant(name.trim().to_string())
+            }
+            _ => RuleTarget::Restaurant(target.trim().to_string()),
+        };
+        if target == RuleTarget::Restaurant(String::new()) {
+            return Err(invalid());
+        }
+
+        Ok(Self {
+            schedule,
+            effect,
+            target,
+        })
+    }
+}
+
+/// A rule as stored for a user, with the restaurant it names resolved.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct StoredLunchRule {
+    pub id: i64,
+    pub rule: LunchRule,
+    pub restaurant_id: Option<RestaurantId>,
+}
+
+impl Display for StoredLunchRule {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.id, self.rule)
+    }
+}
+
+#[derive(Debug, FromRow)]
+pub struct StoredLunchRuleRow {
+    id: i64,
+    weekday: i32,
+    week_of_month: Option<i32>,
+    effect: String,
+    tag: Option<String>,
+    restaurant_id: Option<i32>,
+    restaurant_name: Option<String>,
+}
+
+impl From<StoredLunchRuleRow> for StoredLunchRule {
+    fn from(row: StoredLunchRuleRow) -> Self {
+        let weekday = Weekday::try_from(row.weekday as u8).expect("stored weekdays are valid");
+        let schedule = match row.week_of_month {
+            Some(nth) => RuleSchedule::Monthly {
+                nth: nth as i8,
+                weekday,
+            },
+            None => RuleSchedule::Weekly(weekday),
+        };
+        let target = match (row.tag, row.restaurant_name) {
+            (Some(tag), _) => RuleTarget::Tag(tag.parse().expect("stored tags are valid")),
+            (None, name) => RuleTarget::Restaurant(name.unwrap_or_default()),
+        };
+        Self {
+            id: row.id,
+            rule: LunchRule {
+                schedule,
+                effect: row.effect.parse().expect("stored effects are valid"),
+                target,
+            },
+            restaurant_id: row.restaurant_id.map(|id| id.into()),
+        }
+    }
+}
+
+/// Folds the rules into the options: avoided restaurants are filtered out, preferred
+/// ones are boosted and forced ones become the only candidates.
+///
+/// Rules are applied whether or not they apply on the day, so filter them first.
+pub fn apply_lunch_rules(options: &mut CandidateOptions, rules: &[StoredLunchRule]) {
+    for stored in rules {
+        match (
+            &stored.rule.effect,
+            &stored.rule.target,
+            stored.restaurant_id,
+        ) {
+            (RuleEffect::Prefer, RuleTarget::Tag(tag), _) => options.prefer_tags.push(tag.clone()),
+            (RuleEffect::Avoid, RuleTarget::Tag(tag), _) => options.avoid_tags.push(tag.clone()),
+            (RuleEffect::Force, RuleTarget::Tag(tag), _) => options.tags.push(tag.clone()),
+            (RuleEffect::Prefer, _, Some(id)) => options.prefer.push(id),
+            (RuleEffect::Avoid, _, Some(id)) => options.exclude.push(id),
+            (RuleEffect::Force, _, Some(id)) => options.force.push(id),
+            (_, RuleTarget::Restaurant(_), None) => {}
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use chrono::NaiveDate;
+    use chrono::Weekday;
+
+    use super::LunchRule;
+    use super::RuleEffect;
+    use super::RuleSchedule;
+    use super::RuleTarget;
+
+    fn date(day: u32) -> NaiveDate {
+        NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+    }
+
+    #[test]
+    fn parses_and_formats_rules() {
+        for rule in [
+            "on tuesdays prefer tag=tacos",
+            "on mondays no tag=pizza",
+            "on the first friday of the month always restaurant=Big Thai",
+            "on the last wednesday of the month prefer restaurant=Pho",
+        ] {
+            assert_eq!(rule, rule.parse::<LunchRule>().unwrap().to_string());
+        }
+    }
+
+    #[test]
+    fn parses_natural_rules() {
+        let rule: LunchRule = "on the first Friday of the month is always Big Thai with everyone"
+            .parse()
+            .unwrap();
+
+        assert_eq!(
+            LunchRule {
+                schedule: RuleSchedule::Monthly {
+                    nth: 1,
+                    weekday: Weekday::Fri
+                },
+                effect: RuleEffect::Force,
+                target: RuleTarget::Restaurant("Big Thai".to_string()),
+            },
+            rule
+        );
+        assert_eq!(
+            "on mondays no tag=pizza",
+            "no tag=pizza on Mondays"
+                .parse::<LunchRule>()
+                .unwrap()
+                .to_string()
+        );
+        assert!("on tuesdays".parse::<LunchRule>().is_err());
+        assert!("on someday prefer tag=tacos".parse::<LunchRule>().is_err());
+        assert!("tuesdays prefer tag=tacos".parse::<LunchRule>().is_err());
+    }
+
+    #[test]
+    fn applies_on_matching_days() {
+        // 2026-10-02 is the first friday of october, 2026-10-30 the last
+        let first_friday = RuleSchedule::Monthly {
+            nth: 1,
+            weekday: Weekday::Fri,
+        };
+        let last_friday = RuleSchedule::Monthly {
+            nth: -1,
+            weekday: Weekday::Fri,
+        };
+
+        assert!(first_friday.applies_on(date(2)));
+        assert!(!first_friday.applies_on(date(9)));
+        assert!(last_friday.applies_on(date(30)));
+        assert!(!last_friday.applies_on(date(23)));
+        assert!(RuleSchedule::Weekly(Weekday::Tue).applies_on(date(20)));
+        assert!(!RuleSchedule::Weekly(Weekday::Tue).applies_on(date(21)));
+    }
+}
@@ -0,0 +1,4 @@
+mod models;
+mod simulate;
+pub use models::*;
+pub use simulate::*;
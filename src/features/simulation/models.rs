@@ -0,0 +1,177 @@
+use std::collections::HashSet;
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+use crate::features::HomieSatisfaction;
+use crate::features::RECENCY_WINDOW;
+
+#[derive(Error, Debug)]
+pub enum SimulationPolicyParseError {
+    #[error("Unknown simulation policy: {:?}", policy)]
+    UnknownPolicy { policy: String },
+}
+
+/// How the scripted chooser picks from each simulated day's candidates.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum SimulationPolicy {
+    /// Always take the top candidate
+    #[default]
+    First,
+    /// Take any of the candidates
+    Random,
+    /// Take something nobody has tried yet whenever there is one, otherwise the top candidate
+    Explore,
+}
+
+impl SimulationPolicy {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            SimulationPolicy::First => "first",
+            SimulationPolicy::Random => "random",
+            SimulationPolicy::Explore => "explore",
+        }
+    }
+}
+
+impl Display for SimulationPolicy {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for SimulationPolicy {
+    type Err = SimulationPolicyParseError;
+
+    fn from_str(policy: &str) -> Result<Self, Self::Err> {
+        match policy.trim().to_lowercase().as_str() {
+            "first" | "top" => Ok(SimulationPolicy::First),
+            "random" => Ok(SimulationPolicy::Random),
+            "explore" => Ok(SimulationPolicy::Explore),
+            _ => Err(SimulationPolicyParseError::UnknownPolicy {
+                policy: policy.to_string(),
+            }),
+        }
+    }
+}
+
+/// The picker settings to try out, on top of how the scripted chooser picks.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub struct SimulationSettings {
+    pub policy: SimulationPolicy,
+    /// How many of each homie's latest lunches keep a restaurant off the list,
+    /// [`RECENCY_WINDOW`] unless given
+    pub recency_window: Option<u32>,
+    /// Favorites count the same no matter how fairly lunches were shared
+    pub ignore_fairness: bool,
+    /// Favorites count the same no matter how often they won head-to-head matchups
+    pub ignore_elo: bool,
+}
+
+impl Display for SimulationSettings {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(
+            f,
+            "the {} policy, {} latest lunches kept off the list, fairness {} and elo {}",
+            self.policy,
+            self.recency_window.unwrap_or(RECENCY_WINDOW),
+            match self.ignore_fairness {
+                true => "off",
+                false => "on",
+            },
+            match self.ignore_elo {
+                true => "off",
+                false => "on",
+            }
+        )
+    }
+}
+
+/// What the picker came up with over a stretch of simulated days.
+#[derive(Debug, PartialEq, Clone)]
+pub struct SimulationReport {
+    pub settings: SimulationSettings,
+    /// The restaurant picked on each day, `None` when there were no candidates
+    pub lunches: Vec<Option<String>>,
+    pub satisfaction: Vec<HomieSatisfaction>,
+}
+
+impl SimulationReport {
+    /// How many different restaurants were picked.
+    pub fn variety(&self) -> usize {
+        self.lunches.iter().flatten().collect::<HashSet<_>>().len()
+    }
+
+    /// How many lunches were at a restaurant already picked earlier in the simulation.
+    pub fn repeats(&self) -> usize {
+        self.lunches.iter().flatten().count() - self.variety()
+    }
+
+    /// How many days the candidate list came up empty.
+    pub fn empty_days(&self) -> usize {
+        self.lunches.iter().filter(|l| l.is_none()).count()
+    }
+}
+
+impl Display for SimulationReport {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        writeln!(f, "{} days with {}", self.lunches.len(), self.settings)?;
+        writeln!(f, "    variety: {} restaurants", self.variety())?;
+        writeln!(f, "    repeats: {}", self.repeats())?;
+        writeln!(f, "    empty candidate lists: {}", self.empty_days())?;
+        for satisfaction in &self.satisfaction {
+            writeln!(f, "    {}", satisfaction)?;
+        }
+        Ok(())
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::SimulationPolicy;
+    use super::SimulationReport;
+    use super::SimulationSettings;
+
+    #[test]
+    fn counts_variety_repeats_and_empty_days() {
+        let report = SimulationReport {
+            settings: SimulationSettings::default(),
+            lunches: vec![
+                Some("Tacos".to_string()),
+                Some("Thai".to_string()),
+                None,
+                Some("Tacos".to_string()),
+                Some("Tacos".to_string()),
+            ],
+            satisfaction: Vec::new(),
+        };
+
+        assert_eq!(2, report.variety());
+        assert_eq!(2, report.repeats());
+        assert_eq!(1, report.empty_days());
+    }
+
+    #[test]
+    fn describes_the_settings() {
+        let settings = SimulationSettings {
+            policy: SimulationPolicy::Random,
+            recency_window: Some(2),
+            ignore_fairness: true,
+            ignore_elo: false,
+        };
+
+        assert_eq!(
+            "the random policy, 2 latest lunches kept off the list, fairness off and elo on",
+            settings.to_string()
+        );
+    }
+
+    #[test]
+    fn parses_policies() {
+        assert_eq!(SimulationPolicy::First, "top".parse().unwrap());
+        assert_eq!(SimulationPolicy::Explore, " Explore ".parse().unwrap());
+        assert!("greedy".parse::<SimulationPolicy>().is_err());
+    }
+}
@@ -0,0 +1,223 @@
+use std::collections::HashSet;
+use std::fmt::Debug;
+
+use anyhow::Result;
+use chrono::Days;
+use chrono::Local;
+use chrono::NaiveTime;
+use rand::Rng;
+use sqlx::sqlite::SqliteConnectOptions;
+use sqlx::sqlite::SqlitePoolOptions;
+use sqlx::Pool;
+use sqlx::Sqlite;
+use tracing::Instrument;
+
+use crate::features::add_recent_restaurant_for_homies;
+use crate::features::get_all_homies;
+use crate::features::get_available_homies;
+use crate::features::get_candidate_restaurants;
+use crate::features::get_exploration_restaurant;
+use crate::features::get_homies_favorite_restaurants;
+use crate::features::record_selection_event;
+use crate::features::CandidateOptions;
+use crate::features::HomieId;
+use crate::features::HomieSatisfaction;
+use crate::features::UserLocation;
+use crate::user::UserId;
+
+use super::SimulationPolicy;
+use super::SimulationReport;
+use super::SimulationSettings;
+
+/// Picks lunch for the homies once a day for `days` days with a scripted chooser and
+/// the picker `settings` to try out.
+///
+/// The picks run against an in-memory copy of the database, so nothing is recorded
+/// for real. The simulated lunches are at noon starting tomorrow, so opening hours,
+/// lunch rules and away periods apply as they would on each of those days. Only the
+/// restaurants of `location` are picked when given.
+#[tracing::instrument(skip(db))]
+pub async fn simulate<T, Y>(
+    homie_ids: T,
+    days: u32,
+    settings: SimulationSettings,
+    location: Option<&UserLocation>,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl Simulation,
+) -> Result<SimulationReport>
+where
+    T: IntoIterator<Item = Y> + Debug,
+    Y: Into<HomieId> + Debug,
+{
+    let homie_ids: Vec<HomieId> = homie_ids.into_iter().map(|id| id.into()).collect();
+    let user_id = user_id.into();
+    let simulation = db.in_memory_copy().await?;
+
+    let lunch_time = NaiveTime::from_hms_opt(12, 0, 0).expect("noon is a valid time");
+    let today = Local::now().date_naive();
+    let mut options = CandidateOptions {
+        recency_window: settings.recency_window,
+        ignore_fairness: settings.ignore_fairness,
+        ignore_elo: settings.ignore_elo,
+        location_id: location.map(|l| l.id),
+        origin: location.map(|l| l.coordinates),
+        ..Default::default()
+    };
+    let policy = settings.policy;
+    let mut lunches = Vec::new();
+    for day in 1..=days {
+        let date = today + Days::new(day.into());
+        options.at = Some(date.and_time(lunch_time));
+        let available = get_available_homies(homie_ids.clone(), date, user_id, &simulation).await?;
+        if available.is_empty() {
+            lunches.push(None);
+            continue;
+        }
+        options.party_size = Some(available.len() as u32);
+
+        let mut candidates =
+            get_candidate_restaurants(available.clone(), &options, user_id, &simulation).await?;
+        if policy == SimulationPolicy::Explore {
+            if let Some(new) =
+                get_exploration_restaurant(available.clone(), &options, user_id, &simulation)
+                    .await?
+            {
+                candidates.insert(0, new);
+            }
+        }
+
+        let chosen = match (policy, candidates.len()) {
+            (_, 0) => None,
+            (SimulationPolicy::Random, count) => {
+                Some(&candidates[rand::thread_rng().gen_range(0..count)])
+            }
+            (SimulationPolicy::First | SimulationPolicy::Explore, _) => candidates.first(),
+        };
+        match chosen {
+            Some(chosen) => {
+                record_selection_event(
+                    available.clone(),
+                    &candidates.iter().map(|r| r.id).collect::<Vec<_>>(),
+                    chosen.id,
+                    user_id,
+                    &simulation,
+                )
+                .await?;
+                add_recent_restaurant_for_homies(
+                    available.clone(),
+                    chosen.id,
+                    options.meal,
+                    date,
+                    false,
+                    user_id,
+                    &simulation,
+                )
+                .await?;
+                lunches.push(Some((
+                    chosen.id,
+                    chosen.name.as_str().to_string(),
+                    available,
+                )));
+            }
+            None => lunches.push(None),
+        }
+    }
+
+    let mut satisfaction = Vec::new();
+    for homie in get_all_homies(user_id, &simulation)
+        .await?
+        .into_iter()
+        .filter(|h| homie_ids.contains(&h.id))
+    {
+        let favorites = get_homies_favorite_restaurants(user_id, homie.id, &simulation)
+            .await?
+            .into_iter()
+            .map(|f| f.restaurant.id)
+            .collect::<HashSet<_>>();
+        satisfaction.push(HomieSatisfaction {
+            homie_id: homie.id,
+            homie_name: homie.name.as_str().to_string(),
+            lunches: lunches
+                .iter()
+                .flatten()
+                .filter(|(_, _, present)| present.contains(&homie.id))
+                .count() as i64,
+            satisfied: lunches
+                .iter()
+                .flatten()
+                .filter(|(id, _, present)| present.contains(&homie.id) && favorites.contains(id))
+                .count() as i64,
+        });
+    }
+    simulation.close().await;
+
+    Ok(SimulationReport {
+        settings,
+        lunches: lunches
+            .into_iter()
+            .map(|l| l.map(|(_, name, _)| name))
+            .collect(),
+        satisfaction,
+    })
+}
+
+pub trait Simulation {
+    /// A private copy of the database that only lives as long as the returned pool.
+    async fn in_memory_copy(&self) -> Result<Pool<Sqlite>, sqlx::Error>;
+}
+
+impl Simulation for Pool<Sqlite> {
+    #[tracing::instrument(skip(self))]
+    async fn in_memory_copy(&self) -> Result<Pool<Sqlite>, sqlx::Error> {
+        let file: String =
+            sqlx::query_scalar("select file from pragma_database_list where name = 'main'")
+                .fetch_one(self)
+                .await?;
+        if file.is_empty() {
+            return Err(sqlx::Error::Configuration(
+                "only a database stored in a file can be copied".into(),
+            ));
+        }
+
+        // every connection to :memory: is a database of its own, so there is only one.
+        // sqlite::memory: isn't used since databases attached to it end up in memory too
+        let copy = SqlitePoolOptions::new()
+            .max_connections(1)
+            .idle_timeout(None)
+            .max_lifetime(None)
+            .connect_with(
+                SqliteConnectOptions::new()
+                    .filename(":memory:")
+                    .foreign_keys(false),
+            )
+            .await?;
+        sqlx::migrate!("./migrations").run(&copy).await?;
+
+        sqlx::query("attach database ? as source")
+            .bind(&file)
+            .execute(&copy)
+            .await?;
+        let tables: Vec<String> = sqlx::query_scalar(
+            r#"
+select name
+from source.sqlite_master
+where type = 'table'
+  and name not like 'sqlite_%'
+  and name != '_sqlx_migrations'
+            "#,
+        )
+        .fetch_all(&copy)
+        .await?;
+        for table in tables {
+            sqlx::query(&format!(
+                "delete from main.{table}; insert into main.{table} select * from source.{table}"
+            ))
+            .execute(&copy)
+            .instrument(tracing::info_span!("Copying table into memory", table))
+            .await?;
+        }
+        sqlx::query("detach database source").execute(&copy).await?;
+
+        Ok(copy)
+    }
+}
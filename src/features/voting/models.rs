@@ -0,0 +1,207 @@
+use std::fmt::Display;
+use std::fmt::Formatter;
+use std::str::FromStr;
+
+use thiserror::Error;
+
+#[derive(Error, Debug)]
+pub enum VotingMethodParseError {
+    #[error("Unknown voting method: {:?}", method)]
+    UnknownMethod { method: String },
+}
+
+/// How the home homies' ballots are turned into a single restaurant.
+#[derive(Debug, PartialEq, Eq, Clone, Copy, Default)]
+pub enum VotingMethod {
+    /// Every ranking position is worth points, the most points win
+    #[default]
+    Borda,
+    /// The restaurant with the fewest first choices is dropped until one has a majority
+    InstantRunoff,
+    /// Everyone picks all the restaurants they'd be happy with, the most approvals win
+    Approval,
+}
+
+impl VotingMethod {
+    pub fn as_str(&self) -> &'static str {
+        match self {
+            VotingMethod::Borda => "borda",
+            VotingMethod::InstantRunoff => "instant-runoff",
+            VotingMethod::Approval => "approval",
+        }
+    }
+
+    /// Whether ballots rank the shortlist, rather than approve some of it.
+    pub fn is_ranked(&self) -> bool {
+        !matches!(self, VotingMethod::Approval)
+    }
+}
+
+impl Display for VotingMethod {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(self.as_str())
+    }
+}
+
+impl FromStr for VotingMethod {
+    type Err = VotingMethodParseError;
+
+    fn from_str(method: &str) -> Result<Self, Self::Err> {
+        match method.trim().to_lowercase().as_str() {
+            "borda" => Ok(VotingMethod::Borda),
+            "instant-runoff" | "irv" | "ranked-choice" => Ok(VotingMethod::InstantRunoff),
+            "approval" => Ok(VotingMethod::Approval),
+            _ => Err(VotingMethodParseError::UnknownMethod {
+                method: method.to_string(),
+            }),
+        }
+    }
+}
+
+/// One homie's vote as indexes into the shortlist.
+///
+/// For ranked methods the first index is the favorite choice; for approval the
+/// order doesn't matter.
+#[derive(Debug, PartialEq, Eq, Clone, Default)]
+pub struct Ballot(pub Vec<usize>);
+
+/// Points per shortlisted restaurant index in every round of counting.
+///
+/// Borda and approval are counted in a single round; instant-runoff has a round per
+/// elimination, and eliminated restaurants are left out of the later rounds.
+#[derive(Debug, PartialEq, Eq, Clone)]
+pub struct Tally {
+    pub method: VotingMethod,
+    pub rounds: Vec<Vec<(usize, u32)>>,
+    pub winner: usize,
+}
+
+/// Counts the ballots for a shortlist of `candidates` restaurants.
+///
+/// Ties go to the restaurant that came first in the shortlist. There is no tally
+/// without anything to vote on.
+pub fn tally(method: VotingMethod, candidates: usize, ballots: &[Ballot]) -> Option<Tally> {
+    if candidates == 0 {
+        return None;
+    }
+    Some(match method {
+        VotingMethod::Borda => {
+            let mut points = vec![0; candidates];
+            for ballot in ballots {
+                for (position, &choice) in ballot.0.iter().enumerate() {
+                    points[choice] += (candidates - 1 - position) as u32;
+                }
+            }
+            single_round(method, points)
+        }
+        VotingMethod::Approval => {
+            let mut approvals = vec![0; candidates];
+            for ballot in ballots {
+                for &choice in &ballot.0 {
+                    approvals[choice] += 1;
+                }
+            }
+            single_round(method, approvals)
+        }
+        VotingMethod::InstantRunoff => instant_runoff(candidates, ballots),
+    })
+}
+
+fn most_points(points: &[(usize, u32)]) -> usize {
+    points
+        .iter()
+        .fold(None, |best: Option<(usize, u32)>, &(index, p)| match best {
+            Some((_, best_points)) if best_points >= p => best,
+            _ => Some((index, p)),
+        })
+        .map(|(index, _)| index)
+        .expect("there is at least one candidate")
+}
+
+fn single_round(method: VotingMethod, points: Vec<u32>) -> Tally {
+    let round = points.into_iter().enumerate().collect::<Vec<_>>();
+    Tally {
+        method,
+        winner: most_points(&round),
+        rounds: vec![round],
+    }
+}
+
+fn instant_runoff(candidates: usize, ballots: &[Ballot]) -> Tally {
+    let mut remaining = (0..candidates).collect::<Vec<usize>>();
+    let mut rounds = Vec::new();
+    loop {
+        let mut round = remaining.iter().map(|&c| (c, 0)).collect::<Vec<_>>();
+        for ballot in ballots {
+            if let Some(&choice) = ballot.0.iter().find(|c| remaining.contains(c)) {
+                let (_, votes) = round
+                    .iter_mut()
+                    .find(|(c, _)| *c == choice)
+                    .expect("choice is remaining");
+                *votes += 1;
+            }
+        }
+        let total: u32 = round.iter().map(|(_, votes)| votes).sum();
+        let leader = most_points(&round);
+        let leader_votes = round.iter().find(|(c, _)| *c == leader).unwrap().1;
+        let (last, _) = *round
+            .iter()
+            .rev()
+            .min_by_key(|(_, votes)| *votes)
+            .expect("there is at least one candidate");
+        rounds.push(round);
+
+        if remaining.len() == 1 || total == 0 || leader_votes * 2 > total {
+            return Tally {
+                method: VotingMethod::InstantRunoff,
+                rounds,
+                winner: leader,
+            };
+        }
+        remaining.retain(|&c| c != last);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::tally;
+    use super::Ballot;
+    use super::VotingMethod;
+
+    fn ballots(rankings: &[&[usize]]) -> Vec<Ballot> {
+        rankings.iter().map(|r| Ballot(r.to_vec())).collect()
+    }
+
+    #[test]
+    fn borda_rewards_the_compromise() {
+        let ballots = ballots(&[&[0, 1, 2], &[0, 1, 2], &[2, 1, 0], &[2, 1, 0], &[1, 0, 2]]);
+
+        assert_eq!(1, tally(VotingMethod::Borda, 3, &ballots).unwrap().winner);
+    }
+
+    #[test]
+    fn instant_runoff_transfers_eliminated_votes() {
+        let ballots = ballots(&[&[0, 1, 2], &[0, 1, 2], &[1, 2, 0], &[1, 2, 0], &[2, 1, 0]]);
+
+        let actual = tally(VotingMethod::InstantRunoff, 3, &ballots).unwrap();
+
+        assert_eq!(1, actual.winner);
+        assert_eq!(2, actual.rounds.len());
+        assert_eq!(vec![(0, 2), (1, 3)], actual.rounds[1]);
+    }
+
+    #[test]
+    fn approval_ties_go_to_the_first_shortlisted() {
+        let ballots = ballots(&[&[1, 2], &[2, 1], &[0]]);
+
+        assert_eq!(
+            1,
+            tally(VotingMethod::Approval, 3, &ballots).unwrap().winner
+        );
+    }
+
+    #[test]
+    fn nothing_to_vote_on() {
+        assert!(tally(VotingMethod::Borda, 0, &ballots(&[&[]])).is_none());
+    }
+}
@@ -0,0 +1,4 @@
+mod models;
+mod restaurant_wishlist;
+pub use models::*;
+pub use restaurant_wishlist::*;
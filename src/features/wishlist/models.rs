@@ -0,0 +1,28 @@
+use sqlx::FromRow;
+
+use crate::features::RestaurantId;
+
+/// A restaurant someone wants to try, shared with everyone when there is no homie.
+#[derive(Debug, PartialEq, Eq)]
+pub struct WishlistItem {
+    pub restaurant_id: RestaurantId,
+    pub restaurant_name: String,
+    pub homie_name: Option<String>,
+}
+
+#[derive(Debug, FromRow)]
+pub struct WishlistItemRow {
+    restaurant_id: i32,
+    restaurant_name: String,
+    homie_name: Option<String>,
+}
+
+impl From<WishlistItemRow> for WishlistItem {
+    fn from(row: WishlistItemRow) -> Self {
+        Self {
+            restaurant_id: row.restaurant_id.into(),
+            restaurant_name: row.restaurant_name,
+            homie_name: row.homie_name,
+        }
+    }
+}
@@ -0,0 +1,128 @@
+use std::fmt::Debug;
+
+use sqlx::Pool;
+
+use sqlx::Sqlite;
+use thiserror::Error;
+use tracing::Instrument;
+
+use crate::features::get_homie;
+use crate::features::get_restaurant;
+use crate::features::GetHomie;
+use crate::features::GetHomieError;
+use crate::features::GetRestaurant;
+use crate::features::GetRestaurantError;
+use crate::features::HomieId;
+use crate::features::HomieNameValidationError;
+use crate::features::HomiesName;
+use crate::features::RestaurantId;
+use crate::features::RestaurantName;
+use crate::features::RestaurantNameValidationError;
+use crate::user::UserId;
+
+use super::WishlistItem;
+use super::WishlistItemRow;
+
+/// Adds a restaurant to a homie's wishlist, or to the shared one without a homie.
+///
+/// It stays on the wishlist until it is visited; adding it again puts it back on.
+#[tracing::instrument(skip(db))]
+pub async fn add_to_wishlist(
+    restaurant_name: impl TryInto<RestaurantName, Error = RestaurantNameValidationError> + Debug,
+    homie_name: Option<impl TryInto<HomiesName, Error = HomieNameValidationError> + Debug>,
+    user_id: impl Into<UserId> + Debug,
+    db: &impl RestaurantWishlist,
+) -> Result<(), RestaurantWishlistError> {
+    let user_id = user_id.into();
+    let restaurant = get_restaurant(restaurant_name, user_id, db).await?;
+    let homie_id = match homie_name {
+        Some(homie_name) => Some(get_homie(user_id, homie_name, db).await?.id),
+        None => None,
+    };
+
+    db.add_to_wishlist(restaurant.id, homie_id, user_id).await?;
+
+    Ok(())
+}
+
+/// Wishlisted restaurants that haven't been visited since they were added.
+#[tracing::instrument(skip(db))]
+pub async fn get_wishlist(
+    user_id: impl Into<UserId> + Debug,
+    db: &impl RestaurantWishlist,
+) -> Result<Vec<WishlistItem>, RestaurantWishlistError> {
+    Ok(db.get_wishlist(user_id.into()).await?)
+}
+
+#[derive(Error, Debug)]
+pub enum RestaurantWishlistError {
+    #[error(transparent)]
+    GetRestaurantError(#[from] GetRestaurantError),
+
+    #[error(transparent)]
+    GetHomieError(#[from] GetHomieError),
+
+    #[error(transparent)]
+    DbError(#[from] sqlx::Error),
+}
+
+pub trait RestaurantWishlist: GetRestaurant + GetHomie {
+    async fn add_to_wishlist(
+        &self,
+        restaurant_id: RestaurantId,
+        homie_id: Option<HomieId>,
+        user_id: UserId,
+    ) -> Result<(), sqlx::Error>;
+
+    async fn get_wishlist(&self, user_id: UserId) -> Result<Vec<WishlistItem>, sqlx::Error>;
+}
+
+impl RestaurantWishlist for Pool<Sqlite> {
+    #[tracing::instrument(skip(self))]
+    async fn add_to_wishlist(
+        &self,
+        restaurant_id: RestaurantId,
+        homie_id: Option<HomieId>,
+        user_id: UserId,
+    ) -> Result<(), sqlx::Error> {
+        sqlx::query(
+            r#"
+insert into restaurant_wishlist (restaurant_id, homie_id, user_id)
+values (?, ?, ?)
+on conflict do update set created_at = current_timestamp
+            "#,
+        )
+        .bind(restaurant_id.as_i32())
+        .bind(homie_id.map(|h| h.as_i32()))
+        .bind(user_id.as_i32())
+        .execute(self)
+        .instrument(tracing::info_span!(
+            "Adding restaurant to wishlist db query"
+        ))
+        .await?;
+        Ok(())
+    }
+
+    #[tracing::instrument(skip(self))]
+    async fn get_wishlist(&self, user_id: UserId) -> Result<Vec<WishlistItem>, sqlx::Error> {
+        let wishlist: Vec<WishlistItemRow> = sqlx::query_as(
+            r#"
+select r.id as restaurant_id, r.name as restaurant_name, h.name as homie_name
+from restaurant_wishlist w
+         join restaurants r on r.id = w.restaurant_id and r.user_id = w.user_id
+         left join homies h on h.id = w.homie_id and h.user_id = w.user_id
+where w.user_id = ?
+  and not exists (select 1
+                  from recent_restaurants rr
+                  where rr.restaurant_id = w.restaurant_id
+                    and rr.date >= date(w.created_at))
+order by w.created_at, r.name, h.name
+            "#,
+        )
+        .bind(user_id.as_i32())
+        .fetch_all(self)
+        .instrument(tracing::info_span!("Getting wishlist db query"))
+        .await?;
+        Ok(wishlist.into_iter().map(|w| w.into()).collect())
+    }
+}
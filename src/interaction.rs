@@ -1,4 +1,5 @@
 use anyhow::Result;
+use chrono::Local;
 use dialoguer::theme::ColorfulTheme;
 use std::collections::HashSet;
 use std::fmt::Debug;
@@ -6,10 +7,21 @@ use std::fmt::Debug;
 use dialoguer::{Input, MultiSelect, Select};
 
 use crate::features::{
-    add_homies_favorite_restaurant, create_homie, create_restaurant, get_all_homies,
-    get_all_restaurants, get_homies_favorite_restaurants, remove_homies_favorite_restaurant,
-    AddFavoriteRestaurantToHomie, CreateHomie, CreateRestaurant, GetAllHomies, GetAllRestaurants,
-    GetHomiesFavoriteRestaurants, Homie, RemoveFavoriteRestaurantFromHomie, Restaurant,
+    add_homies_favorite_restaurant, add_homies_vetoed_restaurant, add_recent_restaurant_for_homies,
+    create_homie, create_restaurant, format_distance, get_all_homies, get_all_restaurants,
+    get_available_homies, get_homie_group, get_homie_groups, get_homies_favorite_restaurants,
+    get_homies_vetoed_restaurants, get_last_home_homies, get_todays_picks, get_unrated_visits,
+    rate_recent_restaurant, remember_home_homies, remove_homies_favorite_restaurant,
+    remove_homies_vetoed_restaurant, set_homies_favorite_restaurant_strength,
+    set_restaurant_accommodations, set_restaurant_price_tier, set_restaurant_scope,
+    set_restaurant_service_modes, set_restaurant_tags, tally, AddFavoriteRestaurantToHomie,
+    AddHomiesFavoriteRestaurantError, AddRecentRestaurantToHomie, AddVetoedRestaurantToHomie,
+    Ballot, Coordinates, CreateHomie, CreateRestaurant, DietaryRestriction, FavoriteStrength,
+    GetAllHomies, GetAllRestaurants, GetHomiesFavoriteRestaurants, GetHomiesVetoedRestaurants,
+    GetRecentVisits, Homie, HomieAvailability, HomieGroups, Matchup, MealSlot, PriceTier,
+    RateRecentRestaurant, RemoveFavoriteRestaurantFromHomie, RemoveVetoedRestaurantFromHomie,
+    Restaurant, RestaurantId, RestaurantTag, ServiceMode, SetFavoriteRestaurantStrength,
+    UpdateRestaurantAttributes, VisitRating, VotingMethod,
 };
 use crate::user::UserId;
 use crate::Settings;
@@ -38,18 +50,25 @@ pub fn user_setup() -> Result<Settings> {
     Ok(Settings::new(database_url, enable_telemetry))
 }
 
+/// New restaurants belong to `location_id` when given.
 #[tracing::instrument(name = "User Adds Restaurants Interactively", skip(db))]
 pub async fn add_restaurants_interactive<T>(
+    location_id: Option<i64>,
     user_id: impl Into<UserId> + Debug,
     db: &T,
 ) -> Result<()>
 where
     T: CreateRestaurant
+        + UpdateRestaurantAttributes
         + AddFavoriteRestaurantToHomie
         + GetAllHomies
         + GetAllRestaurants
         + RemoveFavoriteRestaurantFromHomie
-        + GetHomiesFavoriteRestaurants,
+        + GetHomiesFavoriteRestaurants
+        + SetFavoriteRestaurantStrength
+        + AddVetoedRestaurantToHomie
+        + RemoveVetoedRestaurantFromHomie
+        + GetHomiesVetoedRestaurants,
 {
     let mut input = Input::<String>::new()
         .with_prompt("Enter a restaurant name")
@@ -59,7 +78,11 @@ where
     let user_id = user_id.into();
     while !input.is_empty() {
         println!("Adding restaurant: {}", input);
-        match create_restaurant(input, user_id, db).await {
+        match create_restaurant(input.clone(), user_id, db).await {
+            Ok(_) if location_id.is_some() => {
+                set_restaurant_scope(input, location_id, user_id, db).await?;
+                Ok(())
+            }
             Ok(_) => Ok(()),
             Err(e) => match e {
                 crate::features::CreateRestaurantError::InvalidName { name } => {
@@ -80,7 +103,7 @@ where
     }
     let mut input = 0;
     while input != 1 {
-        add_homies_favorite_restaurants_interactive(user_id, db).await?;
+        add_homies_favorite_restaurants_interactive(location_id, user_id, db).await?;
         input = Select::with_theme(&ColorfulTheme::default())
             .with_prompt("Continue adding favorites to homies?")
             .items(&["Yes", "No"])
@@ -93,8 +116,11 @@ where
     Ok(())
 }
 
+/// Only the restaurants of `location_id` are offered, and favorites and vetoes
+/// elsewhere are left alone.
 #[tracing::instrument(name = "User Adds Restaurants Interactively", skip(db))]
 pub async fn add_homies_favorite_restaurants_interactive<T>(
+    location_id: Option<i64>,
     user_id: impl Into<UserId> + Debug,
     db: &T,
 ) -> Result<()>
@@ -103,14 +129,26 @@ where
         + GetAllHomies
         + GetAllRestaurants
         + RemoveFavoriteRestaurantFromHomie
-        + GetHomiesFavoriteRestaurants,
+        + GetHomiesFavoriteRestaurants
+        + SetFavoriteRestaurantStrength
+        + AddVetoedRestaurantToHomie
+        + RemoveVetoedRestaurantFromHomie
+        + GetHomiesVetoedRestaurants,
 {
     let user_id = user_id.into();
     let homies = get_all_homies(user_id, db).await?;
     let selected_home = select_homie(&homies)?;
-    let restaurants = get_all_restaurants(user_id, db).await?;
-    let favorited = get_homies_favorite_restaurants(user_id, selected_home.id, db).await?;
-    let favorited_ids: HashSet<_> = favorited.iter().map(|r| r.id).collect();
+    let restaurants = get_all_restaurants(user_id, db)
+        .await?
+        .into_iter()
+        .filter(|r| r.is_available_at(location_id))
+        .collect::<Vec<_>>();
+    let favorited = get_homies_favorite_restaurants(user_id, selected_home.id, db)
+        .await?
+        .into_iter()
+        .filter(|f| f.restaurant.is_available_at(location_id))
+        .collect::<Vec<_>>();
+    let favorited_ids: HashSet<_> = favorited.iter().map(|f| f.restaurant.id).collect();
     let mut pre_select = vec![];
 
     for restaurant in restaurants.iter() {
@@ -124,9 +162,7 @@ where
     let restaurant_names = restaurants
         .iter()
         // .filter(|r| !favorited_ids.contains(&r.id))
-        .map(|h| {
-            return h.name.as_str();
-        })
+        .map(|h| h.name.as_str())
         .collect::<Vec<&str>>();
 
     let chosen = MultiSelect::with_theme(&ColorfulTheme::default())
@@ -162,6 +198,7 @@ where
 
     let homies_removed_favorites = favorited
         .iter()
+        .map(|f| &f.restaurant)
         .filter(|r| !new_favorite_ids.contains(&r.id));
     for removed in homies_removed_favorites {
         remove_homies_favorite_restaurant(
@@ -173,6 +210,360 @@ where
         .await?;
     }
 
+    let set_strengths = !chosen.is_empty()
+        && Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!(
+                "Rate how much {} likes each favorite?",
+                selected_home.name.as_str()
+            ))
+            .items(&["Yes", "No"])
+            .default(1)
+            .interact()?
+            == 0;
+    if set_strengths {
+        for restaurant in chosen.iter().map(|&index| &restaurants[index]) {
+            let current = favorited
+                .iter()
+                .find(|f| f.restaurant.id == restaurant.id)
+                .map(|f| f.strength)
+                .unwrap_or_default();
+            let strength = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt(restaurant.name.as_str())
+                .items(&FavoriteStrength::ALL)
+                .default(current.as_u8() as usize - 1)
+                .interact()?;
+            let strength = FavoriteStrength::ALL[strength];
+            if strength != current || !favorited_ids.contains(&restaurant.id) {
+                set_homies_favorite_restaurant_strength(
+                    selected_home.name.as_str().to_string(),
+                    restaurant.name.as_str().to_string(),
+                    strength,
+                    user_id,
+                    db,
+                )
+                .await?;
+            }
+        }
+    }
+
+    // a favorite can't also be vetoed, so only offer the remaining restaurants
+    let vetoed = get_homies_vetoed_restaurants(user_id, selected_home.id, db)
+        .await?
+        .into_iter()
+        .filter(|r| r.is_available_at(location_id))
+        .collect::<Vec<_>>();
+    let vetoed_ids: HashSet<_> = vetoed.iter().map(|r| r.id).collect();
+    let vetoable: Vec<_> = restaurants
+        .iter()
+        .filter(|r| !new_favorite_ids.contains(&r.id))
+        .collect();
+    let vetoable_names = vetoable
+        .iter()
+        .map(|r| r.name.as_str())
+        .collect::<Vec<&str>>();
+    let pre_select = vetoable
+        .iter()
+        .map(|r| vetoed_ids.contains(&r.id))
+        .collect::<Vec<bool>>();
+
+    let chosen = MultiSelect::with_theme(&ColorfulTheme::default())
+        .with_prompt(format!(
+            "Which Restaurants does {} veto?",
+            selected_home.name.as_str()
+        ))
+        .items(&vetoable_names)
+        .defaults(&pre_select)
+        .interact()?;
+    let new_vetoed_ids: HashSet<_> = chosen.iter().map(|&index| vetoable[index].id).collect();
+
+    for restaurant in chosen
+        .iter()
+        .map(|&index| vetoable[index])
+        .filter(|r| !vetoed_ids.contains(&r.id))
+    {
+        add_homies_vetoed_restaurant(
+            selected_home.name.as_str().to_string(),
+            restaurant.name.as_str().to_string(),
+            user_id,
+            db,
+        )
+        .await?;
+    }
+
+    for removed in vetoed.iter().filter(|r| !new_vetoed_ids.contains(&r.id)) {
+        remove_homies_vetoed_restaurant(
+            selected_home.name.as_str().to_string(),
+            removed.name.as_str().to_string(),
+            user_id,
+            db,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+#[tracing::instrument(name = "User Edits Restaurant Attributes Interactively", skip(db))]
+pub async fn edit_restaurant_attributes_interactive<T>(
+    location_id: Option<i64>,
+    user_id: impl Into<UserId> + Debug,
+    db: &T,
+) -> Result<()>
+where
+    T: GetAllRestaurants + UpdateRestaurantAttributes,
+{
+    let user_id = user_id.into();
+    let restaurants = get_all_restaurants(user_id, db)
+        .await?
+        .into_iter()
+        .filter(|r| r.is_available_at(location_id))
+        .collect::<Vec<_>>();
+    let restaurant_names = restaurants
+        .iter()
+        .map(|r| r.name.as_str())
+        .collect::<Vec<&str>>();
+
+    let chosen = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Which restaurant would you like to edit?")
+        .items(&restaurant_names)
+        .interact()?;
+    let restaurant = &restaurants[chosen];
+
+    let tags = Input::<String>::with_theme(&ColorfulTheme::default())
+        .with_prompt("Tags (comma separated)")
+        .with_initial_text(
+            restaurant
+                .tags
+                .iter()
+                .map(|t| t.as_str())
+                .collect::<Vec<&str>>()
+                .join(", "),
+        )
+        .allow_empty(true)
+        .interact_text()?;
+    let tags = tags
+        .split(',')
+        .filter(|t| !t.trim().is_empty())
+        .map(|t| t.parse())
+        .collect::<Result<Vec<RestaurantTag>, _>>()?;
+
+    let price_tier = Select::with_theme(&ColorfulTheme::default())
+        .with_prompt("Price tier")
+        .items(&["Unknown", "$", "$$", "$$$", "$$$$"])
+        .default(restaurant.price_tier.map_or(0, |p| p.as_u8() as usize))
+        .interact()?;
+    let price_tier = match price_tier {
+        0 => None,
+        tier => Some(PriceTier::try_from(tier as i32)?),
+    };
+
+    let pre_select = ServiceMode::ALL
+        .iter()
+        .map(|m| restaurant.service_modes.contains(m))
+        .collect::<Vec<bool>>();
+    let chosen_modes = MultiSelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("Service modes")
+        .items(&ServiceMode::ALL)
+        .defaults(&pre_select)
+        .interact()?;
+    let service_modes = chosen_modes
+        .iter()
+        .map(|&index| ServiceMode::ALL[index])
+        .collect::<Vec<ServiceMode>>();
+
+    let pre_select = DietaryRestriction::ALL
+        .iter()
+        .map(|r| restaurant.accommodations.contains(r))
+        .collect::<Vec<bool>>();
+    let chosen_accommodations = MultiSelect::with_theme(&ColorfulTheme::default())
+        .with_prompt("Which dietary restrictions can it accommodate?")
+        .items(&DietaryRestriction::ALL)
+        .defaults(&pre_select)
+        .interact()?;
+    let accommodations = chosen_accommodations
+        .iter()
+        .map(|&index| DietaryRestriction::ALL[index])
+        .collect::<Vec<DietaryRestriction>>();
+
+    let name = restaurant.name.as_str().to_string();
+    set_restaurant_tags(name.clone(), &tags, user_id, db).await?;
+    set_restaurant_price_tier(name.clone(), price_tier, user_id, db).await?;
+    set_restaurant_service_modes(name.clone(), &service_modes, user_id, db).await?;
+    set_restaurant_accommodations(name, &accommodations, user_id, db).await?;
+
+    Ok(())
+}
+
+/// Asks each homie how the latest unrated lunch was.
+///
+/// With `ask_first` the user can decline before any rating is asked for, which
+/// is how the prompt at the start of a pick behaves.
+#[tracing::instrument(name = "User Rates Recent Lunch Interactively", skip(db))]
+pub async fn rate_recent_visits_interactive<T>(
+    include_today: bool,
+    ask_first: bool,
+    user_id: impl Into<UserId> + Debug,
+    db: &T,
+) -> Result<()>
+where
+    T: GetRecentVisits + RateRecentRestaurant,
+{
+    let user_id = user_id.into();
+    let visits = get_unrated_visits(include_today, user_id, db).await?;
+    let Some(first) = visits.first() else {
+        return Ok(());
+    };
+    let lunch = format!("{} on {}", first.restaurant_name, first.date);
+
+    if ask_first
+        && Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("Rate your lunch at {}?", lunch))
+            .items(&["Yes", "No"])
+            .default(1)
+            .interact()?
+            == 1
+    {
+        return Ok(());
+    }
+
+    for visit in &visits {
+        let rating = Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!("How was {} for {}?", lunch, visit.homie_name))
+            .items(&[
+                "Skip",
+                "1 - never again",
+                "2 - meh",
+                "3 - fine",
+                "4 - good",
+                "5 - great",
+            ])
+            .default(0)
+            .interact()?;
+        if rating == 0 {
+            continue;
+        }
+        let note = Input::<String>::with_theme(&ColorfulTheme::default())
+            .with_prompt("Note (optional)")
+            .allow_empty(true)
+            .interact_text()?;
+
+        rate_recent_restaurant(
+            visit.homie_name.clone(),
+            visit.restaurant_name.clone(),
+            Some(visit.date),
+            Some(visit.meal),
+            VisitRating::try_from(rating as i32)?,
+            Some(note),
+            user_id,
+            db,
+        )
+        .await?;
+    }
+
+    Ok(())
+}
+
+/// Records the pick, asking first when some of the homies already went somewhere else
+/// for the meal today. Returns whether the pick was recorded.
+#[tracing::instrument(skip(db))]
+pub async fn record_pick_interactive<T>(
+    home_homies: &[&Homie],
+    restaurant_id: RestaurantId,
+    restaurant_name: &str,
+    meal: MealSlot,
+    user_id: impl Into<UserId> + Debug,
+    db: &T,
+) -> Result<bool>
+where
+    T: AddRecentRestaurantToHomie,
+{
+    let user_id = user_id.into();
+    let homie_ids = home_homies.iter().map(|h| h.id).collect::<Vec<_>>();
+    let replaced = get_todays_picks(homie_ids.clone(), meal, user_id, db)
+        .await?
+        .into_iter()
+        .filter(|p| p.restaurant_id != restaurant_id)
+        .collect::<Vec<_>>();
+    if !replaced.is_empty() {
+        let mut restaurant_names = replaced
+            .iter()
+            .map(|p| p.restaurant_name.as_str())
+            .collect::<Vec<_>>();
+        restaurant_names.dedup();
+        if Select::with_theme(&ColorfulTheme::default())
+            .with_prompt(format!(
+                "Already picked {} for today's {} of {}, replace it with {}?",
+                restaurant_names.join(", "),
+                meal,
+                replaced
+                    .iter()
+                    .map(|p| p.homie_name.as_str())
+                    .collect::<Vec<_>>()
+                    .join(", "),
+                restaurant_name
+            ))
+            .items(&["Yes", "No"])
+            .default(0)
+            .interact()?
+            == 1
+        {
+            return Ok(false);
+        }
+    }
+
+    add_recent_restaurant_for_homies(
+        homie_ids,
+        restaurant_id,
+        meal,
+        Local::now().date_naive(),
+        true,
+        user_id,
+        db,
+    )
+    .await?;
+    Ok(true)
+}
+
+/// Offers to add a wishlisted restaurant to the favorites of the homies who just went.
+#[tracing::instrument(name = "User Promotes Wishlisted Restaurant", skip(db))]
+pub async fn promote_wishlisted_interactive<T>(
+    homie_names: &[&str],
+    restaurant_name: &str,
+    user_id: impl Into<UserId> + Debug,
+    db: &T,
+) -> Result<()>
+where
+    T: AddFavoriteRestaurantToHomie,
+{
+    let user_id = user_id.into();
+    if Select::with_theme(&ColorfulTheme::default())
+        .with_prompt(format!(
+            "First visit to {} from the wishlist, add it to the favorites of {}?",
+            restaurant_name,
+            homie_names.join(", ")
+        ))
+        .items(&["Yes", "No"])
+        .default(0)
+        .interact()?
+        == 1
+    {
+        return Ok(());
+    }
+
+    for homie_name in homie_names {
+        match add_homies_favorite_restaurant(
+            homie_name.to_string(),
+            restaurant_name.to_string(),
+            user_id,
+            db,
+        )
+        .await
+        {
+            Ok(()) | Err(AddHomiesFavoriteRestaurantError::HomieAlreadyHasFavorite { .. }) => {}
+            Err(e) => return Err(e.into()),
+        }
+    }
+
     Ok(())
 }
 
@@ -184,9 +575,7 @@ pub async fn get_favorite_restaurants(homies: &[Homie]) -> Result<Vec<&Homie>> {
     }
     let homies_names = homies
         .iter()
-        .map(|h| {
-            return h.name.as_str();
-        })
+        .map(|h| h.name.as_str())
         .collect::<Vec<&str>>();
 
     let chosen = MultiSelect::with_theme(&ColorfulTheme::default())
@@ -205,9 +594,7 @@ pub async fn get_favorite_restaurants(homies: &[Homie]) -> Result<Vec<&Homie>> {
 pub fn select_homie(homies: &Vec<Homie>) -> Result<&Homie> {
     let homies_names = homies
         .iter()
-        .map(|h| {
-            return h.name.as_str();
-        })
+        .map(|h| h.name.as_str())
         .collect::<Vec<&str>>();
 
     let chosen = Select::with_theme(&ColorfulTheme::default())
@@ -246,43 +633,277 @@ where
     Ok(get_all_homies(user_id, db).await?)
 }
 
-#[tracing::instrument(name = "User Selects Home Homies", skip(homies))]
-pub async fn get_home_homies(homies: &[Homie]) -> Result<Vec<&Homie>> {
+/// Asks who's home, offering the groups first and pre-selecting whoever was home last time.
+///
+/// Homies who are away today are left out of groups and aren't pre-selected. With a
+/// `group` the prompt is skipped. The selection is remembered for the next pick.
+#[tracing::instrument(name = "User Selects Home Homies", skip(homies, db))]
+pub async fn get_home_homies<'a, T>(
+    homies: &'a [Homie],
+    group: Option<&str>,
+    user_id: impl Into<UserId> + Debug,
+    db: &T,
+) -> Result<Vec<&'a Homie>>
+where
+    T: HomieGroups + HomieAvailability,
+{
     if homies.is_empty() {
         tracing::error!("No homies found");
         panic!();
     }
-    let homies_names = homies
+    let user_id = user_id.into();
+    let available = get_available_homies(
+        homies.iter().map(|h| h.id).collect::<Vec<_>>(),
+        Local::now().date_naive(),
+        user_id,
+        db,
+    )
+    .await?;
+    let available_homies = homies
         .iter()
-        .map(|h| {
-            return h.name.as_str();
-        })
-        .collect::<Vec<&str>>();
+        .filter(|h| available.contains(&h.id))
+        .collect::<Vec<&Homie>>();
 
-    let chosen = MultiSelect::with_theme(&ColorfulTheme::default())
-        .with_prompt("Who's home?")
-        .items(&homies_names)
-        .interact()?;
-    if chosen.is_empty() {
-        println!("No homies selected");
-        return Ok(homies.iter().collect());
+    let group = match group {
+        Some(group) => get_homie_group(group, user_id, db).await?,
+        None => {
+            let last = get_last_home_homies(user_id, db).await?;
+            let groups = get_homie_groups(user_id, db).await?;
+            let chosen_group = match groups.is_empty() {
+                true => None,
+                false => {
+                    let mut items = groups.iter().map(|g| g.to_string()).collect::<Vec<_>>();
+                    items.push("Someone else...".to_string());
+                    let chosen = Select::with_theme(&ColorfulTheme::default())
+                        .with_prompt("Who's home?")
+                        .items(&items)
+                        .default(
+                            groups
+                                .iter()
+                                .position(|g| g.is(&last))
+                                .unwrap_or(groups.len()),
+                        )
+                        .interact()?;
+                    groups.into_iter().nth(chosen)
+                }
+            };
+            let Some(group) = chosen_group else {
+                let homies_names = homies
+                    .iter()
+                    .map(|h| h.name.as_str())
+                    .collect::<Vec<&str>>();
+                let defaults = homies
+                    .iter()
+                    .map(|h| available.contains(&h.id) && (last.is_empty() || last.contains(&h.id)))
+                    .collect::<Vec<bool>>();
+
+                let chosen = MultiSelect::with_theme(&ColorfulTheme::default())
+                    .with_prompt("Who's home?")
+                    .items(&homies_names)
+                    .defaults(&defaults)
+                    .interact()?;
+                if chosen.is_empty() {
+                    println!("No homies selected");
+                    return Ok(available_homies);
+                }
+                let home_homies = chosen
+                    .iter()
+                    .map(|&index| &homies[index])
+                    .collect::<Vec<_>>();
+                let homie_ids = home_homies.iter().map(|h| h.id).collect::<Vec<_>>();
+                remember_home_homies(homie_ids, user_id, db).await?;
+                return Ok(home_homies);
+            };
+            group
+        }
+    };
+
+    remember_home_homies(group.homie_ids.clone(), user_id, db).await?;
+    let (home_homies, away): (Vec<&Homie>, Vec<&Homie>) = homies
+        .iter()
+        .filter(|h| group.homie_ids.contains(&h.id))
+        .partition(|h| available.contains(&h.id));
+    for homie in away {
+        println!("{} is away today", homie.name.as_str());
     }
-    let home_homies = chosen.iter().map(|&index| &homies[index]).collect();
     Ok(home_homies)
 }
 
+/// A restaurant's name with how far it is from `origin` and whether to call ahead.
+pub fn restaurant_label(
+    restaurant: &Restaurant,
+    party_size: usize,
+    origin: Option<Coordinates>,
+) -> String {
+    let notes = [
+        origin
+            .zip(restaurant.coordinates)
+            .map(|(origin, at)| format_distance(origin.distance_km(&at))),
+        restaurant
+            .needs_reservation(party_size)
+            .then(|| "call ahead".to_string()),
+    ]
+    .into_iter()
+    .flatten()
+    .collect::<Vec<_>>();
+    match notes.is_empty() {
+        true => restaurant.name.as_str().to_string(),
+        false => format!("{} ({})", restaurant.name.as_str(), notes.join(", ")),
+    }
+}
+
 #[tracing::instrument(name = "User Selects Restarant From List", skip(restaurants))]
-pub async fn select_restaurant(restaurants: &[Restaurant]) -> Result<&Restaurant> {
-    let restaurant_names = restaurants
+pub async fn select_restaurant(
+    restaurants: &[Restaurant],
+    origin: Option<Coordinates>,
+) -> Result<&Restaurant> {
+    let labels = restaurants
         .iter()
-        .map(|h| {
-            return h.name.as_str();
-        })
-        .collect::<Vec<&str>>();
+        .map(|r| restaurant_label(r, 0, origin))
+        .collect::<Vec<String>>();
     let chosen = Select::new()
         .with_prompt("where would you like to eat?")
-        .items(&restaurant_names)
+        .items(&labels)
         .interact()?;
 
     Ok(&restaurants[chosen])
 }
+
+/// Like [`select_restaurant`], with an extra option to reroll while `rerolls_left` allows.
+///
+/// Restaurants where a party of `party_size` should call ahead are marked as such.
+///
+/// Returns the index of the chosen restaurant, or `None` when the list was rerolled.
+#[tracing::instrument(name = "User Selects Restaurant Or Rerolls", skip(restaurants))]
+pub async fn select_restaurant_or_reroll(
+    restaurants: &[Restaurant],
+    rerolls_left: u32,
+    party_size: usize,
+    origin: Option<Coordinates>,
+) -> Result<Option<usize>> {
+    let mut items = restaurants
+        .iter()
+        .map(|r| restaurant_label(r, party_size, origin))
+        .collect::<Vec<String>>();
+    if rerolls_left > 0 {
+        items.push(format!("Not these, reroll ({} left)", rerolls_left));
+    }
+    let chosen = Select::new()
+        .with_prompt("where would you like to eat?")
+        .items(&items)
+        .interact()?;
+
+    Ok((chosen < restaurants.len()).then_some(chosen))
+}
+
+/// How many of the candidates the home homies vote on.
+const VOTE_SHORTLIST_SIZE: usize = 5;
+
+/// Lets every home homie vote on the first few candidates in turn and prints the tally,
+/// returning the winner and the shortlist that was voted on.
+#[tracing::instrument(name = "Home Homies Vote On Restaurant", skip(homies, restaurants))]
+pub async fn vote_restaurant<'a>(
+    homies: &[&Homie],
+    restaurants: &'a [Restaurant],
+    method: VotingMethod,
+) -> Result<(&'a Restaurant, &'a [Restaurant])> {
+    let shortlist = &restaurants[..restaurants.len().min(VOTE_SHORTLIST_SIZE)];
+    if shortlist.is_empty() {
+        return Err(anyhow::anyhow!("No restaurants to vote on"));
+    }
+    let names = shortlist
+        .iter()
+        .map(|r| r.name.as_str())
+        .collect::<Vec<&str>>();
+
+    let mut ballots = Vec::new();
+    for homie in homies {
+        let ballot = match method.is_ranked() {
+            true => {
+                let mut ranking = Vec::new();
+                while ranking.len() < shortlist.len() {
+                    let left = (0..shortlist.len())
+                        .filter(|index| !ranking.contains(index))
+                        .collect::<Vec<usize>>();
+                    if left.len() == 1 {
+                        ranking.push(left[0]);
+                        break;
+                    }
+                    let chosen = Select::with_theme(&ColorfulTheme::default())
+                        .with_prompt(format!(
+                            "{}, what is your choice #{}?",
+                            homie.name.as_str(),
+                            ranking.len() + 1
+                        ))
+                        .items(&left.iter().map(|&i| names[i]).collect::<Vec<&str>>())
+                        .interact()?;
+                    ranking.push(left[chosen]);
+                }
+                Ballot(ranking)
+            }
+            false => Ballot(
+                MultiSelect::with_theme(&ColorfulTheme::default())
+                    .with_prompt(format!(
+                        "{}, where would you be happy to eat?",
+                        homie.name.as_str()
+                    ))
+                    .items(&names)
+                    .interact()?,
+            ),
+        };
+        ballots.push(ballot);
+    }
+
+    let tally = tally(method, shortlist.len(), &ballots)
+        .ok_or_else(|| anyhow::anyhow!("No restaurants to vote on"))?;
+    for (number, round) in tally.rounds.iter().enumerate() {
+        if tally.rounds.len() > 1 {
+            println!("Round {}", number + 1);
+        }
+        for (index, points) in round {
+            println!("    {}: {}", names[*index], points);
+        }
+    }
+    println!("{} wins ({})", names[tally.winner], tally.method);
+
+    Ok((&shortlist[tally.winner], shortlist))
+}
+
+/// Narrows the candidates down through head-to-head prompts, returning the winner and
+/// the outcome of every matchup along the way.
+///
+/// Whoever is left over in a round with an odd number of restaurants goes through
+/// to the next round without a matchup.
+#[tracing::instrument(name = "User Picks Restaurant In Bracket", skip(restaurants))]
+pub async fn bracket_restaurant(restaurants: &[Restaurant]) -> Result<(&Restaurant, Vec<Matchup>)> {
+    let mut round = restaurants.iter().collect::<Vec<&Restaurant>>();
+    let mut matchups = Vec::new();
+    while round.len() > 1 {
+        let mut next_round = Vec::new();
+        for pair in round.chunks(2) {
+            let [first, second] = pair else {
+                next_round.push(pair[0]);
+                continue;
+            };
+            let chosen = Select::with_theme(&ColorfulTheme::default())
+                .with_prompt("This or that?")
+                .items(&[first.name.as_str(), second.name.as_str()])
+                .interact()?;
+            let (winner, loser) = match chosen {
+                0 => (first, second),
+                _ => (second, first),
+            };
+            matchups.push(Matchup {
+                winner: winner.id,
+                loser: loser.id,
+            });
+            next_round.push(*winner);
+        }
+        round = next_round;
+    }
+
+    let winner = round
+        .first()
+        .ok_or_else(|| anyhow::anyhow!("No restaurants to pick from"))?;
+    Ok((winner, matchups))
+}
@@ -1,4 +1,8 @@
 use anyhow::Result;
+use chrono::Datelike;
+use chrono::Days;
+use chrono::Local;
+use chrono::Weekday;
 use clap::Parser;
 use lunch_picker::add_homies_favorite_restaurants_interactive;
 use lunch_picker::add_homies_interactive;
@@ -6,25 +10,93 @@ use lunch_picker::add_restaurants_interactive;
 use lunch_picker::cli_args::AddRestaurant;
 use lunch_picker::cli_args::CliArgs;
 use lunch_picker::cli_args::Command;
+use lunch_picker::cli_args::Fairness;
+use lunch_picker::cli_args::Groups;
+use lunch_picker::cli_args::HomieDiet;
 use lunch_picker::cli_args::Homies;
+use lunch_picker::cli_args::Locations;
+use lunch_picker::cli_args::PickArgs;
+use lunch_picker::cli_args::Plan;
+use lunch_picker::cli_args::RateArgs;
+use lunch_picker::cli_args::Recents;
 use lunch_picker::cli_args::Recipes;
+use lunch_picker::cli_args::RestaurantAccommodations;
+use lunch_picker::cli_args::RestaurantTags;
 use lunch_picker::cli_args::Restaurants;
+use lunch_picker::cli_args::Rules;
+use lunch_picker::cli_args::SimulateArgs;
+use lunch_picker::cli_args::VetoRestaurant;
+use lunch_picker::cli_args::Wishlist;
 use lunch_picker::db::Migrator;
+use lunch_picker::features::add_homie_away_period;
+use lunch_picker::features::add_homie_group;
+use lunch_picker::features::add_homies_dietary_restriction;
 use lunch_picker::features::add_homies_favorite_restaurant;
+use lunch_picker::features::add_homies_vetoed_restaurant;
+use lunch_picker::features::add_lunch_rule;
 use lunch_picker::features::add_recent_restaurant_for_homie;
-use lunch_picker::features::add_recent_restaurant_for_homies;
+use lunch_picker::features::add_restaurant_accommodation;
+use lunch_picker::features::add_restaurant_tag;
+use lunch_picker::features::add_to_wishlist;
 use lunch_picker::features::create_homie;
+use lunch_picker::features::edit_homie_group;
 use lunch_picker::*;
 use sqlx::migrate::MigrateDatabase;
 use std::fs;
 // use lunch_picker::features::create_recipe;
 use lunch_picker::features::create_restaurant;
+use lunch_picker::features::format_distance;
+use lunch_picker::features::generate_lunch_plan;
 use lunch_picker::features::get_all_homies;
+use lunch_picker::features::get_all_restaurants;
 use lunch_picker::features::get_candidate_restaurants;
+use lunch_picker::features::get_dietary_excluded_favorites;
+use lunch_picker::features::get_exploration_restaurant;
+use lunch_picker::features::get_fairness_boosts;
+use lunch_picker::features::get_fairness_report;
+use lunch_picker::features::get_homie_away_periods;
+use lunch_picker::features::get_homie_groups;
+use lunch_picker::features::get_lunch_plan;
+use lunch_picker::features::get_lunch_rules;
+use lunch_picker::features::get_lunch_rules_on;
+use lunch_picker::features::get_planned_lunch;
+use lunch_picker::features::get_recent_visits;
+use lunch_picker::features::get_restaurant;
+use lunch_picker::features::get_restaurant_opening_hours;
+use lunch_picker::features::get_user_location;
+use lunch_picker::features::get_user_locations;
+use lunch_picker::features::get_wishlist;
+use lunch_picker::features::import_calendar_away_periods;
+use lunch_picker::features::rate_recent_restaurant;
+use lunch_picker::features::record_restaurant_matchups;
+use lunch_picker::features::record_selection_event;
+use lunch_picker::features::reject_restaurants;
+use lunch_picker::features::remove_homies_dietary_restriction;
 use lunch_picker::features::remove_homies_favorite_restaurant;
+use lunch_picker::features::remove_homies_vetoed_restaurant;
+use lunch_picker::features::remove_lunch_rule;
+use lunch_picker::features::remove_restaurant_accommodation;
+use lunch_picker::features::remove_restaurant_tag;
+use lunch_picker::features::remove_user_location;
+use lunch_picker::features::set_homies_favorite_restaurant_strength;
+use lunch_picker::features::set_restaurant_location;
+use lunch_picker::features::set_restaurant_opening_hours;
+use lunch_picker::features::set_restaurant_party_size;
+use lunch_picker::features::set_restaurant_price_tier;
+use lunch_picker::features::set_restaurant_scope;
+use lunch_picker::features::set_restaurant_service_modes;
+use lunch_picker::features::set_user_location;
+use lunch_picker::features::simulate;
+use lunch_picker::features::undo_last_pick;
+use lunch_picker::features::CalendarMapping;
+use lunch_picker::features::CandidateOptions;
 use lunch_picker::features::Homie;
+use lunch_picker::features::MealSlot;
+use lunch_picker::features::Restaurant;
+use lunch_picker::features::SimulationSettings;
+use lunch_picker::features::UserLocation;
+use lunch_picker::features::EXPLORATION_RATE;
 use lunch_picker::get_home_homies;
-use lunch_picker::select_restaurant;
 use opentelemetry::trace::TraceError;
 use opentelemetry::KeyValue;
 use opentelemetry_otlp::WithExportConfig;
@@ -66,28 +138,108 @@ pub(crate) fn init_tracer() -> Result<opentelemetry_sdk::trace::Tracer, TraceErr
 
 struct AppState {
     db: Pool<Sqlite>,
+    prompt_for_ratings: bool,
+    reroll_budget: u32,
+    calendar: CalendarMapping,
+    /// Where lunch is, only its restaurants are dealt with
+    location: Option<UserLocation>,
 }
 
 impl AppState {
-    fn new(db: Pool<Sqlite>) -> Self {
-        Self { db }
+    fn new(db: Pool<Sqlite>, settings: &Settings, location: Option<UserLocation>) -> Self {
+        Self {
+            db,
+            prompt_for_ratings: settings.prompt_for_ratings,
+            reroll_budget: settings.reroll_budget,
+            calendar: settings.calendar.clone(),
+            location,
+        }
+    }
+
+    fn location_id(&self) -> Option<i64> {
+        self.location.as_ref().map(|l| l.id)
+    }
+
+    /// The candidates for the home homies, led by the lunch planned for the day when
+    /// picking lunch and something new to try when exploring.
+    async fn shortlist(
+        &self,
+        home_homies: &[&Homie],
+        options: &CandidateOptions,
+        explore: bool,
+    ) -> Result<Vec<Restaurant>> {
+        let mut restaurants =
+            get_candidate_restaurants(home_homies.to_vec(), options, CLI_USER_ID, &self.db).await?;
+        if explore {
+            if let Some(new) =
+                get_exploration_restaurant(home_homies.to_vec(), options, CLI_USER_ID, &self.db)
+                    .await?
+            {
+                println!("Something new to try: {}", new.name.as_str());
+                restaurants.insert(0, new);
+            }
+        }
+        let date = options
+            .at
+            .map_or_else(|| Local::now().date_naive(), |at| at.date());
+        let planned = match options.meal {
+            MealSlot::Lunch => get_planned_lunch(date, CLI_USER_ID, &self.db).await?,
+            _ => None,
+        };
+        if let Some(planned) = planned {
+            if !options.exclude.contains(&planned.restaurant_id) {
+                match restaurants
+                    .iter()
+                    .position(|r| r.id == planned.restaurant_id)
+                {
+                    Some(index) => {
+                        let restaurant = restaurants.remove(index);
+                        println!("Planned for today: {}", restaurant.name.as_str());
+                        restaurants.insert(0, restaurant);
+                    }
+                    None => println!("Planned {} is not available today", planned.restaurant_name),
+                }
+            }
+        }
+        Ok(restaurants)
     }
 
     #[tracing::instrument(name = "User Interaction", skip(self))]
-    async fn work(&self) -> Result<()> {
+    async fn work(&self, pick_args: &PickArgs) -> Result<()> {
+        let mut options: CandidateOptions = pick_args.into();
+        options.location_id = self.location_id();
+        options.origin = self.location.as_ref().map(|l| l.coordinates);
+        if let Some(from) = &pick_args.from {
+            options.origin = Some(
+                get_user_location(from, CLI_USER_ID, &self.db)
+                    .await?
+                    .coordinates,
+            );
+        }
+        let explore = pick_args.explore || rand::random::<f64>() < EXPLORATION_RATE;
         let mut homies: Vec<Homie> = get_all_homies(1, &self.db).await?;
         if homies.is_empty() {
             event!(Level::ERROR, "No homies found");
             homies = add_homies_interactive(CLI_USER_ID, &self.db).await?;
-            add_restaurants_interactive(CLI_USER_ID, &self.db).await?;
+            add_restaurants_interactive(self.location_id(), CLI_USER_ID, &self.db).await?;
         }
 
-        let home_homies = get_home_homies(&homies).await?;
-        let mut restaurants = get_candidate_restaurants(home_homies.clone(), 1, &self.db).await?;
+        if self.prompt_for_ratings {
+            rate_recent_visits_interactive(false, true, CLI_USER_ID, &self.db).await?;
+        }
+
+        let home_homies =
+            get_home_homies(&homies, pick_args.group.as_deref(), CLI_USER_ID, &self.db).await?;
+        for guest in &pick_args.guests {
+            println!("Joined by {}", guest);
+        }
+        let party_size = home_homies.len() + pick_args.guests.len();
+        options.party_size = Some(party_size as u32);
+        let mut restaurants = self.shortlist(&home_homies, &options, explore).await?;
         if restaurants.is_empty() {
             event!(Level::ERROR, "No candidate restaurants found");
-            add_restaurants_interactive(CLI_USER_ID, &self.db).await?;
-            restaurants = get_candidate_restaurants(home_homies.clone(), 1, &self.db).await?;
+            add_restaurants_interactive(self.location_id(), CLI_USER_ID, &self.db).await?;
+            restaurants = self.shortlist(&home_homies, &options, explore).await?;
         }
 
         if restaurants.is_empty() {
@@ -95,19 +247,119 @@ impl AppState {
                 Level::ERROR,
                 "User did not add any restaurants that produced candidates"
             );
-            add_restaurants_interactive(CLI_USER_ID, &self.db).await?;
-            restaurants = get_candidate_restaurants(home_homies.clone(), 1, &self.db).await?;
+            add_restaurants_interactive(self.location_id(), CLI_USER_ID, &self.db).await?;
+            restaurants = self.shortlist(&home_homies, &options, explore).await?;
+        }
+
+        if pick_args.explain {
+            for boost in get_fairness_boosts(
+                home_homies.clone(),
+                options
+                    .at
+                    .map_or_else(|| Local::now().date_naive(), |at| at.date()),
+                CLI_USER_ID,
+                &self.db,
+            )
+            .await?
+            {
+                println!("{}", boost);
+            }
         }
 
-        let selected = select_restaurant(&restaurants).await?;
+        for excluded in get_dietary_excluded_favorites(home_homies.clone(), 1, &self.db).await? {
+            println!("{}", excluded);
+        }
+
+        let (selected, shown) = match pick_args.vote {
+            Some(method) => vote_restaurant(&home_homies, &restaurants, method).await?,
+            None if pick_args.bracket => {
+                let (winner, matchups) = bracket_restaurant(&restaurants).await?;
+                record_restaurant_matchups(home_homies.clone(), &matchups, CLI_USER_ID, &self.db)
+                    .await?;
+                (winner, restaurants.as_slice())
+            }
+            None => {
+                let mut rerolls_left = pick_args.rerolls.unwrap_or(self.reroll_budget);
+                let chosen = loop {
+                    if let Some(chosen) = select_restaurant_or_reroll(
+                        &restaurants,
+                        rerolls_left,
+                        party_size,
+                        options.origin,
+                    )
+                    .await?
+                    {
+                        break chosen;
+                    }
+                    let rejected = restaurants.iter().map(|r| r.id).collect::<Vec<_>>();
+                    reject_restaurants(&rejected, CLI_USER_ID, &self.db).await?;
+                    options.exclude.extend(rejected);
+                    rerolls_left -= 1;
+
+                    let rerolled = self.shortlist(&home_homies, &options, explore).await?;
+                    match rerolled.is_empty() {
+                        true => {
+                            println!("Nothing left to reroll");
+                            rerolls_left = 0;
+                        }
+                        false => restaurants = rerolled,
+                    }
+                };
+                (&restaurants[chosen], restaurants.as_slice())
+            }
+        };
+        // however the pick was made, the winner beat the rest of what was offered
+        record_selection_event(
+            home_homies.clone(),
+            &shown.iter().map(|r| r.id).collect::<Vec<_>>(),
+            selected.id,
+            CLI_USER_ID,
+            &self.db,
+        )
+        .await?;
 
         event!(
             Level::INFO,
             name = "Selected restaurant",
             restaurant_name = selected.name.as_str()
         );
+        if let Some(above) = selected
+            .reservations_above
+            .filter(|_| selected.needs_reservation(party_size))
+        {
+            println!(
+                "Call ahead, {} recommends a reservation for more than {} people",
+                selected.name.as_str(),
+                above
+            );
+        }
 
-        add_recent_restaurant_for_homies(home_homies, selected.id, CLI_USER_ID, &self.db).await?;
+        let wishlisted = get_wishlist(CLI_USER_ID, &self.db)
+            .await?
+            .iter()
+            .any(|w| w.restaurant_id == selected.id);
+        let homie_names = home_homies
+            .iter()
+            .map(|h| h.name.as_str())
+            .collect::<Vec<&str>>();
+        let recorded = record_pick_interactive(
+            &home_homies,
+            selected.id,
+            selected.name.as_str(),
+            options.meal,
+            CLI_USER_ID,
+            &self.db,
+        )
+        .await?;
+        if recorded && wishlisted {
+            promote_wishlisted_interactive(
+                &homie_names,
+                selected.name.as_str(),
+                CLI_USER_ID,
+                &self.db,
+            )
+            .await?;
+        }
 
         Ok(())
     }
@@ -158,7 +410,8 @@ async fn main() -> Result<()> {
         tracing::subscriber::set_global_default(subscriber).unwrap();
     }
 
-    let database_url = std::env::var("DATABASE_URL").unwrap_or(settings.database_url);
+    let database_url =
+        std::env::var("DATABASE_URL").unwrap_or_else(|_| settings.database_url.clone());
     if !sqlx::Sqlite::database_exists(&database_url).await? {
         sqlx::Sqlite::create_database(&database_url).await?;
     }
@@ -172,7 +425,11 @@ async fn main() -> Result<()> {
 
     db.migrate().await?;
 
-    let app_state = AppState::new(db);
+    let location = match args.location.or(settings.current_location.clone()) {
+        Some(name) => Some(get_user_location(&name, CLI_USER_ID, &db).await?),
+        None => None,
+    };
+    let app_state = AppState::new(db, &settings, location);
 
     match args.command {
         Some(cmd) => match cmd {
@@ -224,13 +481,27 @@ async fn main() -> Result<()> {
                         homie_name,
                         restaurant_name,
                     } => {
+                        let wishlisted = get_wishlist(CLI_USER_ID, &app_state.db)
+                            .await?
+                            .iter()
+                            .any(|w| w.restaurant_name == restaurant_name);
                         add_recent_restaurant_for_homie(
-                            homie_name,
-                            restaurant_name,
+                            homie_name.clone(),
+                            restaurant_name.clone(),
+                            MealSlot::default(),
                             1,
                             &app_state.db,
                         )
                         .await?;
+                        if wishlisted {
+                            promote_wishlisted_interactive(
+                                &[homie_name.as_str()],
+                                &restaurant_name,
+                                CLI_USER_ID,
+                                &app_state.db,
+                            )
+                            .await?;
+                        }
                     }
                     AddRestaurant::Delete {
                         homie_name,
@@ -245,30 +516,585 @@ async fn main() -> Result<()> {
                         .await?
                     } // _ => println!("Restaurant command"),
                 },
+                Homies::Strength {
+                    homie_name,
+                    restaurant_name,
+                    strength,
+                } => {
+                    set_homies_favorite_restaurant_strength(
+                        homie_name,
+                        restaurant_name,
+                        strength.try_into()?,
+                        CLI_USER_ID,
+                        &app_state.db,
+                    )
+                    .await?
+                }
+                Homies::Diet(diet_command) => match diet_command {
+                    HomieDiet::Add {
+                        homie_name,
+                        restriction,
+                    } => {
+                        add_homies_dietary_restriction(
+                            homie_name.clone(),
+                            restriction,
+                            CLI_USER_ID,
+                            &app_state.db,
+                        )
+                        .await?;
+                        println!("{} is now {}", homie_name, restriction)
+                    }
+                    HomieDiet::Delete {
+                        homie_name,
+                        restriction,
+                    } => {
+                        remove_homies_dietary_restriction(
+                            homie_name,
+                            restriction,
+                            CLI_USER_ID,
+                            &app_state.db,
+                        )
+                        .await?
+                    }
+                },
+                Homies::Veto(veto_command) => match veto_command {
+                    VetoRestaurant::Add {
+                        homie_name,
+                        restaurant_name,
+                    } => {
+                        add_homies_vetoed_restaurant(
+                            homie_name.clone(),
+                            restaurant_name.clone(),
+                            CLI_USER_ID,
+                            &app_state.db,
+                        )
+                        .await?;
+                        println!("{} vetoed restaurant {}", homie_name, restaurant_name)
+                    }
+                    VetoRestaurant::Remove {
+                        homie_name,
+                        restaurant_name,
+                    } => {
+                        remove_homies_vetoed_restaurant(
+                            homie_name,
+                            restaurant_name,
+                            CLI_USER_ID,
+                            &app_state.db,
+                        )
+                        .await?
+                    }
+                },
+                Homies::Away { homie_name, period } => {
+                    add_homie_away_period(homie_name.clone(), period, CLI_USER_ID, &app_state.db)
+                        .await?;
+                    println!("{} is away {}", homie_name, period);
+                }
+                Homies::ImportCalendar { files } => {
+                    let mut imported = Vec::new();
+                    for file in files {
+                        let ics = fs::read_to_string(&file)?;
+                        imported.extend(
+                            import_calendar_away_periods(
+                                &ics,
+                                &app_state.calendar,
+                                CLI_USER_ID,
+                                &app_state.db,
+                            )
+                            .await?,
+                        );
+                    }
+                    if imported.is_empty() {
+                        println!("No new away periods found");
+                    }
+                    for away in imported {
+                        println!("{}", away);
+                    }
+                }
+                Homies::Availability => {
+                    let today = Local::now().date_naive();
+                    for away in get_homie_away_periods(today, CLI_USER_ID, &app_state.db).await? {
+                        println!("{}", away);
+                    }
+                }
                 Homies::Interactive => {
-                    add_homies_favorite_restaurants_interactive(CLI_USER_ID, &app_state.db).await?;
+                    add_homies_favorite_restaurants_interactive(
+                        app_state.location_id(),
+                        CLI_USER_ID,
+                        &app_state.db,
+                    )
+                    .await?;
                 }
             },
 
+            Command::Rate(RateArgs {
+                homie_name: Some(homie_name),
+                restaurant_name: Some(restaurant_name),
+                rating: Some(rating),
+                note,
+                date,
+                meal,
+            }) => {
+                rate_recent_restaurant(
+                    homie_name,
+                    restaurant_name,
+                    date,
+                    meal,
+                    rating.try_into()?,
+                    note,
+                    CLI_USER_ID,
+                    &app_state.db,
+                )
+                .await?
+            }
+            Command::Rate(_) => {
+                rate_recent_visits_interactive(true, false, CLI_USER_ID, &app_state.db).await?
+            }
+            Command::UndoLastPick => {
+                let undone = undo_last_pick(CLI_USER_ID, &app_state.db).await?;
+                println!(
+                    "Took back {} for the {} of {} with {}",
+                    undone.restaurant_name,
+                    undone.meal,
+                    undone.date,
+                    undone.homie_names.join(", ")
+                );
+            }
+            Command::Recents(Recents::List { days }) => {
+                let visits = get_recent_visits(days, CLI_USER_ID, &app_state.db).await?;
+                let mut lunches = visits
+                    .iter()
+                    .map(|v| (v.date, v.meal, v.restaurant_name.as_str()))
+                    .collect::<Vec<_>>();
+                lunches.dedup();
+                for (date, meal, restaurant_name) in lunches {
+                    match meal {
+                        MealSlot::Lunch => println!("{} {}", date, restaurant_name),
+                        _ => println!("{} {} ({})", date, restaurant_name, meal),
+                    }
+                    for visit in visits.iter().filter(|v| {
+                        v.date == date && v.meal == meal && v.restaurant_name == restaurant_name
+                    }) {
+                        let rating = visit
+                            .rating
+                            .map_or("unrated".to_string(), |r| format!("{}/5", r.as_u8()));
+                        match &visit.note {
+                            Some(note) => {
+                                println!("    {}: {} - {}", visit.homie_name, rating, note)
+                            }
+                            None => println!("    {}: {}", visit.homie_name, rating),
+                        }
+                    }
+                }
+            }
             Command::Restaurants(restaurant_command) => match restaurant_command {
                 Restaurants::Add { restaurant_name } => {
-                    create_restaurant(restaurant_name, CLI_USER_ID, &app_state.db).await?;
+                    create_restaurant(restaurant_name.clone(), CLI_USER_ID, &app_state.db).await?;
+                    if let Some(location) = &app_state.location {
+                        set_restaurant_scope(
+                            restaurant_name,
+                            Some(location.id),
+                            CLI_USER_ID,
+                            &app_state.db,
+                        )
+                        .await?;
+                    }
                 }
                 Restaurants::Delete { restaurant_name: _ } => todo!(),
                 Restaurants::Rename {
                     restaurant_name: _,
                     updated_name: _,
                 } => todo!(),
+                Restaurants::Tags(tag_command) => match tag_command {
+                    RestaurantTags::Add {
+                        restaurant_name,
+                        tag,
+                    } => {
+                        add_restaurant_tag(
+                            restaurant_name.clone(),
+                            tag.clone(),
+                            CLI_USER_ID,
+                            &app_state.db,
+                        )
+                        .await?;
+                        println!("Tagged restaurant {} with {}", restaurant_name, tag)
+                    }
+                    RestaurantTags::Delete {
+                        restaurant_name,
+                        tag,
+                    } => {
+                        remove_restaurant_tag(
+                            restaurant_name.clone(),
+                            tag.clone(),
+                            CLI_USER_ID,
+                            &app_state.db,
+                        )
+                        .await?;
+                        println!("Removed tag {} from restaurant {}", tag, restaurant_name)
+                    }
+                },
+                Restaurants::Price {
+                    restaurant_name,
+                    price_tier,
+                } => {
+                    set_restaurant_price_tier(
+                        restaurant_name,
+                        Some(price_tier.try_into()?),
+                        CLI_USER_ID,
+                        &app_state.db,
+                    )
+                    .await?;
+                }
+                Restaurants::Modes {
+                    restaurant_name,
+                    service_modes,
+                } => {
+                    set_restaurant_service_modes(
+                        restaurant_name,
+                        &service_modes,
+                        CLI_USER_ID,
+                        &app_state.db,
+                    )
+                    .await?;
+                }
+                Restaurants::List { from } => {
+                    let locations = match from {
+                        Some(from) => {
+                            vec![get_user_location(&from, CLI_USER_ID, &app_state.db).await?]
+                        }
+                        None => get_user_locations(CLI_USER_ID, &app_state.db).await?,
+                    };
+                    for restaurant in get_all_restaurants(CLI_USER_ID, &app_state.db)
+                        .await?
+                        .into_iter()
+                        .filter(|r| r.is_available_at(app_state.location_id()))
+                    {
+                        let distances = restaurant.coordinates.map(|at| {
+                            locations
+                                .iter()
+                                .map(|l| {
+                                    format!(
+                                        "{} from {}",
+                                        format_distance(l.coordinates.distance_km(&at)),
+                                        l.name
+                                    )
+                                })
+                                .collect::<Vec<_>>()
+                        });
+                        match distances.filter(|d| !d.is_empty()) {
+                            Some(distances) => {
+                                println!("{} ({})", restaurant.name.as_str(), distances.join(", "))
+                            }
+                            None => println!("{}", restaurant.name.as_str()),
+                        }
+                    }
+                }
+                Restaurants::Location {
+                    restaurant_name,
+                    coordinates,
+                } => {
+                    set_restaurant_location(
+                        restaurant_name,
+                        coordinates,
+                        CLI_USER_ID,
+                        &app_state.db,
+                    )
+                    .await?;
+                }
+                Restaurants::Scope {
+                    restaurant_name,
+                    location,
+                } => {
+                    let location_id = match location {
+                        Some(location) => Some(
+                            get_user_location(&location, CLI_USER_ID, &app_state.db)
+                                .await?
+                                .id,
+                        ),
+                        None => None,
+                    };
+                    set_restaurant_scope(restaurant_name, location_id, CLI_USER_ID, &app_state.db)
+                        .await?;
+                }
+                Restaurants::PartySize {
+                    restaurant_name,
+                    max,
+                    reservations_above,
+                } => {
+                    set_restaurant_party_size(
+                        restaurant_name,
+                        max,
+                        reservations_above,
+                        CLI_USER_ID,
+                        &app_state.db,
+                    )
+                    .await?;
+                }
+                Restaurants::Accommodates(accommodation_command) => match accommodation_command {
+                    RestaurantAccommodations::Add {
+                        restaurant_name,
+                        restriction,
+                    } => {
+                        add_restaurant_accommodation(
+                            restaurant_name,
+                            restriction,
+                            CLI_USER_ID,
+                            &app_state.db,
+                        )
+                        .await?
+                    }
+                    RestaurantAccommodations::Delete {
+                        restaurant_name,
+                        restriction,
+                    } => {
+                        remove_restaurant_accommodation(
+                            restaurant_name,
+                            restriction,
+                            CLI_USER_ID,
+                            &app_state.db,
+                        )
+                        .await?
+                    }
+                },
+                Restaurants::Hours {
+                    restaurant_name,
+                    opening_hours,
+                } => {
+                    set_restaurant_opening_hours(
+                        restaurant_name,
+                        &opening_hours,
+                        CLI_USER_ID,
+                        &app_state.db,
+                    )
+                    .await?;
+                }
+                Restaurants::Show { restaurant_name } => {
+                    let restaurant =
+                        get_restaurant(restaurant_name.clone(), CLI_USER_ID, &app_state.db).await?;
+                    let opening_hours =
+                        get_restaurant_opening_hours(restaurant_name, CLI_USER_ID, &app_state.db)
+                            .await?;
+                    let view = restaurant.as_view();
+                    println!("{}", view.name);
+                    println!("    tags: {}", view.tags.join(", "));
+                    println!(
+                        "    price: {}",
+                        restaurant
+                            .price_tier
+                            .map_or("unknown".to_string(), |p| p.to_string())
+                    );
+                    println!(
+                        "    party size: {}",
+                        match (restaurant.max_party_size, restaurant.reservations_above) {
+                            (None, None) => "unknown".to_string(),
+                            (max, above) => [
+                                max.map(|m| format!("up to {}", m)),
+                                above.map(|a| format!("call ahead above {}", a)),
+                            ]
+                            .into_iter()
+                            .flatten()
+                            .collect::<Vec<_>>()
+                            .join(", "),
+                        }
+                    );
+                    println!(
+                        "    location: {}",
+                        restaurant
+                            .coordinates
+                            .map_or("unknown".to_string(), |c| c.to_string())
+                    );
+                    println!(
+                        "    service modes: {}",
+                        view.service_modes
+                            .iter()
+                            .map(|m| m.to_string())
+                            .collect::<Vec<String>>()
+                            .join(", ")
+                    );
+                    println!(
+                        "    accommodates: {}",
+                        view.accommodations
+                            .iter()
+                            .map(|a| a.to_string())
+                            .collect::<Vec<String>>()
+                            .join(", ")
+                    );
+                    match opening_hours.is_empty() {
+                        true => println!("    hours: unknown"),
+                        false => println!("    hours: {}", opening_hours),
+                    }
+                }
+                Restaurants::Interactive => {
+                    edit_restaurant_attributes_interactive(
+                        app_state.location_id(),
+                        CLI_USER_ID,
+                        &app_state.db,
+                    )
+                    .await?;
+                }
+                Restaurants::Wishlist(wishlist_command) => match wishlist_command {
+                    Wishlist::Add {
+                        restaurant_name,
+                        homie,
+                    } => {
+                        add_to_wishlist(
+                            restaurant_name.clone(),
+                            homie.clone(),
+                            CLI_USER_ID,
+                            &app_state.db,
+                        )
+                        .await?;
+                        match homie {
+                            Some(homie) => {
+                                println!("Added {} to {}'s wishlist", restaurant_name, homie)
+                            }
+                            None => println!("Added {} to the wishlist", restaurant_name),
+                        }
+                    }
+                    Wishlist::List => {
+                        for item in get_wishlist(CLI_USER_ID, &app_state.db).await? {
+                            match item.homie_name {
+                                Some(homie_name) => {
+                                    println!("{} ({})", item.restaurant_name, homie_name)
+                                }
+                                None => println!("{}", item.restaurant_name),
+                            }
+                        }
+                    }
+                },
             },
             Command::Recipes(recipe_command) => match recipe_command {
-                Recipes::Add { recipe_name } => {
+                Recipes::Add { recipe_name: _ } => {
                     // _ = create_recipe(recipe_name, 1, &app_state.db).await?;
                 }
                 _ => println!("Recipe command"),
             },
-            Command::Pick => app_state.work().await?,
+            Command::Pick(pick_args) => app_state.work(&pick_args).await?,
+            Command::Simulate(SimulateArgs {
+                days,
+                homies,
+                policy,
+                recency_window,
+                no_fairness,
+                no_elo,
+            }) => {
+                let all_homies = get_all_homies(CLI_USER_ID, &app_state.db).await?;
+                let mut homie_ids = Vec::new();
+                for name in &homies {
+                    match all_homies.iter().find(|h| h.name.as_str() == name.trim()) {
+                        Some(homie) => homie_ids.push(homie.id),
+                        None => anyhow::bail!("Homie not found: {:?}", name),
+                    }
+                }
+                if homies.is_empty() {
+                    homie_ids = all_homies.iter().map(|h| h.id).collect();
+                }
+                let settings = SimulationSettings {
+                    policy,
+                    recency_window,
+                    ignore_fairness: no_fairness,
+                    ignore_elo: no_elo,
+                };
+                let report = simulate(
+                    homie_ids,
+                    days,
+                    settings,
+                    app_state.location.as_ref(),
+                    CLI_USER_ID,
+                    &app_state.db,
+                )
+                .await?;
+                print!("{}", report);
+            }
+            Command::Plan(plan_command) => {
+                let today = Local::now().date_naive();
+                let this_week = match today.weekday() {
+                    Weekday::Sat | Weekday::Sun => today + Days::new(2),
+                    _ => today,
+                };
+                match plan_command {
+                    Plan::Generate { week, format } => {
+                        let homies = get_all_homies(CLI_USER_ID, &app_state.db).await?;
+                        let home_homies =
+                            get_home_homies(&homies, None, CLI_USER_ID, &app_state.db).await?;
+                        let plan = generate_lunch_plan(
+                            home_homies,
+                            week.unwrap_or(this_week),
+                            today,
+                            app_state.location.as_ref(),
+                            CLI_USER_ID,
+                            &app_state.db,
+                        )
+                        .await?;
+                        println!("{}", plan.export(format));
+                    }
+                    Plan::Show { week, format } => {
+                        let plan =
+                            get_lunch_plan(week.unwrap_or(this_week), CLI_USER_ID, &app_state.db)
+                                .await?;
+                        println!("{}", plan.export(format));
+                    }
+                }
+            }
+            Command::Groups(groups_command) => match groups_command {
+                Groups::Add { name, homies } => {
+                    let group = add_homie_group(&name, &homies, CLI_USER_ID, &app_state.db).await?;
+                    println!("Added {}", group);
+                }
+                Groups::Edit { name, homies } => {
+                    let group =
+                        edit_homie_group(&name, &homies, CLI_USER_ID, &app_state.db).await?;
+                    println!("Updated {}", group);
+                }
+                Groups::List => {
+                    for group in get_homie_groups(CLI_USER_ID, &app_state.db).await? {
+                        println!("{}", group);
+                    }
+                }
+            },
+            Command::Locations(locations_command) => match locations_command {
+                Locations::Set { name, coordinates } => {
+                    set_user_location(&name, coordinates, CLI_USER_ID, &app_state.db).await?;
+                }
+                Locations::Remove { name } => {
+                    remove_user_location(&name, CLI_USER_ID, &app_state.db).await?;
+                }
+                Locations::List => {
+                    for location in get_user_locations(CLI_USER_ID, &app_state.db).await? {
+                        println!("{}", location);
+                    }
+                }
+            },
+            Command::Rules(rules_command) => match rules_command {
+                Rules::List => {
+                    for rule in get_lunch_rules(CLI_USER_ID, &app_state.db).await? {
+                        println!("{}", rule);
+                    }
+                }
+                Rules::Add { rule } => {
+                    let rule = add_lunch_rule(&rule, CLI_USER_ID, &app_state.db).await?;
+                    println!("Added {}", rule);
+                }
+                Rules::Remove { id } => {
+                    remove_lunch_rule(id, CLI_USER_ID, &app_state.db).await?;
+                }
+                Rules::Test { date } => {
+                    let date = date.unwrap_or_else(|| Local::now().date_naive());
+                    let rules = get_lunch_rules_on(date, CLI_USER_ID, &app_state.db).await?;
+                    if rules.is_empty() {
+                        println!("No rules apply on {}", date);
+                    }
+                    for rule in rules {
+                        println!("{}", rule);
+                    }
+                }
+            },
+            Command::Fairness(Fairness::Report { weeks }) => {
+                for satisfaction in get_fairness_report(weeks, CLI_USER_ID, &app_state.db).await? {
+                    println!("{}", satisfaction);
+                }
+            }
         },
-        None => app_state.work().await?,
+        None => app_state.work(&PickArgs::default()).await?,
     }
 
     // app_state.db.close().await;
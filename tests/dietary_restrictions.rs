@@ -0,0 +1,96 @@
+#![cfg(feature = "sqlite_tests")]
+
+use anyhow::Result;
+use lunch_picker::features::add_homies_dietary_restriction;
+use lunch_picker::features::get_candidate_restaurants;
+use lunch_picker::features::get_dietary_excluded_favorites;
+use lunch_picker::features::remove_homies_dietary_restriction;
+use lunch_picker::features::CandidateOptions;
+use lunch_picker::features::DietaryRestriction;
+
+use sqlx::SqlitePool;
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants", "dietary_restrictions"))]
+async fn duplicate_restriction_cannot_be_added(pool: SqlitePool) -> Result<()> {
+    let actual = add_homies_dietary_restriction(
+        "Alice".to_string(),
+        DietaryRestriction::GlutenFree,
+        -1,
+        &pool,
+    )
+    .await;
+
+    assert_eq!(
+        format!("{:?} is already gluten-free", "Alice"),
+        actual.unwrap_err().to_string()
+    );
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants", "dietary_restrictions"))]
+async fn restrictions_cannot_be_added_to_unknown_homies(pool: SqlitePool) -> Result<()> {
+    let actual = add_homies_dietary_restriction(
+        "Bobberto".to_string(),
+        DietaryRestriction::Vegan,
+        -1,
+        &pool,
+    )
+    .await;
+
+    assert_eq!(
+        format!("Homie not found: {:?}", "Bobberto"),
+        actual.unwrap_err().to_string()
+    );
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures(
+    "homies",
+    "restaurants",
+    "homies_favorite_restaurants",
+    "dietary_restrictions"
+))]
+async fn candidates_must_accommodate_every_home_homie(pool: SqlitePool) -> Result<()> {
+    let actual =
+        get_candidate_restaurants(vec![-1, -2], &CandidateOptions::default(), -1, &pool).await?;
+
+    assert_eq!(
+        vec!["Tacos"],
+        actual.iter().map(|r| r.name.as_str()).collect::<Vec<_>>()
+    );
+
+    remove_homies_dietary_restriction(
+        "Alice".to_string(),
+        DietaryRestriction::GlutenFree,
+        -1,
+        &pool,
+    )
+    .await?;
+    let actual =
+        get_candidate_restaurants(vec![-1, -2], &CandidateOptions::default(), -1, &pool).await?;
+
+    assert_eq!(8, actual.len());
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures(
+    "homies",
+    "restaurants",
+    "homies_favorite_restaurants",
+    "dietary_restrictions"
+))]
+async fn excluded_favorites_explain_why(pool: SqlitePool) -> Result<()> {
+    let actual = get_dietary_excluded_favorites(vec![-2, -1], -1, &pool).await?;
+
+    assert_eq!(7, actual.len());
+    assert_eq!(
+        "Bavarian was skipped: it can't accommodate Alice (gluten-free)",
+        actual[0].to_string()
+    );
+    assert!(actual.iter().all(|e| e.restaurant_name != "Tacos"));
+    Ok(())
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "sqlite_tests")]
+
+use anyhow::Result;
+use lunch_picker::features::get_homies_elo_ratings;
+use lunch_picker::features::record_restaurant_matchups;
+use lunch_picker::features::record_selection_event;
+use lunch_picker::features::Matchup;
+use lunch_picker::features::RestaurantId;
+
+use sqlx::SqlitePool;
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants"))]
+async fn chosen_restaurant_beats_everything_shown(pool: SqlitePool) -> Result<()> {
+    let shown: Vec<RestaurantId> = vec![(-1).into(), (-2).into(), (-3).into()];
+
+    record_selection_event(vec![-1, -2], &shown, (-2).into(), -1, &pool).await?;
+
+    for homie_id in [-1, -2] {
+        let actual = get_homies_elo_ratings(homie_id, -1, &pool).await?;
+        assert_eq!(RestaurantId::from(-2), actual[0].restaurant_id);
+        assert_eq!(2, actual[0].matches);
+        assert!(actual[0].rating > 1500.0);
+        assert!(actual[1..].iter().all(|r| r.rating < 1500.0));
+    }
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants"))]
+async fn bracket_matchups_update_ratings(pool: SqlitePool) -> Result<()> {
+    record_restaurant_matchups(
+        vec![-1],
+        &[
+            Matchup {
+                winner: (-3).into(),
+                loser: (-1).into(),
+            },
+            Matchup {
+                winner: (-3).into(),
+                loser: (-2).into(),
+            },
+        ],
+        -1,
+        &pool,
+    )
+    .await?;
+
+    let actual = get_homies_elo_ratings(-1, -1, &pool).await?;
+
+    assert_eq!(RestaurantId::from(-3), actual[0].restaurant_id);
+    assert_eq!(3, actual.len());
+    assert!(get_homies_elo_ratings(-2, -1, &pool).await?.is_empty());
+    Ok(())
+}
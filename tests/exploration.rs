@@ -0,0 +1,63 @@
+#![cfg(feature = "sqlite_tests")]
+
+use anyhow::Result;
+use lunch_picker::features::get_exploration_restaurant;
+use lunch_picker::features::CandidateOptions;
+use lunch_picker::features::RestaurantId;
+
+use sqlx::SqlitePool;
+
+fn untried_except(id: i32) -> Vec<RestaurantId> {
+    // restaurants of user -1 that nobody has favorited or visited
+    (-24..=-14)
+        .chain([-6])
+        .filter(|&other| other != id)
+        .map(RestaurantId::from)
+        .collect()
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures(
+    "homies",
+    "restaurants",
+    "homies_favorite_restaurants",
+    "recent_restaurants"
+))]
+async fn explores_restaurants_nobody_has_tried(pool: SqlitePool) -> Result<()> {
+    let actual = get_exploration_restaurant(vec![-1, -2], &CandidateOptions::default(), -1, &pool)
+        .await?
+        .expect("there are untried restaurants");
+
+    assert!(untried_except(0).contains(&actual.id));
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures(
+    "homies",
+    "restaurants",
+    "homies_favorite_restaurants",
+    "recent_restaurants"
+))]
+async fn exploration_respects_excluded_restaurants(pool: SqlitePool) -> Result<()> {
+    let options = CandidateOptions {
+        exclude: untried_except(-6),
+        ..Default::default()
+    };
+
+    let actual = get_exploration_restaurant(vec![-1, -2], &options, -1, &pool).await?;
+
+    assert_eq!(Some(RestaurantId::from(-6)), actual.map(|r| r.id));
+
+    let options = CandidateOptions {
+        exclude: untried_except(0),
+        ..Default::default()
+    };
+
+    assert!(
+        get_exploration_restaurant(vec![-1, -2], &options, -1, &pool)
+            .await?
+            .is_none()
+    );
+    Ok(())
+}
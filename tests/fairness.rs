@@ -0,0 +1,37 @@
+#![cfg(feature = "sqlite_tests")]
+
+use anyhow::Result;
+use chrono::Local;
+use lunch_picker::features::get_fairness_boosts;
+use lunch_picker::features::get_fairness_report;
+
+use sqlx::SqlitePool;
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants", "homies_favorite_restaurants", "fairness"))]
+async fn report_counts_lunches_at_favorites(pool: SqlitePool) -> Result<()> {
+    let actual = get_fairness_report(4, -1, &pool).await?;
+
+    let alice = actual.iter().find(|s| s.homie_name == "Alice").unwrap();
+    let bob = actual.iter().find(|s| s.homie_name == "Bob").unwrap();
+    assert_eq!((3, 3), (alice.lunches, alice.satisfied));
+    // Bob's lunch at one of his favorites is older than four weeks
+    assert_eq!((3, 0), (bob.lunches, bob.satisfied));
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants", "homies_favorite_restaurants", "fairness"))]
+async fn homies_behind_are_boosted(pool: SqlitePool) -> Result<()> {
+    let actual =
+        get_fairness_boosts(vec![-1, -2, -6], Local::now().date_naive(), -1, &pool).await?;
+
+    assert_eq!(
+        vec![("Alice", 1.0), ("Bob", 2.0), ("Zoe", 1.0)],
+        actual
+            .iter()
+            .map(|b| (b.satisfaction.homie_name.as_str(), b.boost))
+            .collect::<Vec<_>>()
+    );
+    Ok(())
+}
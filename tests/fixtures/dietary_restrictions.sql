@@ -0,0 +1,9 @@
+insert into homies_dietary_restrictions (homie_id, user_id, restriction)
+values
+(-1, -1, 'gluten-free');
+
+insert into restaurant_dietary_accommodations (restaurant_id, user_id, restriction)
+values
+(-2, -1, 'gluten-free'),
+(-2, -1, 'vegan'),
+(-11, -1, 'vegan');
@@ -0,0 +1,9 @@
+insert into recent_restaurants (user_id, homie_id, restaurant_id, date)
+values
+(-1, -1, -1, date('now', '-3 days')),
+(-1, -2, -1, date('now', '-3 days')),
+(-1, -1, -2, date('now', '-5 days')),
+(-1, -2, -2, date('now', '-5 days')),
+(-1, -1, -3, date('now', '-9 days')),
+(-1, -2, -3, date('now', '-9 days')),
+(-1, -2, -11, date('now', '-60 days'));
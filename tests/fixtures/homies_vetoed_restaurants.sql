@@ -0,0 +1,5 @@
+insert into homies_vetoed_restaurants (user_id, homie_id, restaurant_id)
+values
+(-1, -2, -1),
+(-1, -2, -3),
+(-1, -6, -11);
@@ -0,0 +1,11 @@
+insert into recent_restaurants (user_id, homie_id, restaurant_id, date)
+values
+(-1, -1, -1, date('now', '-10 days')),
+(-1, -2, -1, date('now', '-10 days')),
+(-1, -1, -2, date('now', '-2 days')),
+(-1, -2, -2, date('now', '-2 days'));
+
+insert into recent_restaurant_ratings (user_id, homie_id, restaurant_id, date, rating, note)
+values
+(-1, -1, -1, date('now', '-10 days'), 4, 'Great crust'),
+(-1, -2, -1, date('now', '-10 days'), 2, null);
@@ -0,0 +1,17 @@
+update restaurants set price_tier = 1 where id = -1;
+update restaurants set price_tier = 3 where id = -3;
+update restaurants set price_tier = 2 where id = -11;
+
+insert into restaurant_tags (restaurant_id, user_id, tag)
+values
+(-1, -1, 'italian'),
+(-1, -1, 'quick'),
+(-3, -1, 'thai'),
+(-11, -1, 'quick');
+
+insert into restaurant_service_modes (restaurant_id, user_id, service_mode)
+values
+(-1, -1, 'delivery'),
+(-1, -1, 'dine-in'),
+(-3, -1, 'dine-in'),
+(-11, -1, 'takeout');
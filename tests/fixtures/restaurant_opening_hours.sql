@@ -0,0 +1,13 @@
+insert into restaurant_opening_hours (restaurant_id, user_id, weekday, opens, closes)
+values
+(-1, -1, 1, '11:00', '15:00'),
+(-1, -1, 2, '11:00', '15:00'),
+(-1, -1, 3, '11:00', '15:00'),
+(-1, -1, 4, '11:00', '15:00'),
+(-1, -1, 5, '11:00', '15:00'),
+(-2, -1, 0, '11:00', '22:00'),
+(-2, -1, 1, '11:00', '22:00');
+
+insert into restaurant_hours_exceptions (restaurant_id, user_id, date, opens, closes)
+values
+(-2, -1, '2026-10-20', null, null);
@@ -2,6 +2,7 @@
 
 use anyhow::Result;
 use lunch_picker::features::get_candidate_restaurants;
+use lunch_picker::features::CandidateOptions;
 
 use sqlx::SqlitePool;
 
@@ -15,7 +16,8 @@ use sqlx::SqlitePool;
 async fn test_restaurant_candidates(pool: SqlitePool) -> Result<()> {
     let home_homies: Vec<_> = vec![-1, -2];
 
-    let actual = get_candidate_restaurants(home_homies, 1, &pool).await?;
+    let actual =
+        get_candidate_restaurants(home_homies, &CandidateOptions::default(), 1, &pool).await?;
 
     assert_eq!(0, actual.len());
 
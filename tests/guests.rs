@@ -0,0 +1,31 @@
+#![cfg(feature = "sqlite_tests")]
+
+use anyhow::Result;
+use lunch_picker::features::apply_guests;
+use lunch_picker::features::get_candidate_restaurants;
+use lunch_picker::features::CandidateOptions;
+use lunch_picker::features::RestaurantId;
+use lunch_picker::features::RestaurantTag;
+
+use sqlx::SqlitePool;
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures(
+    "homies",
+    "restaurants",
+    "homies_favorite_restaurants",
+    "dietary_restrictions"
+))]
+async fn candidates_accommodate_guests(pool: SqlitePool) -> Result<()> {
+    let mut options = CandidateOptions::default();
+    apply_guests(&mut options, &["Sam:vegan,tapas".parse()?]);
+
+    let actual = get_candidate_restaurants(vec![-2], &options, -1, &pool).await?;
+
+    assert_eq!(
+        vec![RestaurantId::from(-11)],
+        actual.iter().map(|r| r.id).collect::<Vec<_>>()
+    );
+    assert_eq!(vec!["tapas".parse::<RestaurantTag>()?], options.prefer_tags);
+    Ok(())
+}
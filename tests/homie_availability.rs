@@ -0,0 +1,134 @@
+#![cfg(feature = "sqlite_tests")]
+
+use anyhow::Result;
+use chrono::Days;
+use chrono::Local;
+use chrono::NaiveDate;
+use lunch_picker::features::add_homie_away_period;
+use lunch_picker::features::get_available_homies;
+use lunch_picker::features::get_homie_away_periods;
+use lunch_picker::features::import_calendar_away_periods;
+use lunch_picker::features::simulate;
+use lunch_picker::features::AwayPeriod;
+use lunch_picker::features::CalendarMapping;
+use lunch_picker::features::GetHomieError;
+use lunch_picker::features::HomieAvailabilityError;
+use lunch_picker::features::HomieId;
+use lunch_picker::features::SimulationSettings;
+
+use sqlx::SqlitePool;
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 11, day).unwrap()
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies"))]
+async fn away_homies_are_not_available(pool: SqlitePool) -> Result<()> {
+    add_homie_away_period(
+        "Alice".to_string(),
+        "2026-11-02..2026-11-06".parse()?,
+        -1,
+        &pool,
+    )
+    .await?;
+
+    assert_eq!(
+        vec![HomieId::from(-2)],
+        get_available_homies(vec![-1, -2], date(3), -1, &pool).await?
+    );
+    assert_eq!(
+        vec![HomieId::from(-1), HomieId::from(-2)],
+        get_available_homies(vec![-1, -2], date(7), -1, &pool).await?
+    );
+    assert_eq!(
+        vec!["Alice is away 2026-11-02..2026-11-06"],
+        get_homie_away_periods(date(6), -1, &pool)
+            .await?
+            .iter()
+            .map(|a| a.to_string())
+            .collect::<Vec<_>>()
+    );
+    assert!(get_homie_away_periods(date(7), -1, &pool).await?.is_empty());
+    assert!(matches!(
+        add_homie_away_period("Yoko".to_string(), "2026-11-02".parse()?, -1, &pool).await,
+        Err(HomieAvailabilityError::GetHomieError(
+            GetHomieError::HomieNotFound { .. }
+        ))
+    ));
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures(
+    "homies",
+    "restaurants",
+    "homies_favorite_restaurants",
+    "recent_restaurants"
+))]
+async fn simulated_lunches_skip_away_homies(pool: SqlitePool) -> Result<()> {
+    let today = Local::now().date_naive();
+    let period = AwayPeriod {
+        starts_on: today + Days::new(1),
+        ends_on: today + Days::new(2),
+    };
+    add_homie_away_period("Alice".to_string(), period, -1, &pool).await?;
+
+    let report = simulate(
+        vec![-1, -2],
+        5,
+        SimulationSettings::default(),
+        None,
+        -1,
+        &pool,
+    )
+    .await?;
+
+    assert_eq!(0, report.empty_days());
+    assert_eq!(
+        vec![3, 5],
+        report
+            .satisfaction
+            .iter()
+            .map(|s| s.lunches)
+            .collect::<Vec<_>>()
+    );
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies"))]
+async fn calendars_are_imported_once(pool: SqlitePool) -> Result<()> {
+    let ics = "BEGIN:VCALENDAR
+BEGIN:VEVENT
+SUMMARY:Vacation
+DTSTART;VALUE=DATE:20261102
+DTEND;VALUE=DATE:20261104
+ATTENDEE;CN=Bobby Tables:mailto:bob@example.com
+END:VEVENT
+BEGIN:VEVENT
+SUMMARY:Lunch with Zoe
+DTSTART;VALUE=DATE:20261105
+END:VEVENT
+END:VCALENDAR
+";
+    let mut mapping = CalendarMapping::default();
+    mapping
+        .homies
+        .insert("bob@example.com".to_string(), "Bob".to_string());
+
+    let imported = import_calendar_away_periods(ics, &mapping, -1, &pool).await?;
+
+    assert_eq!(
+        vec!["Bob is away 2026-11-02..2026-11-03"],
+        imported.iter().map(|a| a.to_string()).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![HomieId::from(-1), HomieId::from(-6)],
+        get_available_homies(vec![-1, -2, -6], date(3), -1, &pool).await?
+    );
+    assert!(import_calendar_away_periods(ics, &mapping, -1, &pool)
+        .await?
+        .is_empty());
+    Ok(())
+}
@@ -0,0 +1,77 @@
+#![cfg(feature = "sqlite_tests")]
+
+use anyhow::Result;
+use lunch_picker::features::add_homie_group;
+use lunch_picker::features::edit_homie_group;
+use lunch_picker::features::get_homie_group;
+use lunch_picker::features::get_homie_groups;
+use lunch_picker::features::get_last_home_homies;
+use lunch_picker::features::remember_home_homies;
+use lunch_picker::features::GetHomieError;
+use lunch_picker::features::HomieGroupsError;
+use lunch_picker::features::HomieId;
+
+use sqlx::SqlitePool;
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies"))]
+async fn groups_are_added_edited_and_listed(pool: SqlitePool) -> Result<()> {
+    let office = add_homie_group(
+        "office crew",
+        &["Bob".to_string(), "Alice".to_string()],
+        -1,
+        &pool,
+    )
+    .await?;
+    add_homie_group("family", &["Zoe".to_string()], -1, &pool).await?;
+
+    assert_eq!("office crew: Alice, Bob", office.to_string());
+    assert!(office.is(&[HomieId::from(-2), HomieId::from(-1)]));
+    assert!(matches!(
+        add_homie_group("office crew", &["Zoe".to_string()], -1, &pool).await,
+        Err(HomieGroupsError::GroupAlreadyExists { .. })
+    ));
+    assert!(matches!(
+        add_homie_group("band", &["Yoko".to_string()], -1, &pool).await,
+        Err(HomieGroupsError::GetHomieError(
+            GetHomieError::HomieNotFound { .. }
+        ))
+    ));
+
+    edit_homie_group("office crew", &["Zoe".to_string()], -1, &pool).await?;
+
+    assert_eq!(
+        vec!["family: Zoe", "office crew: Zoe"],
+        get_homie_groups(-1, &pool)
+            .await?
+            .iter()
+            .map(|g| g.to_string())
+            .collect::<Vec<_>>()
+    );
+    assert!(get_homie_groups(-2, &pool).await?.is_empty());
+    assert!(matches!(
+        get_homie_group("office crew", -2, &pool).await,
+        Err(HomieGroupsError::GroupNotFound { .. })
+    ));
+    assert!(matches!(
+        edit_homie_group("band", &["Zoe".to_string()], -1, &pool).await,
+        Err(HomieGroupsError::GroupNotFound { .. })
+    ));
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies"))]
+async fn last_home_homies_are_remembered(pool: SqlitePool) -> Result<()> {
+    assert!(get_last_home_homies(-1, &pool).await?.is_empty());
+
+    remember_home_homies(vec![-1, -2], -1, &pool).await?;
+    remember_home_homies(vec![-6, -1], -1, &pool).await?;
+
+    assert_eq!(
+        vec![HomieId::from(-6), HomieId::from(-1)],
+        get_last_home_homies(-1, &pool).await?
+    );
+    assert!(get_last_home_homies(-2, &pool).await?.is_empty());
+    Ok(())
+}
@@ -2,7 +2,10 @@
 
 use anyhow::Result;
 use lunch_picker::features::add_homies_favorite_restaurant;
+use lunch_picker::features::get_homies_favorite_restaurants;
 use lunch_picker::features::remove_homies_favorite_restaurant;
+use lunch_picker::features::set_homies_favorite_restaurant_strength;
+use lunch_picker::features::FavoriteStrength;
 
 use sqlx::SqlitePool;
 
@@ -54,3 +57,48 @@ async fn no_favorites_are_removed_for_non_existant_homies(pool: SqlitePool) -> R
     assert_eq!("No favorite removed", actual.unwrap_err().to_string());
     Ok(())
 }
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants", "homies_favorite_restaurants"))]
+async fn favorites_default_to_medium_strength(pool: SqlitePool) -> Result<()> {
+    set_homies_favorite_restaurant_strength(
+        "Alice".to_string(),
+        "Pizza".to_string(),
+        3.try_into()?,
+        -1,
+        &pool,
+    )
+    .await?;
+
+    let favorites = get_homies_favorite_restaurants(-1, -1, &pool).await?;
+    let strength_of = |name: &str| {
+        favorites
+            .iter()
+            .find(|f| f.restaurant.name.as_str() == name)
+            .unwrap()
+            .strength
+    };
+
+    assert_eq!(FavoriteStrength::try_from(3)?, strength_of("Pizza"));
+    assert_eq!(FavoriteStrength::default(), strength_of("Tacos"));
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants", "homies_favorite_restaurants"))]
+async fn strength_cannot_be_set_for_non_favorites(pool: SqlitePool) -> Result<()> {
+    let actual = set_homies_favorite_restaurant_strength(
+        "Bob".to_string(),
+        "Pizza".to_string(),
+        1.try_into()?,
+        -1,
+        &pool,
+    )
+    .await;
+
+    assert_eq!(
+        format!("{:?} hasn't favorited {:?}", "Bob", "Pizza"),
+        actual.unwrap_err().to_string()
+    );
+    Ok(())
+}
@@ -0,0 +1,60 @@
+#![cfg(feature = "sqlite_tests")]
+
+use anyhow::Result;
+use lunch_picker::features::add_homies_vetoed_restaurant;
+use lunch_picker::features::get_candidate_restaurants;
+use lunch_picker::features::remove_homies_vetoed_restaurant;
+use lunch_picker::features::CandidateOptions;
+
+use sqlx::SqlitePool;
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants", "homies_vetoed_restaurants"))]
+async fn duplicate_cannot_be_added(pool: SqlitePool) -> Result<()> {
+    let actual =
+        add_homies_vetoed_restaurant("Bob".to_string(), "Pizza".to_string(), -1, &pool).await;
+
+    assert_eq!(
+        format!("{:?} already vetoed {:?}", "Bob", "Pizza"),
+        actual.unwrap_err().to_string()
+    );
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants", "homies_vetoed_restaurants"))]
+async fn no_vetoes_are_removed_for_non_existant_homies(pool: SqlitePool) -> Result<()> {
+    let actual =
+        remove_homies_vetoed_restaurant("Bobberto".to_string(), "Pizza".to_string(), -1, &pool)
+            .await;
+
+    assert_eq!("No veto removed", actual.unwrap_err().to_string());
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures(
+    "homies",
+    "restaurants",
+    "homies_favorite_restaurants",
+    "homies_vetoed_restaurants"
+))]
+async fn vetoed_restaurants_are_never_candidates(pool: SqlitePool) -> Result<()> {
+    let actual =
+        get_candidate_restaurants(vec![-1, -2], &CandidateOptions::default(), -1, &pool).await?;
+    let mut names = actual.iter().map(|r| r.name.as_str()).collect::<Vec<_>>();
+    names.sort();
+
+    // Zoe's veto on Spanish doesn't count, she isn't home
+    assert_eq!(
+        vec!["Bavarian", "Cuban", "Escargo", "Seafood", "Spanish", "Tacos"],
+        names
+    );
+
+    remove_homies_vetoed_restaurant("Bob".to_string(), "Pizza".to_string(), -1, &pool).await?;
+    let actual =
+        get_candidate_restaurants(vec![-1, -2], &CandidateOptions::default(), -1, &pool).await?;
+
+    assert_eq!(7, actual.len());
+    Ok(())
+}
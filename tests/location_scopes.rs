@@ -0,0 +1,81 @@
+#![cfg(feature = "sqlite_tests")]
+
+use anyhow::Result;
+use chrono::NaiveDate;
+use lunch_picker::features::generate_lunch_plan;
+use lunch_picker::features::get_candidate_restaurants;
+use lunch_picker::features::get_homies_favorite_restaurants;
+use lunch_picker::features::get_user_location;
+use lunch_picker::features::set_restaurant_scope;
+use lunch_picker::features::set_user_location;
+use lunch_picker::features::CandidateOptions;
+use lunch_picker::features::RestaurantId;
+
+use sqlx::SqlitePool;
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants", "homies_favorite_restaurants"))]
+async fn candidates_belong_to_the_location(pool: SqlitePool) -> Result<()> {
+    set_user_location("office", "52.5163,13.3777".parse()?, -1, &pool).await?;
+    set_user_location("home", "52.4800,13.4300".parse()?, -1, &pool).await?;
+    let office = get_user_location("office", -1, &pool).await?;
+    let home = get_user_location("home", -1, &pool).await?;
+    set_restaurant_scope("Spanish".to_string(), Some(office.id), -1, &pool).await?;
+    set_restaurant_scope("Cuban".to_string(), Some(home.id), -1, &pool).await?;
+
+    let at_office = CandidateOptions {
+        location_id: Some(office.id),
+        ..Default::default()
+    };
+    let candidates = get_candidate_restaurants(vec![-2], &at_office, -1, &pool).await?;
+
+    assert_eq!(
+        vec![RestaurantId::from(-11)],
+        candidates.iter().map(|r| r.id).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        2,
+        get_candidate_restaurants(vec![-2], &CandidateOptions::default(), -1, &pool)
+            .await?
+            .len()
+    );
+
+    // favorites are shared, only what's offered depends on the location
+    let favorites = get_homies_favorite_restaurants(-1, -2, &pool)
+        .await?
+        .into_iter()
+        .filter(|f| f.restaurant.is_available_at(Some(home.id)))
+        .map(|f| f.restaurant.id)
+        .collect::<Vec<_>>();
+    assert_eq!(vec![RestaurantId::from(-12)], favorites);
+
+    set_restaurant_scope("Cuban".to_string(), None, -1, &pool).await?;
+    assert_eq!(
+        2,
+        get_candidate_restaurants(vec![-2], &at_office, -1, &pool)
+            .await?
+            .len()
+    );
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants", "homies_favorite_restaurants"))]
+async fn plans_stick_to_the_location(pool: SqlitePool) -> Result<()> {
+    set_user_location("office", "52.5163,13.3777".parse()?, -1, &pool).await?;
+    set_user_location("home", "52.4800,13.4300".parse()?, -1, &pool).await?;
+    let office = get_user_location("office", -1, &pool).await?;
+    let home = get_user_location("home", -1, &pool).await?;
+    set_restaurant_scope("Spanish".to_string(), Some(office.id), -1, &pool).await?;
+    set_restaurant_scope("Cuban".to_string(), Some(home.id), -1, &pool).await?;
+    let monday = NaiveDate::from_ymd_opt(2026, 10, 19).unwrap();
+
+    let plan = generate_lunch_plan(vec![-2], monday, monday, Some(&office), -1, &pool).await?;
+
+    assert!(!plan.lunches.is_empty());
+    assert!(plan
+        .lunches
+        .iter()
+        .all(|l| l.restaurant_id == RestaurantId::from(-11)));
+    Ok(())
+}
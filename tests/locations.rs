@@ -0,0 +1,97 @@
+#![cfg(feature = "sqlite_tests")]
+
+use anyhow::Result;
+use lunch_picker::features::get_candidate_restaurants;
+use lunch_picker::features::get_restaurant;
+use lunch_picker::features::get_user_location;
+use lunch_picker::features::get_user_locations;
+use lunch_picker::features::remove_user_location;
+use lunch_picker::features::set_restaurant_location;
+use lunch_picker::features::set_user_location;
+use lunch_picker::features::CandidateOptions;
+use lunch_picker::features::RestaurantId;
+use lunch_picker::features::UserLocationsError;
+
+use sqlx::SqlitePool;
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies"))]
+async fn locations_are_set_moved_and_removed(pool: SqlitePool) -> Result<()> {
+    set_user_location("office", "52.5163,13.3777".parse()?, -1, &pool).await?;
+    set_user_location("home", "52.4800,13.4300".parse()?, -1, &pool).await?;
+    set_user_location("office", "52.5219,13.4132".parse()?, -1, &pool).await?;
+
+    assert_eq!(
+        vec!["home: 52.48,13.43", "office: 52.5219,13.4132"],
+        get_user_locations(-1, &pool)
+            .await?
+            .iter()
+            .map(|l| l.to_string())
+            .collect::<Vec<_>>()
+    );
+    assert!(get_user_locations(-2, &pool).await?.is_empty());
+
+    remove_user_location("home", -1, &pool).await?;
+
+    assert!(matches!(
+        get_user_location("home", -1, &pool).await,
+        Err(UserLocationsError::LocationNotFound { .. })
+    ));
+    assert!(matches!(
+        remove_user_location("home", -1, &pool).await,
+        Err(UserLocationsError::LocationNotFound { .. })
+    ));
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants", "homies_favorite_restaurants"))]
+async fn candidates_are_within_reach(pool: SqlitePool) -> Result<()> {
+    set_user_location("office", "52.5163,13.3777".parse()?, -1, &pool).await?;
+    set_restaurant_location(
+        "Spanish".to_string(),
+        Some("48.8566,2.3522".parse()?),
+        -1,
+        &pool,
+    )
+    .await?;
+    set_restaurant_location(
+        "Cuban".to_string(),
+        Some("52.5219,13.4132".parse()?),
+        -1,
+        &pool,
+    )
+    .await?;
+    let mut options = CandidateOptions {
+        origin: Some(get_user_location("office", -1, &pool).await?.coordinates),
+        max_km: Some(1.5),
+        ..Default::default()
+    };
+
+    assert!(get_candidate_restaurants(vec![-2], &options, -1, &pool)
+        .await?
+        .is_empty());
+
+    options.max_km = Some(5.0);
+    assert_eq!(
+        vec![RestaurantId::from(-12)],
+        get_candidate_restaurants(vec![-2], &options, -1, &pool)
+            .await?
+            .iter()
+            .map(|r| r.id)
+            .collect::<Vec<_>>()
+    );
+
+    set_restaurant_location("Spanish".to_string(), None, -1, &pool).await?;
+    assert!(get_restaurant("Spanish".to_string(), -1, &pool)
+        .await?
+        .coordinates
+        .is_none());
+    assert_eq!(
+        2,
+        get_candidate_restaurants(vec![-2], &options, -1, &pool)
+            .await?
+            .len()
+    );
+    Ok(())
+}
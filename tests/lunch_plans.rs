@@ -0,0 +1,72 @@
+#![cfg(feature = "sqlite_tests")]
+
+use std::collections::HashSet;
+
+use anyhow::Result;
+use chrono::NaiveDate;
+use lunch_picker::features::generate_lunch_plan;
+use lunch_picker::features::get_lunch_plan;
+use lunch_picker::features::get_planned_lunch;
+use lunch_picker::features::RestaurantId;
+
+use sqlx::SqlitePool;
+
+fn date(day: u32) -> NaiveDate {
+    NaiveDate::from_ymd_opt(2026, 10, day).unwrap()
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures(
+    "homies",
+    "restaurants",
+    "homies_favorite_restaurants",
+    "recent_restaurants"
+))]
+async fn plans_a_week_without_repeats(pool: SqlitePool) -> Result<()> {
+    // 2026-10-19 is a monday
+    let plan = generate_lunch_plan(vec![-1, -2], date(21), date(19), None, -1, &pool).await?;
+
+    assert_eq!(
+        (19..=23).map(date).collect::<Vec<_>>(),
+        plan.lunches.iter().map(|l| l.date).collect::<Vec<_>>()
+    );
+    let planned = plan
+        .lunches
+        .iter()
+        .map(|l| l.restaurant_id)
+        .collect::<HashSet<_>>();
+    assert_eq!(5, planned.len());
+    // Alice and Bob had pizza recently
+    assert!(!planned.contains(&RestaurantId::from(-1)));
+    // Bob's only favorites are Spanish and Cuban
+    assert!(planned.contains(&(-11).into()) || planned.contains(&(-12).into()));
+    assert!(plan.without_favorite.is_empty());
+
+    assert_eq!(
+        plan.lunches,
+        get_lunch_plan(date(23), -1, &pool).await?.lunches
+    );
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants", "homies_favorite_restaurants"))]
+async fn replanning_keeps_the_days_gone_by(pool: SqlitePool) -> Result<()> {
+    let first = generate_lunch_plan(vec![-1, -2], date(19), date(19), None, -1, &pool).await?;
+
+    let second = generate_lunch_plan(vec![-1], date(19), date(22), None, -1, &pool).await?;
+
+    assert_eq!(
+        vec![date(22), date(23)],
+        second.lunches.iter().map(|l| l.date).collect::<Vec<_>>()
+    );
+    let stored = get_lunch_plan(date(19), -1, &pool).await?.lunches;
+    assert_eq!(5, stored.len());
+    assert_eq!(first.lunches[..3], stored[..3]);
+    assert_eq!(
+        Some(second.lunches[0].clone()),
+        get_planned_lunch(date(22), -1, &pool).await?
+    );
+    assert_eq!(None, get_planned_lunch(date(24), -1, &pool).await?);
+    Ok(())
+}
@@ -0,0 +1,138 @@
+#![cfg(feature = "sqlite_tests")]
+
+use anyhow::Result;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use lunch_picker::features::add_lunch_rule;
+use lunch_picker::features::get_candidate_restaurants;
+use lunch_picker::features::get_lunch_rules;
+use lunch_picker::features::get_lunch_rules_on;
+use lunch_picker::features::remove_lunch_rule;
+use lunch_picker::features::CandidateOptions;
+use lunch_picker::features::GetRestaurantError;
+use lunch_picker::features::LunchRule;
+use lunch_picker::features::LunchRulesError;
+use lunch_picker::features::RestaurantId;
+
+use sqlx::SqlitePool;
+
+// 2026-10-19 is the third monday of october
+fn monday_noon() -> Option<NaiveDateTime> {
+    NaiveDate::from_ymd_opt(2026, 10, 19).and_then(|d| d.and_hms_opt(12, 0, 0))
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants"))]
+async fn rules_are_added_listed_and_removed(pool: SqlitePool) -> Result<()> {
+    let tacos: LunchRule = "on tuesdays prefer tag=tacos".parse()?;
+    let thai: LunchRule = "on the first friday of the month is always Thai".parse()?;
+
+    let tacos = add_lunch_rule(&tacos, -1, &pool).await?;
+    let thai = add_lunch_rule(&thai, -1, &pool).await?;
+
+    assert_eq!(Some(RestaurantId::from(-3)), thai.restaurant_id);
+    assert_eq!(
+        vec![tacos.clone(), thai.clone()],
+        get_lunch_rules(-1, &pool).await?
+    );
+    assert_eq!(
+        vec![tacos.clone()],
+        get_lunch_rules_on(NaiveDate::from_ymd_opt(2026, 10, 20).unwrap(), -1, &pool).await?
+    );
+    assert!(get_lunch_rules(-2, &pool).await?.is_empty());
+
+    remove_lunch_rule(thai.id, -1, &pool).await?;
+
+    assert_eq!(vec![tacos], get_lunch_rules(-1, &pool).await?);
+    assert!(matches!(
+        remove_lunch_rule(thai.id, -1, &pool).await,
+        Err(LunchRulesError::RuleNotFound { .. })
+    ));
+    assert!(matches!(
+        add_lunch_rule(&"on mondays no Nowhere".parse()?, -1, &pool).await,
+        Err(LunchRulesError::GetRestaurantError(
+            GetRestaurantError::RestaurantNotFound { .. }
+        ))
+    ));
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures(
+    "homies",
+    "restaurants",
+    "homies_favorite_restaurants",
+    "restaurant_attributes"
+))]
+async fn rules_filter_and_force_candidates(pool: SqlitePool) -> Result<()> {
+    let options = CandidateOptions {
+        at: monday_noon(),
+        ..Default::default()
+    };
+    add_lunch_rule(&"no tag=quick on mondays".parse()?, -1, &pool).await?;
+
+    let actual = get_candidate_restaurants(vec![-1, -2], &options, -1, &pool).await?;
+
+    assert!(!actual.is_empty());
+    assert!(actual
+        .iter()
+        .all(|r| r.id != (-1).into() && r.id != (-11).into()));
+
+    add_lunch_rule(
+        &"on the third monday of the month always restaurant=Thai".parse()?,
+        -1,
+        &pool,
+    )
+    .await?;
+
+    let actual = get_candidate_restaurants(vec![-1, -2], &options, -1, &pool).await?;
+
+    assert_eq!(
+        vec![RestaurantId::from(-3)],
+        actual.iter().map(|r| r.id).collect::<Vec<_>>()
+    );
+
+    // turning the forced pick down falls back to the other candidates
+    let options = CandidateOptions {
+        exclude: vec![(-3).into()],
+        ..options
+    };
+    let actual = get_candidate_restaurants(vec![-1, -2], &options, -1, &pool).await?;
+
+    assert!(actual.len() > 1);
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures(
+    "homies",
+    "restaurants",
+    "homies_favorite_restaurants",
+    "homies_vetoed_restaurants"
+))]
+async fn forced_restaurant_is_still_vetoed(pool: SqlitePool) -> Result<()> {
+    let options = CandidateOptions {
+        at: monday_noon(),
+        ..Default::default()
+    };
+    add_lunch_rule(
+        &"on the third monday of the month always restaurant=Thai".parse()?,
+        -1,
+        &pool,
+    )
+    .await?;
+
+    // Bob vetoed Thai, so the rule gives way to the usual candidates
+    let actual = get_candidate_restaurants(vec![-1, -2], &options, -1, &pool).await?;
+
+    assert!(!actual.is_empty());
+    assert!(actual.iter().all(|r| r.id != (-3).into()));
+
+    let actual = get_candidate_restaurants(vec![-1], &options, -1, &pool).await?;
+
+    assert_eq!(
+        vec![RestaurantId::from(-3)],
+        actual.iter().map(|r| r.id).collect::<Vec<_>>()
+    );
+    Ok(())
+}
@@ -0,0 +1,80 @@
+#![cfg(feature = "sqlite_tests")]
+
+use anyhow::Result;
+use lunch_picker::features::add_recent_restaurant_for_homie;
+use lunch_picker::features::get_candidate_restaurants;
+use lunch_picker::features::get_recent_visits;
+use lunch_picker::features::rate_recent_restaurant;
+use lunch_picker::features::CandidateOptions;
+use lunch_picker::features::MealSlot;
+use lunch_picker::features::RestaurantId;
+use lunch_picker::features::VisitRating;
+use sqlx::SqlitePool;
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures(
+    "homies",
+    "restaurants",
+    "homies_favorite_restaurants",
+    "recent_restaurants"
+))]
+async fn lunch_and_dinner_are_recorded_separately(pool: SqlitePool) -> Result<()> {
+    add_recent_restaurant_for_homie(
+        "Alice".to_string(),
+        "Pizza".to_string(),
+        MealSlot::Dinner,
+        -1,
+        &pool,
+    )
+    .await?;
+
+    rate_recent_restaurant(
+        "Alice".to_string(),
+        "Pizza".to_string(),
+        None,
+        Some(MealSlot::Dinner),
+        VisitRating::try_from(4)?,
+        None,
+        -1,
+        &pool,
+    )
+    .await?;
+
+    let pizza = get_recent_visits(1, -1, &pool)
+        .await?
+        .into_iter()
+        .filter(|v| v.homie_name == "Alice" && v.restaurant_name == "Pizza")
+        .map(|v| (v.meal, v.rating.map(|r| r.as_u8())))
+        .collect::<Vec<_>>();
+
+    assert_eq!(2, pizza.len());
+    assert!(pizza.contains(&(MealSlot::Lunch, None)));
+    assert!(pizza.contains(&(MealSlot::Dinner, Some(4))));
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures(
+    "homies",
+    "restaurants",
+    "homies_favorite_restaurants",
+    "recent_restaurants"
+))]
+async fn lunch_does_not_rule_out_dinner(pool: SqlitePool) -> Result<()> {
+    let lunch =
+        get_candidate_restaurants(vec![-1], &CandidateOptions::default(), -1, &pool).await?;
+    let dinner = get_candidate_restaurants(
+        vec![-1],
+        &CandidateOptions {
+            meal: MealSlot::Dinner,
+            ..Default::default()
+        },
+        -1,
+        &pool,
+    )
+    .await?;
+
+    assert!(lunch.iter().all(|r| r.id != RestaurantId::from(-1)));
+    assert!(dinner.iter().any(|r| r.id == RestaurantId::from(-1)));
+    Ok(())
+}
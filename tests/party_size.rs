@@ -0,0 +1,65 @@
+#![cfg(feature = "sqlite_tests")]
+
+use anyhow::Result;
+use lunch_picker::features::get_candidate_restaurants;
+use lunch_picker::features::get_restaurant;
+use lunch_picker::features::set_restaurant_party_size;
+use lunch_picker::features::CandidateOptions;
+use lunch_picker::features::RestaurantId;
+use lunch_picker::features::UpdateRestaurantAttributesError;
+
+use sqlx::SqlitePool;
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants", "homies_favorite_restaurants"))]
+async fn candidates_seat_the_whole_party(pool: SqlitePool) -> Result<()> {
+    set_restaurant_party_size("Cuban".to_string(), Some(2), None, -1, &pool).await?;
+    set_restaurant_party_size("Spanish".to_string(), None, Some(2), -1, &pool).await?;
+    let mut options = CandidateOptions {
+        party_size: Some(3),
+        ..Default::default()
+    };
+
+    let actual = get_candidate_restaurants(vec![-2], &options, -1, &pool).await?;
+
+    assert_eq!(
+        vec![RestaurantId::from(-11)],
+        actual.iter().map(|r| r.id).collect::<Vec<_>>()
+    );
+    assert!(actual[0].needs_reservation(3));
+    assert!(!actual[0].needs_reservation(2));
+
+    options.party_size = Some(2);
+    assert_eq!(
+        2,
+        get_candidate_restaurants(vec![-2], &options, -1, &pool)
+            .await?
+            .len()
+    );
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants"))]
+async fn party_sizes_are_set_and_cleared(pool: SqlitePool) -> Result<()> {
+    set_restaurant_party_size("Pizza".to_string(), Some(12), Some(6), -1, &pool).await?;
+
+    let pizza = get_restaurant("Pizza".to_string(), -1, &pool).await?;
+    assert_eq!(
+        (Some(12), Some(6)),
+        (pizza.max_party_size, pizza.reservations_above)
+    );
+
+    set_restaurant_party_size("Pizza".to_string(), None, None, -1, &pool).await?;
+
+    let pizza = get_restaurant("Pizza".to_string(), -1, &pool).await?;
+    assert_eq!(
+        (None, None),
+        (pizza.max_party_size, pizza.reservations_above)
+    );
+    assert!(matches!(
+        set_restaurant_party_size("Pizza".to_string(), Some(0), None, -1, &pool).await,
+        Err(UpdateRestaurantAttributesError::InvalidPartySize)
+    ));
+    Ok(())
+}
@@ -0,0 +1,107 @@
+#![cfg(feature = "sqlite_tests")]
+
+use anyhow::Result;
+use chrono::Local;
+use chrono::NaiveDate;
+use lunch_picker::features::add_recent_restaurant_for_homies;
+use lunch_picker::features::get_recent_visits;
+use lunch_picker::features::undo_last_pick;
+use lunch_picker::features::AddHomiesRecentRestaurantError;
+use lunch_picker::features::MealSlot;
+use lunch_picker::features::PickEventsError;
+use sqlx::SqlitePool;
+
+fn today() -> NaiveDate {
+    Local::now().date_naive()
+}
+
+async fn todays_visits(pool: &SqlitePool) -> Result<Vec<(String, String)>> {
+    let mut visits = get_recent_visits(1, -1, pool)
+        .await?
+        .into_iter()
+        .map(|v| (v.homie_name, v.restaurant_name))
+        .collect::<Vec<_>>();
+    visits.sort();
+    Ok(visits)
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants"))]
+async fn picking_again_today_replaces_the_pick(pool: SqlitePool) -> Result<()> {
+    add_recent_restaurant_for_homies(vec![-1, -2], -1, MealSlot::Lunch, today(), false, -1, &pool)
+        .await?;
+    add_recent_restaurant_for_homies(vec![-1, -2], -1, MealSlot::Lunch, today(), false, -1, &pool)
+        .await?;
+
+    let refused =
+        add_recent_restaurant_for_homies(vec![-2], -2, MealSlot::Lunch, today(), false, -1, &pool)
+            .await;
+    assert!(matches!(
+        refused,
+        Err(AddHomiesRecentRestaurantError::AlreadyPickedToday { .. })
+    ));
+
+    add_recent_restaurant_for_homies(vec![-2], -2, MealSlot::Lunch, today(), true, -1, &pool)
+        .await?;
+
+    assert_eq!(
+        vec![
+            ("Alice".to_string(), "Pizza".to_string()),
+            ("Bob".to_string(), "Tacos".to_string())
+        ],
+        todays_visits(&pool).await?
+    );
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants"))]
+async fn undo_takes_back_the_latest_pick(pool: SqlitePool) -> Result<()> {
+    add_recent_restaurant_for_homies(vec![-1], -1, MealSlot::Lunch, today(), false, -1, &pool)
+        .await?;
+    add_recent_restaurant_for_homies(
+        vec![-1, -2],
+        -2,
+        MealSlot::Dinner,
+        today(),
+        false,
+        -1,
+        &pool,
+    )
+    .await?;
+
+    let undone = undo_last_pick(-1, &pool).await?;
+    assert_eq!("Tacos", undone.restaurant_name);
+    assert_eq!(MealSlot::Dinner, undone.meal);
+    assert_eq!(vec!["Alice", "Bob"], undone.homie_names);
+    assert_eq!(
+        vec![("Alice".to_string(), "Pizza".to_string())],
+        todays_visits(&pool).await?
+    );
+
+    assert_eq!("Pizza", undo_last_pick(-1, &pool).await?.restaurant_name);
+    assert!(todays_visits(&pool).await?.is_empty());
+    assert!(matches!(
+        undo_last_pick(-1, &pool).await,
+        Err(PickEventsError::NothingToUndo)
+    ));
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants"))]
+async fn undoing_a_repeated_pick_keeps_the_first(pool: SqlitePool) -> Result<()> {
+    add_recent_restaurant_for_homies(vec![-1], -1, MealSlot::Lunch, today(), false, -1, &pool)
+        .await?;
+    add_recent_restaurant_for_homies(vec![-1], -2, MealSlot::Dinner, today(), false, -1, &pool)
+        .await?;
+    add_recent_restaurant_for_homies(vec![-1], -1, MealSlot::Lunch, today(), false, -1, &pool)
+        .await?;
+
+    assert_eq!("Tacos", undo_last_pick(-1, &pool).await?.restaurant_name);
+    assert_eq!(
+        vec![("Alice".to_string(), "Pizza".to_string())],
+        todays_visits(&pool).await?
+    );
+    Ok(())
+}
@@ -0,0 +1,95 @@
+#![cfg(feature = "sqlite_tests")]
+
+use anyhow::Result;
+use lunch_picker::features::get_recent_visits;
+use lunch_picker::features::get_unrated_visits;
+use lunch_picker::features::rate_recent_restaurant;
+use lunch_picker::features::VisitRating;
+
+use sqlx::SqlitePool;
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants", "recent_restaurant_ratings"))]
+async fn latest_unrated_lunch_is_offered_for_rating(pool: SqlitePool) -> Result<()> {
+    let actual = get_unrated_visits(false, -1, &pool).await?;
+
+    assert_eq!(
+        vec![("Alice", "Tacos"), ("Bob", "Tacos")],
+        actual
+            .iter()
+            .map(|v| (v.homie_name.as_str(), v.restaurant_name.as_str()))
+            .collect::<Vec<_>>()
+    );
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants", "recent_restaurant_ratings"))]
+async fn rating_defaults_to_latest_visit(pool: SqlitePool) -> Result<()> {
+    rate_recent_restaurant(
+        "Alice".to_string(),
+        "Tacos".to_string(),
+        None,
+        None,
+        VisitRating::try_from(5)?,
+        Some("  Best al pastor  ".to_string()),
+        -1,
+        &pool,
+    )
+    .await?;
+
+    let actual = get_recent_visits(7, -1, &pool).await?;
+    let alice = actual.iter().find(|v| v.homie_name == "Alice").unwrap();
+
+    assert_eq!(Some(VisitRating::try_from(5)?), alice.rating);
+    assert_eq!(Some("Best al pastor".to_string()), alice.note);
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants", "recent_restaurant_ratings"))]
+async fn rating_again_replaces_the_rating(pool: SqlitePool) -> Result<()> {
+    rate_recent_restaurant(
+        "Bob".to_string(),
+        "Pizza".to_string(),
+        None,
+        None,
+        VisitRating::try_from(3)?,
+        Some("".to_string()),
+        -1,
+        &pool,
+    )
+    .await?;
+
+    let actual = get_recent_visits(14, -1, &pool).await?;
+    let bob = actual
+        .iter()
+        .find(|v| v.homie_name == "Bob" && v.restaurant_name == "Pizza")
+        .unwrap();
+
+    assert_eq!(Some(VisitRating::try_from(3)?), bob.rating);
+    assert_eq!(None, bob.note);
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants", "recent_restaurant_ratings"))]
+async fn restaurants_not_visited_cannot_be_rated(pool: SqlitePool) -> Result<()> {
+    let actual = rate_recent_restaurant(
+        "Alice".to_string(),
+        "Thai".to_string(),
+        None,
+        None,
+        VisitRating::try_from(3)?,
+        None,
+        -1,
+        &pool,
+    )
+    .await;
+
+    assert_eq!(
+        format!("{:?} has no visit to {:?} to rate", "Alice", "Thai"),
+        actual.unwrap_err().to_string()
+    );
+    Ok(())
+}
@@ -2,6 +2,7 @@
 
 use anyhow::Result;
 use lunch_picker::features::add_recent_restaurant_for_homie;
+use lunch_picker::features::MealSlot;
 use sqlx::SqlitePool;
 
 #[cfg_attr(not(feature = "sqlite_tests"), ignore)]
@@ -12,8 +13,14 @@ use sqlx::SqlitePool;
     "recent_restaurants"
 ))]
 async fn duplicate_cannot_be_added(pool: SqlitePool) -> Result<()> {
-    let actual =
-        add_recent_restaurant_for_homie("Alice".to_string(), "Pizza".to_string(), -1, &pool).await;
+    let actual = add_recent_restaurant_for_homie(
+        "Alice".to_string(),
+        "Pizza".to_string(),
+        MealSlot::Lunch,
+        -1,
+        &pool,
+    )
+    .await;
 
     assert_eq!(
         format!("{:?} already has {:?} recentd", "Alice", "Pizza"),
@@ -30,10 +37,14 @@ async fn duplicate_cannot_be_added(pool: SqlitePool) -> Result<()> {
     "recent_restaurants"
 ))]
 async fn valid(pool: SqlitePool) -> Result<()> {
-    Ok(
-        add_recent_restaurant_for_homie("Ringo".to_string(), "Pizza".to_string(), -1, &pool)
-            .await?,
+    Ok(add_recent_restaurant_for_homie(
+        "Ringo".to_string(),
+        "Pizza".to_string(),
+        MealSlot::Lunch,
+        -1,
+        &pool,
     )
+    .await?)
 }
 
 #[cfg_attr(not(feature = "sqlite_tests"), ignore)]
@@ -44,9 +55,14 @@ async fn valid(pool: SqlitePool) -> Result<()> {
     "recent_restaurants"
 ))]
 async fn no_recents_are_added_for_non_existant_homies(pool: SqlitePool) -> Result<()> {
-    let actual =
-        add_recent_restaurant_for_homie("Bobbert".to_string(), "Pizza".to_string(), -1, &pool)
-            .await;
+    let actual = add_recent_restaurant_for_homie(
+        "Bobbert".to_string(),
+        "Pizza".to_string(),
+        MealSlot::Lunch,
+        -1,
+        &pool,
+    )
+    .await;
 
     assert_eq!("No recent added", actual.unwrap_err().to_string());
     Ok(())
@@ -0,0 +1,81 @@
+#![cfg(feature = "sqlite_tests")]
+
+use anyhow::Result;
+use lunch_picker::features::add_restaurant_tag;
+use lunch_picker::features::get_all_restaurants;
+use lunch_picker::features::get_candidate_restaurants;
+use lunch_picker::features::remove_restaurant_tag;
+use lunch_picker::features::CandidateOptions;
+use lunch_picker::features::ServiceMode;
+
+use sqlx::SqlitePool;
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants", "restaurant_attributes"))]
+async fn duplicate_tag_cannot_be_added(pool: SqlitePool) -> Result<()> {
+    let actual = add_restaurant_tag("Pizza".to_string(), "Quick".to_string(), -1, &pool).await;
+
+    assert_eq!(
+        format!("{:?} is already tagged {:?}", "Pizza", "quick"),
+        actual.unwrap_err().to_string()
+    );
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants", "restaurant_attributes"))]
+async fn tags_are_added_and_removed(pool: SqlitePool) -> Result<()> {
+    add_restaurant_tag("Tacos".to_string(), "mexican".to_string(), -1, &pool).await?;
+    remove_restaurant_tag("Pizza".to_string(), "quick".to_string(), -1, &pool).await?;
+
+    let restaurants = get_all_restaurants(-1, &pool).await?;
+    let tags_of = |name: &str| {
+        restaurants
+            .iter()
+            .find(|r| r.name.as_str() == name)
+            .unwrap()
+            .as_view()
+            .tags
+    };
+
+    assert_eq!(vec!["mexican"], tags_of("Tacos"));
+    assert_eq!(vec!["italian"], tags_of("Pizza"));
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants", "restaurant_attributes"))]
+async fn tags_cannot_be_added_to_unknown_restaurants(pool: SqlitePool) -> Result<()> {
+    let actual = add_restaurant_tag("Nowhere".to_string(), "thai".to_string(), -1, &pool).await;
+
+    assert_eq!(
+        format!("Restaurant not found: {:?}", "Nowhere"),
+        actual.unwrap_err().to_string()
+    );
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures(
+    "homies",
+    "restaurants",
+    "homies_favorite_restaurants",
+    "restaurant_attributes"
+))]
+async fn candidates_are_filtered_by_attributes(pool: SqlitePool) -> Result<()> {
+    let options = CandidateOptions {
+        tags: vec!["quick".parse()?],
+        max_price_tier: Some(1.try_into()?),
+        service_mode: Some(ServiceMode::Delivery),
+        at: None,
+        ..Default::default()
+    };
+
+    let actual = get_candidate_restaurants(vec![-1, -2], &options, -1, &pool).await?;
+
+    assert_eq!(
+        vec!["Pizza"],
+        actual.iter().map(|r| r.name.as_str()).collect::<Vec<_>>()
+    );
+    Ok(())
+}
@@ -0,0 +1,57 @@
+#![cfg(feature = "sqlite_tests")]
+
+use anyhow::Result;
+use lunch_picker::features::get_restaurant_matchups;
+use lunch_picker::features::record_restaurant_matchups;
+use lunch_picker::features::HomieId;
+use lunch_picker::features::Matchup;
+
+use sqlx::SqlitePool;
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants"))]
+async fn every_matchup_is_recorded_for_the_home_homies(pool: SqlitePool) -> Result<()> {
+    let matchups = vec![
+        Matchup {
+            winner: (-1).into(),
+            loser: (-2).into(),
+        },
+        Matchup {
+            winner: (-3).into(),
+            loser: (-1).into(),
+        },
+    ];
+
+    // homie -3 belongs to another user
+    record_restaurant_matchups(vec![-1, -2, -3], &matchups, -1, &pool).await?;
+    let actual = get_restaurant_matchups(-1, &pool).await?;
+
+    assert_eq!(
+        matchups,
+        actual.iter().map(|m| m.matchup).collect::<Vec<_>>()
+    );
+    for recorded in actual {
+        let mut homie_ids = recorded.homie_ids;
+        homie_ids.sort();
+        assert_eq!(vec![HomieId::from(-2), HomieId::from(-1)], homie_ids);
+    }
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants"))]
+async fn matchups_are_kept_per_user(pool: SqlitePool) -> Result<()> {
+    record_restaurant_matchups(
+        vec![-1],
+        &[Matchup {
+            winner: (-1).into(),
+            loser: (-2).into(),
+        }],
+        -1,
+        &pool,
+    )
+    .await?;
+
+    assert!(get_restaurant_matchups(-2, &pool).await?.is_empty());
+    Ok(())
+}
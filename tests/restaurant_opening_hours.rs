@@ -0,0 +1,142 @@
+#![cfg(feature = "sqlite_tests")]
+
+use anyhow::Result;
+use chrono::NaiveDate;
+use chrono::NaiveDateTime;
+use chrono::NaiveTime;
+use lunch_picker::features::get_candidate_restaurants;
+use lunch_picker::features::get_restaurant_opening_hours;
+use lunch_picker::features::set_restaurant_opening_hours;
+use lunch_picker::features::CandidateOptions;
+use lunch_picker::features::OpeningHours;
+
+use sqlx::SqlitePool;
+
+fn at(date: &str, time: &str) -> Option<NaiveDateTime> {
+    Some(
+        NaiveDate::parse_from_str(date, "%Y-%m-%d")
+            .unwrap()
+            .and_time(NaiveTime::parse_from_str(time, "%H:%M").unwrap()),
+    )
+}
+
+async fn candidate_names(pool: &SqlitePool, options: &CandidateOptions) -> Result<Vec<String>> {
+    let actual = get_candidate_restaurants(vec![-1, -2], options, -1, pool).await?;
+    let mut names = actual
+        .iter()
+        .map(|r| r.name.as_str().to_string())
+        .collect::<Vec<_>>();
+    names.sort();
+    Ok(names)
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures(
+    "homies",
+    "restaurants",
+    "homies_favorite_restaurants",
+    "restaurant_opening_hours"
+))]
+async fn restaurants_closed_on_mondays_are_not_candidates(pool: SqlitePool) -> Result<()> {
+    let options = CandidateOptions {
+        at: at("2026-10-19", "12:00"),
+        ..Default::default()
+    };
+
+    let names = candidate_names(&pool, &options).await?;
+
+    assert!(!names.contains(&"Pizza".to_string()));
+    assert!(names.contains(&"Tacos".to_string()));
+    assert_eq!(7, names.len());
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures(
+    "homies",
+    "restaurants",
+    "homies_favorite_restaurants",
+    "restaurant_opening_hours"
+))]
+async fn holiday_exceptions_replace_the_weekly_schedule(pool: SqlitePool) -> Result<()> {
+    let options = CandidateOptions {
+        at: at("2026-10-20", "12:00"),
+        ..Default::default()
+    };
+
+    let names = candidate_names(&pool, &options).await?;
+
+    assert!(names.contains(&"Pizza".to_string()));
+    assert!(!names.contains(&"Tacos".to_string()));
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures(
+    "homies",
+    "restaurants",
+    "homies_favorite_restaurants",
+    "restaurant_opening_hours"
+))]
+async fn restaurants_without_hours_are_always_candidates(pool: SqlitePool) -> Result<()> {
+    let options = CandidateOptions {
+        at: at("2026-10-21", "23:30"),
+        ..Default::default()
+    };
+
+    let names = candidate_names(&pool, &options).await?;
+
+    assert_eq!(6, names.len());
+    assert!(!names.contains(&"Pizza".to_string()));
+    assert!(!names.contains(&"Tacos".to_string()));
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants"))]
+async fn opening_hours_can_be_set_and_read_back(pool: SqlitePool) -> Result<()> {
+    let opening_hours: OpeningHours = "Tu-Su 11:00-15:00,17:00-22:00; 2026-12-25 off".parse()?;
+
+    set_restaurant_opening_hours("Thai".to_string(), &opening_hours, -1, &pool).await?;
+    let actual = get_restaurant_opening_hours("Thai".to_string(), -1, &pool).await?;
+
+    assert_eq!(opening_hours, actual);
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants", "homies_favorite_restaurants"))]
+async fn closed_days_alone_are_known_hours(pool: SqlitePool) -> Result<()> {
+    let opening_hours: OpeningHours = "Mo off".parse()?;
+
+    set_restaurant_opening_hours("Thai".to_string(), &opening_hours, -1, &pool).await?;
+
+    assert_eq!(
+        opening_hours,
+        get_restaurant_opening_hours("Thai".to_string(), -1, &pool).await?
+    );
+    for day in ["2026-10-19", "2026-10-20"] {
+        let options = CandidateOptions {
+            at: at(day, "12:00"),
+            ..Default::default()
+        };
+        assert!(!candidate_names(&pool, &options)
+            .await?
+            .contains(&"Thai".to_string()));
+    }
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants"))]
+async fn hours_of_unknown_restaurants_cannot_be_set(pool: SqlitePool) -> Result<()> {
+    let actual =
+        set_restaurant_opening_hours("Nowhere".to_string(), &OpeningHours::default(), -1, &pool)
+            .await;
+
+    assert_eq!(
+        format!("Restaurant not found: {:?}", "Nowhere"),
+        actual.unwrap_err().to_string()
+    );
+    Ok(())
+}
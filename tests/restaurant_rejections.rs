@@ -0,0 +1,41 @@
+#![cfg(feature = "sqlite_tests")]
+
+use anyhow::Result;
+use lunch_picker::features::get_candidate_restaurants;
+use lunch_picker::features::get_recently_rejected_restaurants;
+use lunch_picker::features::reject_restaurants;
+use lunch_picker::features::CandidateOptions;
+use lunch_picker::features::RestaurantId;
+
+use sqlx::SqlitePool;
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants"))]
+async fn rejected_restaurants_are_remembered(pool: SqlitePool) -> Result<()> {
+    // -4 belongs to another user
+    let rejected: Vec<RestaurantId> = vec![(-1).into(), (-2).into(), (-4).into()];
+
+    reject_restaurants(&rejected, -1, &pool).await?;
+    reject_restaurants(&rejected[..1], -1, &pool).await?;
+
+    assert_eq!(
+        vec![RestaurantId::from(-2), RestaurantId::from(-1)],
+        get_recently_rejected_restaurants(-1, &pool).await?
+    );
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants", "homies_favorite_restaurants"))]
+async fn rerolled_candidates_exclude_what_was_shown(pool: SqlitePool) -> Result<()> {
+    let options = CandidateOptions {
+        exclude: vec![(-1).into(), (-2).into()],
+        ..Default::default()
+    };
+
+    let actual = get_candidate_restaurants(vec![-1, -2], &options, -1, &pool).await?;
+
+    assert_eq!(6, actual.len());
+    assert!(actual.iter().all(|r| !options.exclude.contains(&r.id)));
+    Ok(())
+}
@@ -0,0 +1,152 @@
+#![cfg(feature = "sqlite_tests")]
+
+use anyhow::Result;
+use chrono::Datelike;
+use chrono::Days;
+use chrono::Local;
+use lunch_picker::features::set_restaurant_opening_hours;
+use lunch_picker::features::simulate;
+use lunch_picker::features::OpeningHours;
+use lunch_picker::features::SimulationPolicy;
+use lunch_picker::features::SimulationSettings;
+
+use sqlx::SqlitePool;
+
+async fn count_recents(pool: &SqlitePool) -> Result<i64> {
+    Ok(
+        sqlx::query_scalar("select count(*) from recent_restaurants")
+            .fetch_one(pool)
+            .await?,
+    )
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures(
+    "homies",
+    "restaurants",
+    "homies_favorite_restaurants",
+    "recent_restaurants"
+))]
+async fn simulates_without_touching_the_database(pool: SqlitePool) -> Result<()> {
+    let recents = count_recents(&pool).await?;
+
+    let report = simulate(
+        vec![-1, -2],
+        10,
+        SimulationSettings::default(),
+        None,
+        -1,
+        &pool,
+    )
+    .await?;
+
+    assert_eq!(10, report.lunches.len());
+    assert_eq!(0, report.empty_days());
+    // a restaurant isn't offered again while it is among the latest lunches
+    assert!(report.variety() > 5);
+    assert_eq!(
+        vec![10, 10],
+        report
+            .satisfaction
+            .iter()
+            .map(|s| s.lunches)
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(recents, count_recents(&pool).await?);
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures(
+    "homies",
+    "restaurants",
+    "homies_favorite_restaurants",
+    "recent_restaurants"
+))]
+async fn exploring_picks_what_nobody_has_favorited(pool: SqlitePool) -> Result<()> {
+    let report = simulate(
+        vec![-1, -2],
+        5,
+        SimulationSettings {
+            policy: SimulationPolicy::Explore,
+            ..Default::default()
+        },
+        None,
+        -1,
+        &pool,
+    )
+    .await?;
+
+    assert_eq!(5, report.variety());
+    assert!(report.satisfaction.iter().all(|s| s.satisfied == 0));
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants", "homies_favorite_restaurants"))]
+async fn the_recency_window_decides_when_favorites_come_back(pool: SqlitePool) -> Result<()> {
+    let simulate_window = |recency_window| {
+        simulate(
+            vec![-2],
+            4,
+            SimulationSettings {
+                recency_window: Some(recency_window),
+                ignore_fairness: true,
+                ..Default::default()
+            },
+            None,
+            -1,
+            &pool,
+        )
+    };
+
+    // Bob has two favorites, so they take turns
+    let short = simulate_window(1).await?;
+    assert_eq!(0, short.empty_days());
+    assert_eq!(2, short.variety());
+
+    // and run out once both are among the latest lunches
+    let long = simulate_window(2).await?;
+    assert_eq!(2, long.empty_days());
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants", "homies_favorite_restaurants"))]
+async fn simulated_days_follow_the_weekly_opening_hours(pool: SqlitePool) -> Result<()> {
+    // the third simulated day
+    let closed_on = (Local::now().date_naive() + Days::new(3)).weekday();
+    let days = ["Mo", "Tu", "We", "Th", "Fr", "Sa", "Su"];
+    let opening_hours: OpeningHours = format!(
+        "Mo-Su 11:00-15:00; {} off",
+        days[closed_on.num_days_from_monday() as usize]
+    )
+    .parse()?;
+    // Bob's favorites
+    for restaurant in ["Spanish", "Cuban"] {
+        set_restaurant_opening_hours(restaurant.to_string(), &opening_hours, -1, &pool).await?;
+    }
+
+    let report = simulate(
+        vec![-2],
+        7,
+        SimulationSettings {
+            recency_window: Some(0),
+            ..Default::default()
+        },
+        None,
+        -1,
+        &pool,
+    )
+    .await?;
+
+    assert_eq!(
+        vec![true, true, false, true, true, true, true],
+        report
+            .lunches
+            .iter()
+            .map(|l| l.is_some())
+            .collect::<Vec<_>>()
+    );
+    Ok(())
+}
@@ -0,0 +1,81 @@
+#![cfg(feature = "sqlite_tests")]
+
+use anyhow::Result;
+use chrono::Local;
+use lunch_picker::features::add_recent_restaurant_for_homies;
+use lunch_picker::features::add_to_wishlist;
+use lunch_picker::features::get_candidate_restaurants;
+use lunch_picker::features::get_wishlist;
+use lunch_picker::features::CandidateOptions;
+use lunch_picker::features::MealSlot;
+use lunch_picker::features::RestaurantId;
+use lunch_picker::features::RestaurantWishlistError;
+
+use sqlx::SqlitePool;
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants"))]
+async fn wishlist_is_shared_or_per_homie(pool: SqlitePool) -> Result<()> {
+    add_to_wishlist("Mexican".to_string(), None::<String>, -1, &pool).await?;
+    add_to_wishlist("Korean".to_string(), Some("Alice".to_string()), -1, &pool).await?;
+    add_to_wishlist("Korean".to_string(), Some("Alice".to_string()), -1, &pool).await?;
+
+    let actual = get_wishlist(-1, &pool)
+        .await?
+        .into_iter()
+        .map(|w| (w.restaurant_name, w.homie_name))
+        .collect::<Vec<_>>();
+
+    assert_eq!(
+        vec![
+            ("Korean".to_string(), Some("Alice".to_string())),
+            ("Mexican".to_string(), None)
+        ],
+        actual
+    );
+    assert!(matches!(
+        add_to_wishlist("Nowhere".to_string(), None::<String>, -1, &pool).await,
+        Err(RestaurantWishlistError::GetRestaurantError(_))
+    ));
+    Ok(())
+}
+
+#[cfg_attr(not(feature = "sqlite_tests"), ignore)]
+#[sqlx::test(fixtures("homies", "restaurants", "homies_favorite_restaurants"))]
+async fn wishlisted_restaurants_come_first_until_visited(pool: SqlitePool) -> Result<()> {
+    add_to_wishlist("Mexican".to_string(), None::<String>, -1, &pool).await?;
+    // Zoe isn't home
+    add_to_wishlist("Korean".to_string(), Some("Zoe".to_string()), -1, &pool).await?;
+
+    let actual =
+        get_candidate_restaurants(vec![-1, -2], &CandidateOptions::default(), -1, &pool).await?;
+
+    assert_eq!(9, actual.len());
+    assert_eq!(RestaurantId::from(-6), actual[0].id);
+    assert!(actual.iter().all(|r| r.id != RestaurantId::from(-14)));
+
+    add_recent_restaurant_for_homies(
+        vec![-1],
+        -6,
+        MealSlot::Lunch,
+        Local::now().date_naive(),
+        false,
+        -1,
+        &pool,
+    )
+    .await?;
+
+    let actual =
+        get_candidate_restaurants(vec![-1, -2], &CandidateOptions::default(), -1, &pool).await?;
+
+    assert!(actual.iter().all(|r| r.id != RestaurantId::from(-6)));
+    assert_eq!(
+        vec!["Korean".to_string()],
+        get_wishlist(-1, &pool)
+            .await?
+            .into_iter()
+            .map(|w| w.restaurant_name)
+            .collect::<Vec<_>>()
+    );
+    Ok(())
+}